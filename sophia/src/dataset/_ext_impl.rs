@@ -25,6 +25,16 @@ where
     fn quads(&self) -> DQuadSource<Self> {
         Box::new(<[Q]>::iter(self).map(StreamedQuad::by_ref).as_quad_source())
     }
+
+    #[inline]
+    fn len(&self) -> DResult<Self, usize> {
+        Ok(<[Q]>::len(self))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> DResult<Self, bool> {
+        Ok(<[Q]>::is_empty(self))
+    }
 }
 
 impl<Q> Dataset for Vec<Q>
@@ -38,6 +48,16 @@ where
     fn quads(&self) -> DQuadSource<Self> {
         Box::new(<[Q]>::iter(self).map(StreamedQuad::by_ref).as_quad_source())
     }
+
+    #[inline]
+    fn len(&self) -> DResult<Self, usize> {
+        Ok(Vec::len(self))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> DResult<Self, bool> {
+        Ok(Vec::is_empty(self))
+    }
 }
 
 impl MutableDataset for Vec<([BoxTerm; 3], Option<BoxTerm>)> {
@@ -87,6 +107,11 @@ impl MutableDataset for Vec<([BoxTerm; 3], Option<BoxTerm>)> {
             Ok(false)
         }
     }
+
+    fn clear(&mut self) -> MDResult<Self, ()> {
+        Vec::clear(self);
+        Ok(())
+    }
 }
 
 impl<Q, S: ::std::hash::BuildHasher> Dataset for HashSet<Q, S>
@@ -100,6 +125,16 @@ where
     fn quads(&self) -> DQuadSource<Self> {
         Box::from(self.iter().map(StreamedQuad::by_ref).as_quad_source())
     }
+
+    #[inline]
+    fn len(&self) -> DResult<Self, usize> {
+        Ok(HashSet::len(self))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> DResult<Self, bool> {
+        Ok(HashSet::is_empty(self))
+    }
 }
 
 impl<S: ::std::hash::BuildHasher> MutableDataset for HashSet<([BoxTerm; 3], Option<BoxTerm>), S> {
@@ -143,6 +178,10 @@ impl<S: ::std::hash::BuildHasher> MutableDataset for HashSet<([BoxTerm; 3], Opti
         let g = g.map(|n| n.into());
         Ok(HashSet::remove(self, &([s, p, o], g)))
     }
+    fn clear(&mut self) -> MDResult<Self, ()> {
+        HashSet::clear(self);
+        Ok(())
+    }
 }
 
 impl<T, S: ::std::hash::BuildHasher> SetDataset for HashSet<T, S> where T: Eq + Hash + Triple {}
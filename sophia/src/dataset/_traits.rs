@@ -1,18 +1,25 @@
 // this module is transparently re-exported by its parent `dataset`
 
-use std::collections::HashSet;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::{HashMap, HashSet};
 use std::convert::Infallible;
 use std::error::Error;
+use std::hash::{Hash, Hasher};
 use std::marker::PhantomData;
 
 use resiter::filter::*;
 use resiter::map::*;
 
 use crate::dataset::adapter::DatasetGraph;
+use crate::graph::inmem::FastGraph;
+use crate::graph::MutableGraph;
+use crate::ns::rdf;
+use crate::parser::QuadParser;
 use crate::quad::stream::*;
 use crate::quad::streaming_mode::*;
 use crate::quad::*;
-use crate::triple::stream::StreamResult;
+use crate::triple::stream::{StreamResult, TripleSource};
+use crate::triple::Triple;
 use sophia_term::matcher::*;
 use sophia_term::*;
 
@@ -279,6 +286,29 @@ pub trait Dataset {
         Box::new(self.quads_with_spg(s, p, g).filter_ok(move |q| q.o() == o))
     }
 
+    /// The number of quads in this dataset.
+    ///
+    /// The default implementation iterates over all quads,
+    /// but implementations keeping track of their size (such as in-memory datasets)
+    /// answer in constant time.
+    fn len(&self) -> DResult<Self, usize> {
+        let mut len = 0;
+        for q in self.quads() {
+            q?;
+            len += 1;
+        }
+        Ok(len)
+    }
+
+    /// Return `true` if this dataset contains no quad.
+    fn is_empty(&self) -> DResult<Self, bool> {
+        match self.quads().next() {
+            None => Ok(true),
+            Some(Ok(_)) => Ok(false),
+            Some(Err(err)) => Err(err),
+        }
+    }
+
     /// Return `true` if this dataset contains the given quad.
     fn contains<T, U, V, W>(
         &self,
@@ -552,6 +582,18 @@ pub trait Dataset {
         }
     }
 
+    /// Borrows the default graph of this dataset
+    /// (a shortcut for [`graph(None)`](#method.graph)).
+    fn default_graph(&self) -> DatasetGraph<Self, &Self, Option<BoxTerm>> {
+        self.graph(None::<&BoxTerm>)
+    }
+
+    /// Borrows mutably the default graph of this dataset
+    /// (a shortcut for [`graph_mut(None)`](#method.graph_mut)).
+    fn default_graph_mut(&mut self) -> DatasetGraph<Self, &mut Self, Option<BoxTerm>> {
+        self.graph_mut(None::<&BoxTerm>)
+    }
+
     /// Borrows a graph containing the union of all graphs matched by `gmatcher`
     fn union_graph<T>(&self, gmatcher: T) -> DatasetGraph<Self, &Self, T>
     where
@@ -563,6 +605,138 @@ pub trait Dataset {
             _phantom: PhantomData,
         }
     }
+
+    /// Whether the conjunction of the given quad patterns has at least one solution
+    /// in this dataset (similar to a SPARQL `ASK` query).
+    ///
+    /// Each pattern is a `[subject, predicate, object, graph name]` array,
+    /// where any term may be a variable.
+    /// Variables shared by several patterns must be bound to the same term in all of them.
+    ///
+    /// NB: like in SPARQL's `GRAPH ?g` construct,
+    /// a variable in the graph name position only matches named graphs;
+    /// the default graph can not be queried with this method.
+    ///
+    /// The search stops as soon as a solution is found.
+    fn ask<T>(&self, patterns: &[[Term<T>; 4]]) -> DResult<Self, bool>
+    where
+        T: TermData,
+    {
+        solve_from(self, patterns, &mut HashMap::new(), &mut |_| true)
+    }
+
+    /// All the solutions of the conjunction of the given quad patterns in this dataset
+    /// (similar to a SPARQL `SELECT *` query).
+    ///
+    /// Patterns are interpreted as in [`ask`](#method.ask).
+    /// Each solution maps the name of every variable to the term it is bound to;
+    /// in particular, a variable in the graph name position is bound to the name of a graph,
+    /// which tells in which graph(s) the other patterns hold.
+    ///
+    /// NB: all the solutions are computed before this method returns.
+    /// The search is a backtracking over nested iterators borrowing the patterns,
+    /// which can not be suspended into an iterator returned by a trait method.
+    /// Use [`ask`](#method.ask) if only the existence of a solution matters.
+    fn match_bgp<T>(
+        &self,
+        patterns: &[[Term<T>; 4]],
+    ) -> DResult<Self, Vec<HashMap<String, BoxTerm>>>
+    where
+        T: TermData,
+    {
+        let mut solutions = vec![];
+        solve_from(self, patterns, &mut HashMap::new(), &mut |b| {
+            solutions.push(b.clone());
+            false
+        })?;
+        Ok(solutions)
+    }
+
+    /// Split this dataset into one [`FastGraph`](../graph/inmem/type.FastGraph.html)
+    /// per graph name (`None` denoting the default graph).
+    ///
+    /// See [`FastDataset::from_graph_map`](inmem/type.FastDataset.html#method.from_graph_map)
+    /// for the inverse operation.
+    fn into_graph_map(self) -> DResult<Self, HashMap<Option<BoxTerm>, FastGraph>>
+    where
+        Self: Sized,
+    {
+        let mut map: HashMap<Option<BoxTerm>, FastGraph> = HashMap::new();
+        for q in self.quads() {
+            let q = q?;
+            let g = map.entry(q.g().map(BoxTerm::from)).or_default();
+            // FastGraph::MutationError is Infallible
+            g.insert(q.s(), q.p(), q.o()).unwrap();
+        }
+        Ok(map)
+    }
+}
+
+/// Call `on_solution` on each solution of `patterns` in `d` compatible with the bindings `b`,
+/// until it returns true.
+///
+/// Return whether `on_solution` returned true;
+/// in that case, `b` may contain additional bindings.
+fn solve_from<D, T, F>(
+    d: &D,
+    patterns: &[[Term<T>; 4]],
+    b: &mut HashMap<String, BoxTerm>,
+    on_solution: &mut F,
+) -> DResult<D, bool>
+where
+    D: Dataset + ?Sized,
+    T: TermData,
+    F: FnMut(&HashMap<String, BoxTerm>) -> bool,
+{
+    let (pattern, rest) = match patterns.split_first() {
+        None => return Ok(on_solution(b)),
+        Some(split) => split,
+    };
+    let m: Vec<AnyOrExactly<BoxTerm>> = pattern
+        .iter()
+        .map(|t| match t {
+            Term::Variable(var) => b.get(var.as_ref()).cloned().into(),
+            _ => AnyOrExactly::Exactly(t.into()),
+        })
+        .collect();
+    let mg: AnyOrExactly<Option<BoxTerm>> = match &m[3] {
+        AnyOrExactly::Any => AnyOrExactly::Any,
+        AnyOrExactly::Exactly(g) => AnyOrExactly::Exactly(Some(g.clone())),
+    };
+    for q in d.quads_matching(&m[0], &m[1], &m[2], &mg) {
+        let q = q?;
+        let g = match q.g() {
+            Some(g) => g,
+            None => continue,
+        };
+        let mut added = vec![];
+        let mut consistent = true;
+        for (pt, t) in pattern.iter().zip([q.s(), q.p(), q.o(), g].iter()) {
+            if let Term::Variable(var) = pt {
+                let vname: &str = var.as_ref();
+                match b.get(vname) {
+                    Some(bound) => {
+                        // variable bound by this very pattern
+                        if bound != *t {
+                            consistent = false;
+                            break;
+                        }
+                    }
+                    None => {
+                        b.insert(vname.to_string(), BoxTerm::from(*t));
+                        added.push(vname);
+                    }
+                }
+            }
+        }
+        if consistent && solve_from(d, rest, b, on_solution)? {
+            return Ok(true);
+        }
+        for vname in added {
+            b.remove(vname);
+        }
+    }
+    Ok(false)
 }
 
 /// Type alias for results produced by a mutable dataset.
@@ -619,6 +793,80 @@ pub trait MutableDataset: Dataset {
         V: TermData,
         W: TermData;
 
+    /// Insert the given quad in this dataset,
+    /// after replacing its literals by their canonical form
+    /// (see [`Term::canonicalized`](../term/enum.Term.html#method.canonicalized)).
+    ///
+    /// This is an opt-in alternative to [`insert`](#tymethod.insert),
+    /// ensuring that value-equal literals such as `"+5"^^xsd:integer` and `"5"^^xsd:integer`
+    /// are stored with a single lexical form.
+    /// Literals whose datatype has no supported canonical form are inserted as is.
+    fn insert_canonical<T, U, V, W>(
+        &mut self,
+        s: &Term<T>,
+        p: &Term<U>,
+        o: &Term<V>,
+        g: Option<&Term<W>>,
+    ) -> MDResult<Self, bool>
+    where
+        T: TermData,
+        U: TermData,
+        V: TermData,
+        W: TermData,
+    {
+        match (
+            s.canonicalized(),
+            p.canonicalized(),
+            o.canonicalized(),
+            g.and_then(Term::canonicalized),
+        ) {
+            (None, None, None, None) => self.insert(s, p, o, g),
+            (cs, cp, co, cg) => {
+                let g = cg.or_else(|| g.map(BoxTerm::from));
+                self.insert(
+                    &cs.unwrap_or_else(|| s.into()),
+                    &cp.unwrap_or_else(|| p.into()),
+                    &co.unwrap_or_else(|| o.into()),
+                    g.as_ref(),
+                )
+            }
+        }
+    }
+
+    /// Insert the given quad in this dataset,
+    /// unless it belongs to a named graph
+    /// and its triple `s p o` is already in the default graph.
+    ///
+    /// This is an opt-in alternative to [`insert`](#tymethod.insert),
+    /// for importing data without repeating in named graphs
+    /// what the default graph already states.
+    /// Quads of the default graph are inserted as with `insert`.
+    ///
+    /// Return `true` iff the quad was actually inserted.
+    fn insert_unless_in_default<T, U, V, W>(
+        &mut self,
+        s: &Term<T>,
+        p: &Term<U>,
+        o: &Term<V>,
+        g: Option<&Term<W>>,
+    ) -> MDResult<Self, bool>
+    where
+        T: TermData,
+        U: TermData,
+        V: TermData,
+        W: TermData,
+        <Self as Dataset>::Error: Into<Self::MutationError>,
+    {
+        if g.is_some()
+            && self
+                .contains(s, p, o, None::<&Term<W>>)
+                .map_err(Into::into)?
+        {
+            return Ok(false);
+        }
+        self.insert(s, p, o, g)
+    }
+
     /// Insert into this dataset all quads from the given source.
     #[inline]
     fn insert_all<TS>(
@@ -638,6 +886,57 @@ pub trait MutableDataset: Dataset {
         .and(Ok(c))
     }
 
+    /// Insert into the graph named `graph` all triples from the given source
+    /// (`None` denoting the default graph).
+    ///
+    /// Return the number of quads actually inserted
+    /// (see [`insert`](#tymethod.insert)).
+    #[inline]
+    fn insert_all_into<TS, W>(
+        &mut self,
+        graph: Option<&Term<W>>,
+        src: &mut TS,
+    ) -> StreamResult<usize, TS::Error, <Self as MutableDataset>::MutationError>
+    where
+        TS: TripleSource,
+        W: TermData,
+    {
+        let mut c = 0;
+        src.try_for_each_triple(|t| -> MDResult<Self, ()> {
+            if self.insert(t.s(), t.p(), t.o(), graph)? {
+                c += 1;
+            }
+            Ok(())
+        })
+        .and(Ok(c))
+    }
+
+    /// Parse `data` with `parser`, and insert the resulting quads into this dataset.
+    ///
+    /// Return the number of quads actually inserted.
+    ///
+    /// NB: datasets storing their terms in a [`TermIndexMap`]
+    /// (such as [`FastDataset`](inmem/type.FastDataset.html))
+    /// look up every parsed term in their own term table,
+    /// and only copy (and intern) the terms that are not already there,
+    /// so that terms shared by several loaded files are only stored once.
+    ///
+    /// [`TermIndexMap`]: ../term/index_map/trait.TermIndexMap.html
+    fn parse_into<P, T>(
+        &mut self,
+        parser: &P,
+        data: T,
+    ) -> StreamResult<
+        usize,
+        <P::Source as QuadSource>::Error,
+        <Self as MutableDataset>::MutationError,
+    >
+    where
+        P: QuadParser<T>,
+    {
+        self.insert_all(&mut parser.parse(data))
+    }
+
     /// Remove from this dataset all quads from the given source.
     #[inline]
     fn remove_all<TS>(
@@ -732,6 +1031,170 @@ pub trait MutableDataset: Dataset {
             .map_err(|err| err.inner_into())?;
         Ok(())
     }
+
+    /// Insert in graph `target` a copy of every quad of graph `source`.
+    /// The quads of `source` are left untouched.
+    ///
+    /// Either graph name may be `None`, denoting the default graph.
+    ///
+    /// Return the number of quads actually inserted
+    /// (see [`insert`](#tymethod.insert)).
+    fn copy_graph<T, U>(
+        &mut self,
+        source: Option<&Term<T>>,
+        target: Option<&Term<U>>,
+    ) -> MDResult<Self, usize>
+    where
+        T: TermData,
+        U: TermData,
+        <Self as Dataset>::Error: Into<Self::MutationError>,
+    {
+        let to_copy = self
+            .quads_with_g(source)
+            .map_ok(|q| {
+                [
+                    BoxTerm::from(q.s()),
+                    BoxTerm::from(q.p()),
+                    BoxTerm::from(q.o()),
+                ]
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)?;
+        let mut c = 0;
+        for [s, p, o] in to_copy.iter() {
+            if self.insert(s, p, o, target)? {
+                c += 1;
+            }
+        }
+        Ok(c)
+    }
+
+    /// Remove all quads from this dataset.
+    ///
+    /// Note that the default implementation is rather naive,
+    /// and could be improved in specific implementations of the trait.
+    fn clear(&mut self) -> MDResult<Self, ()>
+    where
+        <Self as Dataset>::Error: Into<Self::MutationError>,
+    {
+        let mut to_remove = vec![];
+        for q in self.quads() {
+            let q = q.map_err(Into::<Self::MutationError>::into)?;
+            to_remove.push((
+                [
+                    BoxTerm::from(q.s()),
+                    BoxTerm::from(q.p()),
+                    BoxTerm::from(q.o()),
+                ],
+                q.g().map(BoxTerm::from),
+            ));
+        }
+        for ([s, p, o], g) in to_remove.iter() {
+            self.remove(s, p, o, g.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Remove all quads from the graph named `name`
+    /// (`None` denoting the default graph),
+    /// and return the number of quads actually removed.
+    ///
+    /// The other graphs of this dataset are left untouched.
+    ///
+    /// NB: the datasets provided by this crate do not keep track of graph names
+    /// independently of the quads they contain,
+    /// so a cleared named graph is no longer listed in [`graph_names`](trait.Dataset.html#method.graph_names).
+    fn clear_graph<T>(&mut self, name: Option<&Term<T>>) -> MDResult<Self, usize>
+    where
+        T: TermData,
+        <Self as Dataset>::Error: Into<Self::MutationError>,
+    {
+        let to_remove = self
+            .quads_with_g(name)
+            .map_ok(|q| {
+                [
+                    BoxTerm::from(q.s()),
+                    BoxTerm::from(q.p()),
+                    BoxTerm::from(q.o()),
+                ]
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()
+            .map_err(Into::into)?;
+        let mut c = 0;
+        for [s, p, o] in to_remove.iter() {
+            if self.remove(s, p, o, name)? {
+                c += 1;
+            }
+        }
+        Ok(c)
+    }
+
+    /// Insert `quad` in this dataset, and annotate it with the given `annotations`
+    /// (pairs of predicate and object), e.g. to record its provenance.
+    ///
+    /// A fresh blank node is minted to identify the statement,
+    /// and returned by this method.
+    /// In the graph named `graph` (`None` denoting the default graph),
+    /// it is described with the [RDF reification] vocabulary
+    /// (`rdf:Statement`, `rdf:subject`, `rdf:predicate` and `rdf:object`),
+    /// and with one triple per annotation.
+    /// Note that the graph name of `quad` is not recorded by that vocabulary;
+    /// it can be passed as an annotation if required.
+    ///
+    /// Each call mints a distinct statement node,
+    /// even when annotating the same quad several times.
+    ///
+    /// [RDF reification]: https://www.w3.org/TR/rdf11-mt/#reification
+    fn annotate_quad<Q, T, U>(
+        &mut self,
+        quad: &Q,
+        annotations: &[[Term<T>; 2]],
+        graph: Option<&Term<U>>,
+    ) -> MDResult<Self, BoxTerm>
+    where
+        Q: Quad,
+        T: TermData,
+        U: TermData,
+        <Self as Dataset>::Error: Into<Self::MutationError>,
+    {
+        let mut hasher = DefaultHasher::new();
+        (quad.s(), quad.p(), quad.o(), quad.g()).hash(&mut hasher);
+        let mut n = hasher.finish();
+        let node = loop {
+            let node = BoxTerm::new_bnode(format!("stmt{:016x}", n)).unwrap();
+            let used = self
+                .quads_with_s(&node)
+                .next()
+                .transpose()
+                .map_err(Into::into)?
+                .is_some()
+                || self
+                    .quads_with_o(&node)
+                    .next()
+                    .transpose()
+                    .map_err(Into::into)?
+                    .is_some()
+                || self
+                    .quads_with_g(Some(&node))
+                    .next()
+                    .transpose()
+                    .map_err(Into::into)?
+                    .is_some();
+            if !used {
+                break node;
+            }
+            n = n.wrapping_add(1);
+        };
+        self.insert(quad.s(), quad.p(), quad.o(), quad.g())?;
+        self.insert(&node, &rdf::type_, &rdf::Statement, graph)?;
+        self.insert(&node, &rdf::subject, quad.s(), graph)?;
+        self.insert(&node, &rdf::predicate, quad.p(), graph)?;
+        self.insert(&node, &rdf::object, quad.o(), graph)?;
+        for [p, o] in annotations {
+            self.insert(&node, p, o, graph)?;
+        }
+        Ok(node)
+    }
 }
 
 /// Marker trait constraining the semantics of
@@ -186,7 +186,7 @@ pub(crate) mod test {
     use crate::dataset::test::*;
     use crate::dataset::MDResult;
     use crate::dataset::*;
-    use crate::ns::rdfs;
+    use crate::ns::{rdf, rdfs};
     use sophia_term::BoxTerm;
     use std::collections::HashSet;
 
@@ -235,4 +235,22 @@ pub(crate) mod test {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_default_graph() -> MDResult<MyDataset, ()> {
+        let mut d = MyDataset::new();
+        populate(&mut d)?;
+        assert_eq!(d.default_graph().triples().count(), 4);
+        assert_eq!(d.default_graph().triples_with_p(&rdf::type_).count(), 4);
+        assert_eq!(d.default_graph().triples_with_o(&rdf::Property).count(), 2);
+        assert!(Graph::contains(&d.default_graph(), &C1, &rdf::type_, &rdfs::Class).unwrap());
+        assert!(!Graph::contains(&d.default_graph(), &C2, &rdfs::subClassOf, &C1).unwrap());
+
+        assert!(
+            MutableGraph::insert(&mut d.default_graph_mut(), &C2, &rdfs::subClassOf, &C1).unwrap()
+        );
+        assert!(Dataset::contains(&d, &C2, &rdfs::subClassOf, &C1, *DG)?);
+        assert_eq!(d.default_graph().triples().count(), 5);
+        Ok(())
+    }
 }
@@ -4,6 +4,9 @@ use std::hash::Hash;
 
 use sophia_term::*;
 
+use crate::dataset::Dataset;
+use crate::quad::Quad;
+
 /// A utility trait for implementing [`Dataset`] and [`MutableDataset`]
 /// based on an internal [`TermIndexMap`] for efficient storage.
 ///
@@ -74,6 +77,34 @@ pub trait IndexedDataset {
         V: TermData,
         W: TermData;
 
+    /// Remove all quads from this Dataset.
+    ///
+    /// The default implementation removes the quads one by one,
+    /// and should be overridden by implementations that can do better.
+    /// It panics if [`quads`](../trait.Dataset.html#tymethod.quads) yields an error,
+    /// so implementations whose `quads` may fail must override it.
+    fn clear_indexed(&mut self)
+    where
+        Self: Dataset + Sized,
+    {
+        // errors can not be reported here,
+        // but in-memory datasets built on IndexedDataset never raise any
+        // (their Dataset::Error is Infallible)
+        let to_remove: Vec<([BoxTerm; 3], Option<BoxTerm>)> = self
+            .quads()
+            .map(Result::unwrap)
+            .map(|q| {
+                (
+                    [q.s().into(), q.p().into(), q.o().into()],
+                    q.g().map(BoxTerm::from),
+                )
+            })
+            .collect();
+        for ([s, p, o], g) in to_remove.iter() {
+            self.remove_indexed(s, p, o, g.as_ref());
+        }
+    }
+
     fn shrink_to_fit(&mut self);
 }
 
@@ -107,6 +138,10 @@ macro_rules! impl_mutable_dataset_for_indexed_dataset {
         {
             Ok(self.remove_indexed(s, p, o, g).is_some())
         }
+        fn clear(&mut self) -> MDResult<Self, ()> {
+            self.clear_indexed();
+            Ok(())
+        }
     };
 }
 
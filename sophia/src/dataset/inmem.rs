@@ -17,9 +17,13 @@
 //! [`FastDataset`]: type.FastDataset.html
 //! [`LightDataset`]: type.LightDataset.html
 
+use std::collections::HashMap;
+
 use super::_traits::*;
 use super::indexed::*;
-use crate::graph::inmem::TermIndexMapU;
+use crate::graph::inmem::{FastGraph, TermIndexMapU};
+use crate::graph::Graph;
+use crate::triple::Triple;
 use sophia_term::{factory::*, *};
 
 #[macro_use]
@@ -52,12 +56,92 @@ pub type FastDataset = FastWrapper<GenericDataset<u32, RcTermFactory>>;
 /// Fast to load but slow to query, with a relatively low memory footprint.
 pub type LightDataset = GenericDataset<u32, RcTermFactory>;
 
+impl FastDataset {
+    /// Assemble a dataset from one graph per graph name (`None` denoting the default graph),
+    /// such as the one returned by
+    /// [`Dataset::into_graph_map`](../trait.Dataset.html#method.into_graph_map).
+    pub fn from_graph_map(map: HashMap<Option<BoxTerm>, FastGraph>) -> FastDataset {
+        let mut d = FastDataset::new();
+        for (name, g) in map.iter() {
+            for t in g.triples() {
+                // FastGraph::Error and FastDataset::MutationError are Infallible
+                let t = t.unwrap();
+                d.insert(t.s(), t.p(), t.o(), name.as_ref()).unwrap();
+            }
+        }
+        d
+    }
+}
+
 #[cfg(test)]
 test_dataset_impl!(test_fastd, FastDataset);
 
 #[cfg(test)]
 test_dataset_impl!(test_lightd, LightDataset);
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::nq::NQuadsParser;
+    use crate::quad::Quad;
+
+    #[test]
+    fn parse_into_shares_terms() {
+        let file1 = r#"
+            <http://example.org/s1> <http://example.org/p> <http://example.org/o> .
+            <http://example.org/s1> <http://example.org/p> "foo" <http://example.org/g> .
+        "#;
+        let file2 = r#"
+            <http://example.org/s2> <http://example.org/p> <http://example.org/o> <http://example.org/g> .
+            <http://example.org/s1> <http://example.org/p> <http://example.org/o> .
+        "#;
+        let parser = NQuadsParser::default();
+        let mut d = FastDataset::new();
+
+        assert_eq!(d.parse_into(&parser, file1.as_bytes()).unwrap(), 2);
+        let terms = d.get_wrapped().get_wrapped().terms();
+        assert_eq!(terms.len(), 5);
+
+        assert_eq!(d.parse_into(&parser, file2.as_bytes()).unwrap(), 1);
+        assert_eq!(d.quads().count(), 3);
+        // only s2 was added to the term table
+        let terms = d.get_wrapped().get_wrapped().terms();
+        assert_eq!(terms.len(), 6);
+    }
+
+    #[test]
+    fn graph_map_round_trip() {
+        let nq = r#"
+            <http://example.org/s> <http://example.org/p> <http://example.org/o> .
+            _:b <http://example.org/p> "foo" .
+            <http://example.org/s> <http://example.org/p> _:b <http://example.org/g1> .
+            <http://example.org/s> <http://example.org/p> "bar"@en <http://example.org/g2> .
+        "#;
+        let mut d = FastDataset::new();
+        d.parse_into(&NQuadsParser::default(), nq.as_bytes())
+            .unwrap();
+        let quads = |d: &FastDataset| {
+            d.quads()
+                .map(|q| {
+                    let q = q.unwrap();
+                    let t: [BoxTerm; 3] = [q.s().into(), q.p().into(), q.o().into()];
+                    (t, q.g().map(BoxTerm::from))
+                })
+                .collect::<std::collections::HashSet<_>>()
+        };
+        let expected = quads(&d);
+
+        let map = d.into_graph_map().unwrap();
+        assert_eq!(map.len(), 3);
+        assert_eq!(map[&None].triples().count(), 2);
+        let g1 = BoxTerm::new_iri("http://example.org/g1").unwrap();
+        assert_eq!(map[&Some(g1)].triples().count(), 1);
+
+        let d = FastDataset::from_graph_map(map);
+        assert_eq!(quads(&d), expected);
+    }
+}
+
 /// Flavours of Dataset implementations with a smaller memory-footprint.
 ///
 /// The trade-off is that these implementations can only contain a small number (2^16) of terms.
@@ -118,14 +118,15 @@ where
         if let Some(gi) = self.wrapped.get_index_for_graph_name(g) {
             if let Some(si) = self.wrapped.get_index(s) {
                 if let Some(pi) = self.wrapped.get_index(p) {
-                    let g = self.wrapped.get_graph_name(gi).unwrap();
-                    let s = self.wrapped.get_term(si).unwrap();
-                    let p = self.wrapped.get_term(pi).unwrap();
-                    let ois = self.gsp2o.get(&[gi, si, pi]).unwrap();
-                    return Box::new(ois.iter().map(move |oi| {
-                        let o = self.wrapped.get_term(*oi).unwrap();
-                        Ok(StreamedQuad::by_term_refs(s, p, o, g))
-                    }));
+                    if let Some(ois) = self.gsp2o.get(&[gi, si, pi]) {
+                        let g = self.wrapped.get_graph_name(gi).unwrap();
+                        let s = self.wrapped.get_term(si).unwrap();
+                        let p = self.wrapped.get_term(pi).unwrap();
+                        return Box::new(ois.iter().map(move |oi| {
+                            let o = self.wrapped.get_term(*oi).unwrap();
+                            Ok(StreamedQuad::by_term_refs(s, p, o, g))
+                        }));
+                    }
                 }
             }
         }
@@ -171,6 +172,13 @@ where
         }
     }
 
+    #[inline]
+    fn idw_hook_clear_indexed(&mut self) {
+        self.g2s.clear();
+        self.gs2p.clear();
+        self.gsp2o.clear();
+    }
+
     #[inline]
     fn idw_hook_shrink_to_fit(&mut self) {
         self.g2s.shrink_to_fit();
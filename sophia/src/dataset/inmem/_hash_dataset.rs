@@ -41,14 +41,10 @@ where
         }
     }
 
+    /// The term index map where this dataset interns its terms.
     #[inline]
-    pub fn len(&self) -> usize {
-        self.quads.len()
-    }
-
-    #[inline]
-    pub fn is_empty(&self) -> bool {
-        self.quads.is_empty()
+    pub fn terms(&self) -> &I {
+        &self.terms
     }
 }
 
@@ -149,6 +145,12 @@ where
         None
     }
 
+    fn clear_indexed(&mut self) {
+        // the capacity of self.quads is kept, in case the dataset is re-populated
+        self.terms = I::default();
+        self.quads.clear();
+    }
+
     #[inline]
     fn shrink_to_fit(&mut self) {
         self.terms.shrink_to_fit();
@@ -176,6 +178,14 @@ where
             ))
         }))
     }
+
+    fn len(&self) -> DResult<Self, usize> {
+        Ok(self.quads.len())
+    }
+
+    fn is_empty(&self) -> DResult<Self, bool> {
+        Ok(self.quads.is_empty())
+    }
 }
 
 impl<I> MutableDataset for HashDataset<I>
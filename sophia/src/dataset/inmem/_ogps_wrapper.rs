@@ -170,6 +170,13 @@ where
         }
     }
 
+    #[inline]
+    fn idw_hook_clear_indexed(&mut self) {
+        self.o2g.clear();
+        self.og2p.clear();
+        self.ogp2s.clear();
+    }
+
     #[inline]
     fn idw_hook_shrink_to_fit(&mut self) {
         self.o2g.shrink_to_fit();
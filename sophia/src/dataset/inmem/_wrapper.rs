@@ -238,6 +238,18 @@ pub trait DatasetWrapper {
         self.get_wrapped().contains(s, p, o, g)
     }
 
+    #[inline]
+    /// Mimmic the [`len`](../trait.Dataset.html#method.len) method.
+    fn dw_len(&self) -> DResult<Self::Wrapped, usize> {
+        self.get_wrapped().len()
+    }
+
+    #[inline]
+    /// Mimmic the [`is_empty`](../trait.Dataset.html#method.is_empty) method.
+    fn dw_is_empty(&self) -> DResult<Self::Wrapped, bool> {
+        self.get_wrapped().is_empty()
+    }
+
     #[inline]
     /// Mimmic the [`subjects`](../trait.Dataset.html#method.subjects) method.
     fn dw_subjects(&self) -> DResultTermSet<Self::Wrapped> {
@@ -509,6 +521,16 @@ macro_rules! impl_dataset_for_wrapper {
             DatasetWrapper::dw_contains(self, s, p, o, g)
         }
 
+        #[inline]
+        fn len(&self) -> $crate::dataset::DResult<Self, usize> {
+            DatasetWrapper::dw_len(self)
+        }
+
+        #[inline]
+        fn is_empty(&self) -> $crate::dataset::DResult<Self, bool> {
+            DatasetWrapper::dw_is_empty(self)
+        }
+
         #[inline]
         fn subjects(&self) -> $crate::dataset::DResultTermSet<Self> {
             DatasetWrapper::dw_subjects(self)
@@ -567,6 +589,13 @@ where
     /// [`IndexedDataset::remove_indexed`](../indexed/trait.IndexedDataset.html#tymethod.remove_indexed).
     fn idw_hook_remove_indexed(&mut self, modified: &Option<[T::Index; 4]>);
 
+    /// Hook to be executed at the end of
+    /// [`IndexedDataset::clear_indexed`](../indexed/trait.IndexedDataset.html#method.clear_indexed).
+    ///
+    /// The default implementation does nothing;
+    /// wrappers maintaining their own indexes must override it.
+    fn idw_hook_clear_indexed(&mut self) {}
+
     /// Hook to be executed at the end of
     /// [`IndexedDataset::shrink_to_fit`](../indexed/trait.IndexedDataset.html#tymethod.shrink_to_fit).
     fn idw_hook_shrink_to_fit(&mut self);
@@ -647,6 +676,11 @@ macro_rules! impl_indexed_dataset_for_wrapper {
             modified
         }
 
+        fn clear_indexed(&mut self) {
+            self.get_wrapped_mut().clear_indexed();
+            self.idw_hook_clear_indexed();
+        }
+
         fn shrink_to_fit(&mut self) {
             self.get_wrapped_mut().shrink_to_fit();
             self.idw_hook_shrink_to_fit();
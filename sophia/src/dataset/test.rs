@@ -157,6 +157,7 @@ macro_rules! test_dataset_impl {
             use $crate::dataset::test::*;
             use $crate::dataset::*;
             use $crate::ns::*;
+            use $crate::quad::Quad;
 
             #[allow(unused_imports)]
             use super::*;
@@ -324,8 +325,248 @@ macro_rules! test_dataset_impl {
                 Ok(())
             }
 
+            #[test]
+            fn test_copy_graph() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                populate(&mut d)?;
+
+                let g3 = StaticTerm::new_iri_suffixed(NS, "G3").unwrap();
+                assert_eq!(d.copy_graph(*GN1, Some(&g3))?, 6);
+                assert_eq!(d.quads_with_g(*GN1).count(), 6);
+                assert_eq!(d.quads_with_g(Some(&g3)).count(), 6);
+                assert!(Dataset::contains(&d, &C2, &rdfs::subClassOf, &C1, Some(&g3)).unwrap());
+
+                assert_eq!(d.copy_graph(*DG, *GN2)?, 4);
+                assert_eq!(d.quads_with_g(*DG).count(), 4);
+                assert_eq!(d.quads_with_g(*GN2).count(), 11);
+                assert_consistent_hint(27, d.quads().size_hint());
+                Ok(())
+            }
+
+            #[test]
+            fn test_insert_unless_in_default() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                assert!(MutableDataset::insert(
+                    &mut d,
+                    &C1,
+                    &rdf::type_,
+                    &rdfs::Class,
+                    *DG
+                )?);
+
+                assert!(!d.insert_unless_in_default(&C1, &rdf::type_, &rdfs::Class, *GN1)?);
+                assert!(!Dataset::contains(&d, &C1, &rdf::type_, &rdfs::Class, *GN1).unwrap());
+                assert!(d.insert_unless_in_default(&C2, &rdf::type_, &rdfs::Class, *GN1)?);
+                assert!(Dataset::contains(&d, &C2, &rdf::type_, &rdfs::Class, *GN1).unwrap());
+                assert!(d.insert_unless_in_default(&C2, &rdf::type_, &rdfs::Class, *DG)?);
+                assert_eq!(d.quads_with_g(*DG).count(), 2);
+                assert_consistent_hint(3, d.quads().size_hint());
+                Ok(())
+            }
+
+            #[test]
+            fn test_insert_all_into() -> MDResult<$mutable_dataset_impl, ()> {
+                use $crate::triple::stream::AsTripleSource;
+
+                let mut d = $mutable_dataset_factory();
+                let triples = vec![
+                    [&*C1, &rdf::type_, &rdfs::Class],
+                    [&*C2, &rdf::type_, &rdfs::Class],
+                    [&*C2, &rdfs::subClassOf, &*C1],
+                ];
+                let inserted = d
+                    .insert_all_into(*GN1, &mut triples.clone().into_iter().as_triple_source())
+                    .unwrap();
+                assert_eq!(inserted, 3);
+                assert_eq!(d.quads_with_g(*GN1).count(), 3);
+                assert_eq!(d.quads_with_g(*DG).count(), 0);
+                assert!(Dataset::contains(&d, &C2, &rdfs::subClassOf, &C1, *GN1).unwrap());
+
+                let inserted = d
+                    .insert_all_into(*DG, &mut triples.into_iter().as_triple_source())
+                    .unwrap();
+                assert_eq!(inserted, 3);
+                assert_eq!(d.quads_with_g(*DG).count(), 3);
+                assert_consistent_hint(6, d.quads().size_hint());
+                Ok(())
+            }
+
+            #[test]
+            fn test_clear_graph() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                populate(&mut d)?;
+
+                assert_eq!(d.clear_graph(*GN1)?, 6);
+                assert_eq!(d.quads_with_g(*GN1).count(), 0);
+                assert_eq!(d.quads_with_g(*GN2).count(), 7);
+                assert_eq!(d.quads_with_g(*DG).count(), 4);
+                assert_eq!(d.quads().count(), 11);
+                assert_eq!(d.clear_graph(*GN1)?, 0);
+
+                assert_eq!(d.clear_graph(*DG)?, 4);
+                assert_eq!(d.quads_with_g(*GN2).count(), 7);
+                assert_consistent_hint(7, d.quads().size_hint());
+                Ok(())
+            }
+
+            #[test]
+            fn test_annotate_quad() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                populate(&mut d)?;
+                let meta = StaticTerm::new_iri_suffixed(NS, "meta").unwrap();
+                let source = StaticTerm::new_iri_suffixed(NS, "source").unwrap();
+                let timestamp = StaticTerm::new_iri_suffixed(NS, "timestamp").unwrap();
+                let doc = StaticTerm::new_iri_suffixed(NS, "doc").unwrap();
+                let date =
+                    StaticTerm::new_literal_dt("2020-01-01T00:00:00Z", xsd::dateTime).unwrap();
+                let quad = ([*C1, *P1, *I1A], *GN1);
+
+                let annotations = [[source, doc], [timestamp, date]];
+                let stmt = d.annotate_quad(&quad, &annotations, Some(&meta))?;
+                assert!(matches!(stmt, Term::BNode(_)));
+                assert!(Dataset::contains(&d, &C1, &P1, &I1A, *GN1).unwrap());
+                assert_eq!(d.quads_with_g(Some(&meta)).count(), 6);
+
+                // read the annotations back, starting from the quad
+                let found: Vec<BoxTerm> = d
+                    .quads_with_po(&rdf::subject, &*C1)
+                    .map(|q| q.unwrap().s().into())
+                    .collect();
+                assert_eq!(found, vec![stmt.clone()]);
+                assert!(Dataset::contains(&d, &stmt, &rdf::predicate, &P1, Some(&meta)).unwrap());
+                assert!(Dataset::contains(&d, &stmt, &rdf::object, &I1A, Some(&meta)).unwrap());
+                assert!(Dataset::contains(&d, &stmt, &source, &doc, Some(&meta)).unwrap());
+                assert!(Dataset::contains(&d, &stmt, &timestamp, &date, Some(&meta)).unwrap());
+
+                let stmt2 = d.annotate_quad(&quad, &[] as &[[StaticTerm; 2]], *DG)?;
+                assert!(stmt2 != stmt);
+                assert_eq!(d.quads_with_g(Some(&meta)).count(), 6);
+                assert_eq!(d.quads_with_s(&stmt2).count(), 4);
+                Ok(())
+            }
+
+            #[test]
+            fn test_insert_canonical() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                let plus_five = StaticTerm::new_literal_dt("+5", xsd::integer).unwrap();
+                let five = StaticTerm::new_literal_dt("5", xsd::integer).unwrap();
+                assert!(d.insert_canonical(&C1, &P1, &plus_five, *GN1)?);
+                assert!(Dataset::contains(&d, &C1, &P1, &five, *GN1).unwrap());
+                assert!(!Dataset::contains(&d, &C1, &P1, &plus_five, *GN1).unwrap());
+                if $is_set {
+                    assert!(!d.insert_canonical(&C1, &P1, &five, *GN1)?);
+                    assert_eq!(d.quads().count(), 1);
+                }
+
+                // not canonicalized by default
+                MutableDataset::insert(&mut d, &C1, &P1, &plus_five, *GN1)?;
+                assert!(Dataset::contains(&d, &C1, &P1, &plus_five, *GN1).unwrap());
+                Ok(())
+            }
+
+            #[test]
+            fn test_clear() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                populate(&mut d)?;
+
+                MutableDataset::clear(&mut d)?;
+                assert_eq!(d.quads().count(), 0);
+                assert_eq!(d.quads_with_g(*GN1).count(), 0);
+                assert!(!Dataset::contains(&d, &*C1, &rdf::type_, &rdfs::Class, *DG).unwrap());
+
+                MutableDataset::insert(&mut d, &*C1, &rdf::type_, &rdfs::Class, *DG)?;
+                assert_eq!(d.quads().count(), 1);
+                assert_eq!(d.quads_with_p(&rdf::type_).count(), 1);
+                assert!(Dataset::contains(&d, &*C1, &rdf::type_, &rdfs::Class, *DG).unwrap());
+                MutableDataset::clear(&mut d)?;
+                assert_eq!(d.quads().count(), 0);
+                Ok(())
+            }
+
             // Test Dataset
 
+            #[test]
+            fn test_ask() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                populate(&mut d)?;
+
+                let x = StaticTerm::new_variable("x").unwrap();
+                let y = StaticTerm::new_variable("y").unwrap();
+                let g = StaticTerm::new_variable("g").unwrap();
+
+                let satisfiable = [
+                    [x, rdf::type_, *C1, g],
+                    [x, *P1, y, g],
+                    [y, rdf::type_, *C2, g],
+                ];
+                assert!(d.ask(&satisfiable).unwrap());
+
+                let unsatisfiable = [[x, rdf::type_, *C1, g], [x, rdfs::subClassOf, y, g]];
+                assert!(!d.ask(&unsatisfiable).unwrap());
+
+                // C2 is a class only in the default graph, which ?g does not match
+                let default_graph = [
+                    [x, rdf::type_, rdfs::Class, g],
+                    [x, rdfs::subClassOf, *C1, g],
+                ];
+                assert!(!d.ask(&default_graph).unwrap());
+                // P1 is a property only in the default graph,
+                // while C1 is a class both in the default graph and in G1
+                assert!(!d.ask(&[[*P1, rdf::type_, rdf::Property, g]]).unwrap());
+                assert!(d.ask(&[[*C1, rdf::type_, rdfs::Class, g]]).unwrap());
+
+                let named = [
+                    [x, rdf::type_, rdfs::Class, *G1],
+                    [x, rdfs::subClassOf, y, *G1],
+                ];
+                assert!(!d.ask(&named).unwrap());
+                let named = [
+                    [x, rdfs::subClassOf, y, *G1],
+                    [y, rdf::type_, rdfs::Class, *G1],
+                ];
+                assert!(d.ask(&named).unwrap());
+
+                assert!(d.ask::<&str>(&[]).unwrap());
+                Ok(())
+            }
+
+            #[test]
+            fn test_match_bgp() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                populate(&mut d)?;
+
+                let s = StaticTerm::new_variable("s").unwrap();
+                let p = StaticTerm::new_variable("p").unwrap();
+                let g = StaticTerm::new_variable("g").unwrap();
+
+                // in which graphs does C1 appear as an object?
+                let solutions = d.match_bgp(&[[s, p, *C1, g]]).unwrap();
+                assert_eq!(solutions.len(), 4);
+                let graphs: std::collections::HashSet<_> =
+                    solutions.iter().map(|b| b["g"].clone()).collect();
+                assert_eq!(graphs.len(), 2);
+                assert!(graphs.contains(&BoxTerm::from(&*G1)));
+                assert!(graphs.contains(&BoxTerm::from(&*G2)));
+
+                let x = StaticTerm::new_variable("x").unwrap();
+                let y = StaticTerm::new_variable("y").unwrap();
+                let joined = [[x, rdf::type_, *C1, g], [x, *P1, y, g]];
+                let mut solutions = d.match_bgp(&joined).unwrap();
+                solutions.sort_by(|b1, b2| b1["x"].cmp(&b2["x"]));
+                assert_eq!(solutions.len(), 2);
+                assert_eq!(solutions[0]["x"], *I1A);
+                assert_eq!(solutions[0]["y"], *I2A);
+                assert_eq!(solutions[1]["x"], *I1B);
+                assert_eq!(solutions[1]["y"], *I2B);
+                assert!(solutions.iter().all(|b| b["g"] == *G2 && b.len() == 3));
+
+                assert_eq!(
+                    d.match_bgp::<&str>(&[]).unwrap(),
+                    vec![std::collections::HashMap::new()]
+                );
+                Ok(())
+            }
+
             #[test]
             fn test_quads() -> MDResult<$mutable_dataset_impl, ()> {
                 let mut d = $mutable_dataset_factory();
@@ -723,6 +964,18 @@ macro_rules! test_dataset_impl {
                 Ok(())
             }
 
+            #[test]
+            fn test_len() -> MDResult<$mutable_dataset_impl, ()> {
+                let mut d = $mutable_dataset_factory();
+                assert_eq!(Dataset::len(&d).unwrap(), 0);
+                assert!(Dataset::is_empty(&d).unwrap());
+
+                populate(&mut d)?;
+                assert_eq!(Dataset::len(&d).unwrap(), d.quads().count());
+                assert!(!Dataset::is_empty(&d).unwrap());
+                Ok(())
+            }
+
             #[test]
             fn test_subjects() -> MDResult<$mutable_dataset_impl, ()> {
                 let mut d = $mutable_dataset_factory();
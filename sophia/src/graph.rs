@@ -13,6 +13,9 @@ pub mod adapter;
 #[macro_use]
 pub mod indexed;
 pub mod inmem;
+#[cfg(feature = "integrity")]
+pub mod integrity;
+pub mod isomorphism;
 
 mod _ext_impl;
 pub use self::_ext_impl::*;
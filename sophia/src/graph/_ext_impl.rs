@@ -28,6 +28,16 @@ where
                 .as_triple_source(),
         )
     }
+
+    #[inline]
+    fn len(&self) -> GResult<Self, usize> {
+        Ok(<[T]>::len(self))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> GResult<Self, bool> {
+        Ok(<[T]>::is_empty(self))
+    }
 }
 
 impl<T> Graph for Vec<T>
@@ -45,6 +55,16 @@ where
                 .as_triple_source(),
         )
     }
+
+    #[inline]
+    fn len(&self) -> GResult<Self, usize> {
+        Ok(Vec::len(self))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> GResult<Self, bool> {
+        Ok(Vec::is_empty(self))
+    }
 }
 
 impl MutableGraph for Vec<[BoxTerm; 3]> {
@@ -79,6 +99,10 @@ impl MutableGraph for Vec<[BoxTerm; 3]> {
             Ok(false)
         }
     }
+    fn clear(&mut self) -> MGResult<Self, ()> {
+        Vec::clear(self);
+        Ok(())
+    }
 }
 
 impl<T, BH> Graph for HashSet<T, BH>
@@ -93,6 +117,16 @@ where
     fn triples(&self) -> GTripleSource<Self> {
         Box::from(self.iter().map(StreamedTriple::by_ref).as_triple_source())
     }
+
+    #[inline]
+    fn len(&self) -> GResult<Self, usize> {
+        Ok(HashSet::len(self))
+    }
+
+    #[inline]
+    fn is_empty(&self) -> GResult<Self, bool> {
+        Ok(HashSet::is_empty(self))
+    }
 }
 
 impl<BH> MutableGraph for HashSet<[BoxTerm; 3], BH>
@@ -123,6 +157,10 @@ where
         let o = BoxTerm::from(o);
         Ok(HashSet::remove(self, &[s, p, o]))
     }
+    fn clear(&mut self) -> MGResult<Self, ()> {
+        HashSet::clear(self);
+        Ok(())
+    }
 }
 
 impl<'a, T, S: ::std::hash::BuildHasher> SetGraph for HashSet<T, S> where T: Eq + Hash + Triple {}
@@ -1,6 +1,6 @@
 // this module is transparently re-exported by its parent `graph`
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::hash::Hash;
 use std::marker::PhantomData;
 
@@ -8,9 +8,13 @@ use resiter::filter::*;
 use resiter::map::*;
 
 use crate::graph::adapter::GraphAsDataset;
+use crate::graph::inmem::FastGraph;
+use crate::query::text::TextIndex;
 use crate::triple::stream::*;
 use crate::triple::streaming_mode::*;
 use crate::triple::*;
+use sophia_term::iri::Iri;
+use sophia_term::literal::Literal;
 use sophia_term::matcher::TermMatcher;
 use sophia_term::*;
 
@@ -174,6 +178,29 @@ pub trait Graph {
         Box::new(self.triples_with_sp(s, p).filter_ok(move |t| t.o() == o))
     }
 
+    /// The number of triples in this graph.
+    ///
+    /// The default implementation iterates over all triples,
+    /// but implementations keeping track of their size (such as in-memory graphs)
+    /// answer in constant time.
+    fn len(&self) -> GResult<Self, usize> {
+        let mut len = 0;
+        for t in self.triples() {
+            t?;
+            len += 1;
+        }
+        Ok(len)
+    }
+
+    /// Return `true` if this graph contains no triple.
+    fn is_empty(&self) -> GResult<Self, bool> {
+        match self.triples().next() {
+            None => Ok(true),
+            Some(Ok(_)) => Ok(false),
+            Some(Err(err)) => Err(err),
+        }
+    }
+
     /// Return `true` if this graph contains the given triple.
     fn contains<T, U, V>(&self, s: &Term<T>, p: &Term<U>, o: &Term<V>) -> GResult<Self, bool>
     where
@@ -358,6 +385,349 @@ pub trait Graph {
         Ok(res)
     }
 
+    /// Split this graph into independent sub-graphs,
+    /// grouping triples by the key computed by `key` on each of them.
+    ///
+    /// Each triple is copied into the [`FastGraph`](inmem/type.FastGraph.html)
+    /// associated to its key.
+    fn partition_by<K, F>(&self, key: F) -> GResult<Self, HashMap<K, FastGraph>>
+    where
+        K: Eq + Hash,
+        F: Fn(&GTriple<Self>) -> K,
+    {
+        let mut parts: HashMap<K, FastGraph> = HashMap::new();
+        for t in self.triples() {
+            let t = t?;
+            let part = parts.entry(key(&t)).or_default();
+            // FastGraph::MutationError is Infallible
+            part.insert(t.s(), t.p(), t.o()).unwrap();
+        }
+        Ok(parts)
+    }
+
+    /// Build a new [`FastGraph`](inmem/type.FastGraph.html)
+    /// containing the result of `f` applied to every triple of this graph.
+    ///
+    /// Triples for which `f` returns `None` are dropped,
+    /// which makes this method suitable for both rewriting and redacting a graph.
+    /// This graph is left untouched.
+    fn map<F>(&self, f: F) -> GResult<Self, FastGraph>
+    where
+        F: Fn([&GTerm<Self>; 3]) -> Option<[BoxTerm; 3]>,
+    {
+        let mut res = FastGraph::new();
+        for t in self.triples() {
+            let t = t?;
+            if let Some([s, p, o]) = f([t.s(), t.p(), t.o()]) {
+                // FastGraph::MutationError is Infallible
+                res.insert(&s, &p, &o).unwrap();
+            }
+        }
+        Ok(res)
+    }
+
+    /// Return the [Concise Bounded Description] of `node` in this graph,
+    /// i.e. all the triples having `node` as their subject,
+    /// and, recursively, the Concise Bounded Descriptions of the blank nodes
+    /// appearing as their objects.
+    ///
+    /// Each blank node is described only once, so cycles of blank nodes are supported.
+    /// Reifications of the included triples, which are also part of the CBD
+    /// as originally defined, are not included.
+    ///
+    /// [Concise Bounded Description]: https://www.w3.org/Submission/CBD/
+    fn describe<T>(&self, node: &Term<T>) -> GResult<Self, FastGraph>
+    where
+        T: TermData,
+    {
+        concise_bounded_description(self, node, false)
+    }
+
+    /// Return the *symmetric* [Concise Bounded Description] of `node` in this graph,
+    /// i.e. all the triples having `node` as their subject or their object,
+    /// and, recursively, the symmetric Concise Bounded Descriptions of the blank nodes
+    /// appearing in them.
+    ///
+    /// As for [`describe`](#method.describe), each blank node is described only once,
+    /// and triples involving `node` in both positions are included only once.
+    ///
+    /// [Concise Bounded Description]: https://www.w3.org/Submission/CBD/
+    fn describe_symmetric<T>(&self, node: &Term<T>) -> GResult<Self, FastGraph>
+    where
+        T: TermData,
+    {
+        concise_bounded_description(self, node, true)
+    }
+
+    /// Return (a copy of) up to `n` triples of this graph,
+    /// for previewing its content.
+    ///
+    /// The first `n` triples are returned, in the iteration order of [`triples`](#tymethod.triples);
+    /// if the graph contains less than `n` triples, all of them are returned.
+    fn sample(&self, n: usize) -> GResult<Self, Vec<[BoxTerm; 3]>> {
+        self.triples()
+            .take(n)
+            .map_ok(|t| [t.s().into(), t.p().into(), t.o().into()])
+            .collect()
+    }
+
+    /// Return (a copy of) all the triples of this graph, sorted according to `order`,
+    /// e.g. [`TripleOrder::POS`](../triple/enum.TripleOrder.html#variant.POS)
+    /// to group them by predicate.
+    ///
+    /// See [`sort_triples`](../triple/fn.sort_triples.html) for the ordering of terms;
+    /// since all three positions are compared, the result does not depend on
+    /// the iteration order of [`triples`](#tymethod.triples).
+    fn triples_sorted_by(&self, order: TripleOrder) -> GResult<Self, Vec<[BoxTerm; 3]>> {
+        let mut triples = self
+            .triples()
+            .map_ok(|t| [t.s().into(), t.p().into(), t.o().into()])
+            .collect::<Result<Vec<_>, _>>()?;
+        sort_triples(&mut triples, order);
+        Ok(triples)
+    }
+
+    /// Return the namespaces of the IRIs used in this graph,
+    /// with their number of occurrences,
+    /// sorted by decreasing number of occurrences.
+    ///
+    /// The namespace of an IRI is its prefix up to (and including)
+    /// its last `#` or `/`;
+    /// IRIs containing neither (such as URNs) are ignored.
+    /// Datatypes of literals are also taken into account.
+    fn namespaces(&self) -> GResult<Self, Vec<(String, usize)>> {
+        let mut counts: HashMap<String, usize> = HashMap::new();
+        for t in self.triples() {
+            let t = t?;
+            for term in [t.s(), t.p(), t.o()].iter() {
+                let dt;
+                let iri = match term {
+                    Term::Iri(iri) => iri.value(),
+                    Term::Literal(lit) if lit.lang().is_none() => {
+                        dt = lit.dt();
+                        dt.value()
+                    }
+                    _ => continue,
+                };
+                if let Some(ns) = iri_namespace(&iri) {
+                    match counts.get_mut(ns) {
+                        Some(c) => *c += 1,
+                        None => {
+                            counts.insert(ns.to_string(), 1);
+                        }
+                    }
+                }
+            }
+        }
+        let mut namespaces: Vec<_> = counts.into_iter().collect();
+        namespaces.sort_by(|(ns1, c1), (ns2, c2)| c2.cmp(c1).then_with(|| ns1.cmp(ns2)));
+        Ok(namespaces)
+    }
+
+    /// Estimate the number of bytes occupied by the strings backing the terms of this graph.
+    ///
+    /// Each distinct string is counted only once,
+    /// assuming that the graph interns its strings
+    /// (as [`FastGraph`](inmem/type.FastGraph.html) and [`LightGraph`](inmem/type.LightGraph.html) do),
+    /// so the result is typically much smaller than the sum of
+    /// [`Term::heap_size`](../term/enum.Term.html#method.heap_size) over all the terms of all triples.
+    /// The overhead of the graph structure itself is not accounted for.
+    fn estimated_heap_size(&self) -> GResult<Self, usize> {
+        let mut seen: HashSet<Box<str>> = HashSet::new();
+        let mut size = 0;
+        for t in self.triples() {
+            let t = t?;
+            for term in [t.s(), t.p(), t.o()].iter() {
+                term.clone_with(|txt| {
+                    if !seen.contains(txt) {
+                        size += txt.len();
+                        seen.insert(txt.into());
+                    }
+                    txt
+                });
+            }
+        }
+        Ok(size)
+    }
+
+    /// Return the predicates used in this graph, with their number of triples,
+    /// sorted by decreasing number of triples.
+    ///
+    /// Predicates with the same number of triples are sorted in increasing order.
+    fn predicate_histogram(&self) -> GResult<Self, Vec<(BoxTerm, usize)>> {
+        let mut counts: HashMap<BoxTerm, usize> = HashMap::new();
+        for t in self.triples() {
+            let t = t?;
+            *counts.entry(t.p().into()).or_insert(0) += 1;
+        }
+        let mut histogram: Vec<_> = counts.into_iter().collect();
+        histogram.sort_by(|(p1, c1), (p2, c2)| c2.cmp(c1).then_with(|| p1.cmp(p2)));
+        Ok(histogram)
+    }
+
+    /// An iterator visiting all triples with at least one IRI starting with `ns`,
+    /// among the positions selected by `positions`.
+    ///
+    /// `ns` is compared textually with IRIs,
+    /// so it should generally end with `/` or `#`.
+    fn triples_in_namespace<'s>(
+        &'s self,
+        ns: &'s str,
+        positions: NamespacePositions,
+    ) -> GTripleSource<'s, Self> {
+        Box::from(self.triples().filter_ok(move |t| {
+            (positions.subject && is_in_namespace(t.s(), ns))
+                || (positions.predicate && is_in_namespace(t.p(), ns))
+                || (positions.object && is_in_namespace(t.o(), ns))
+                || (positions.datatype
+                    && match t.o() {
+                        Term::Literal(lit) => lit.dt().to_string_value().starts_with(ns),
+                        _ => false,
+                    })
+        }))
+    }
+
+    /// Build a full-text index of the literals
+    /// which are the object of a triple with one of the given `predicates`,
+    /// associating their words to the subject of that triple.
+    ///
+    /// See [`TextIndex::search`](../query/text/struct.TextIndex.html#method.search).
+    fn build_text_index<T>(&self, predicates: &[Term<T>]) -> GResult<Self, TextIndex>
+    where
+        T: TermData,
+    {
+        let mut index = TextIndex::new();
+        for p in predicates {
+            for t in self.triples_with_p(p) {
+                let t = t?;
+                if let Term::Literal(lit) = t.o() {
+                    index.insert(t.s(), lit.txt().as_ref());
+                }
+            }
+        }
+        Ok(index)
+    }
+
+    /// Assign a numeric identifier to each blank node of this graph.
+    ///
+    /// Identifiers are assigned from 0, in the order in which blank nodes first occur
+    /// in the (sorted) list of triples of this graph.
+    /// Hence, the result does not depend on the iteration order of [`triples`](#tymethod.triples),
+    /// and two graphs with the same triples get the same index.
+    fn bnode_index(&self) -> GResult<Self, HashMap<BoxTerm, usize>> {
+        let mut triples: Vec<[BoxTerm; 3]> = self
+            .triples()
+            .map_ok(|t| [t.s().into(), t.p().into(), t.o().into()])
+            .collect::<Result<_, _>>()?;
+        triples.sort_unstable();
+        let mut res = HashMap::new();
+        for term in triples.iter().flat_map(|t| t.iter()) {
+            if let Term::BNode(_) = term {
+                let id = res.len();
+                res.entry(term.clone()).or_insert(id);
+            }
+        }
+        Ok(res)
+    }
+
+    /// Check whether this graph contains a cycle going only through blank nodes,
+    /// i.e. a blank node from which following triples (from subject to object)
+    /// through other blank nodes leads back to it (such as a malformed RDF list).
+    ///
+    /// Cycles going through IRIs are legal and common, and are therefore ignored;
+    /// see [`has_cycle`](#method.has_cycle) to include them.
+    fn has_bnode_cycle(&self) -> GResult<Self, bool> {
+        self.has_cycle(false)
+    }
+
+    /// Check whether this graph contains a cycle going only through blank nodes,
+    /// or, if `include_iris` is true, through blank nodes and IRIs.
+    ///
+    /// See also [`has_bnode_cycle`](#method.has_bnode_cycle).
+    fn has_cycle(&self, include_iris: bool) -> GResult<Self, bool> {
+        fn in_scope<T: TermData>(t: &Term<T>, include_iris: bool) -> bool {
+            match t {
+                Term::BNode(_) => true,
+                Term::Iri(_) => include_iris,
+                _ => false,
+            }
+        }
+        let mut edges: HashMap<BoxTerm, Vec<BoxTerm>> = HashMap::new();
+        for t in self.triples() {
+            let t = t?;
+            if in_scope(t.s(), include_iris) && in_scope(t.o(), include_iris) {
+                edges.entry(t.s().into()).or_default().push(t.o().into());
+            }
+        }
+        // iterative depth-first search,
+        // where a node is on the stack while its successors are being visited
+        let mut done = HashSet::new();
+        let mut on_stack = HashSet::new();
+        for start in edges.keys() {
+            if done.contains(start) {
+                continue;
+            }
+            let mut stack = vec![(start, 0)];
+            on_stack.insert(start);
+            while let Some((node, i)) = stack.last_mut() {
+                let node: &BoxTerm = node;
+                match edges.get(node).and_then(|succ| succ.get(*i)) {
+                    Some(next) => {
+                        *i += 1;
+                        if on_stack.contains(next) {
+                            return Ok(true);
+                        }
+                        if !done.contains(next) {
+                            on_stack.insert(next);
+                            stack.push((next, 0));
+                        }
+                    }
+                    None => {
+                        on_stack.remove(node);
+                        done.insert(node);
+                        stack.pop();
+                    }
+                }
+            }
+        }
+        Ok(false)
+    }
+
+    /// Check whether this graph and `other` contain the same triples.
+    ///
+    /// This is *ground* equality: if either graph contains blank nodes,
+    /// `false` is returned, as blank node identifiers are not significant.
+    /// With the `isomorphism` feature enabled, graphs containing blank nodes
+    /// are compared modulo a renaming of their blank nodes
+    /// (see [`isomorphic_graphs`](isomorphism/fn.isomorphic_graphs.html)),
+    /// which is exponential in the number of blank nodes in the worst case.
+    fn graph_eq<G>(&self, other: &G) -> GResult<Self, bool>
+    where
+        G: Graph,
+        G::Error: Into<Self::Error>,
+    {
+        let mine: HashSet<[BoxTerm; 3]> = self
+            .triples()
+            .map_ok(|t| [t.s().into(), t.p().into(), t.o().into()])
+            .collect::<Result<_, _>>()?;
+        let theirs: HashSet<[BoxTerm; 3]> = other
+            .triples()
+            .map_ok(|t| [t.s().into(), t.p().into(), t.o().into()])
+            .collect::<Result<_, _>>()
+            .map_err(Into::into)?;
+        if mine.len() != theirs.len() {
+            return Ok(false);
+        }
+        let has_bnode = |t: &[BoxTerm; 3]| t.iter().any(|term| matches!(term, Term::BNode(_)));
+        if mine.iter().chain(theirs.iter()).any(has_bnode) {
+            #[cfg(feature = "isomorphism")]
+            return Ok(crate::graph::isomorphism::isomorphic_graphs(&mine, &theirs));
+            #[cfg(not(feature = "isomorphism"))]
+            return Ok(false);
+        }
+        Ok(mine == theirs)
+    }
+
     /// [`Dataset`](../dataset/trait.Dataset.html) adapter borrowing this graph
     fn borrow_as_dataset(&self) -> GraphAsDataset<Self, &Self> {
         GraphAsDataset(self, PhantomData)
@@ -432,6 +802,35 @@ pub trait MutableGraph: Graph {
         U: TermData,
         V: TermData;
 
+    /// Insert the given triple in this graph,
+    /// after replacing its literals by their canonical form
+    /// (see [`Term::canonicalized`](../term/enum.Term.html#method.canonicalized)).
+    ///
+    /// This is an opt-in alternative to [`insert`](#tymethod.insert),
+    /// ensuring that value-equal literals such as `"+5"^^xsd:integer` and `"5"^^xsd:integer`
+    /// are stored with a single lexical form.
+    /// Literals whose datatype has no supported canonical form are inserted as is.
+    fn insert_canonical<T, U, V>(
+        &mut self,
+        s: &Term<T>,
+        p: &Term<U>,
+        o: &Term<V>,
+    ) -> MGResult<Self, bool>
+    where
+        T: TermData,
+        U: TermData,
+        V: TermData,
+    {
+        match (s.canonicalized(), p.canonicalized(), o.canonicalized()) {
+            (None, None, None) => self.insert(s, p, o),
+            (cs, cp, co) => self.insert(
+                &cs.unwrap_or_else(|| s.into()),
+                &cp.unwrap_or_else(|| p.into()),
+                &co.unwrap_or_else(|| o.into()),
+            ),
+        }
+    }
+
     /// Insert into this graph all triples from the given source.
     #[inline]
     fn insert_all<TS>(
@@ -509,12 +908,34 @@ pub trait MutableGraph: Graph {
     /// Note that the default implementation is rather naive,
     /// and could be improved in specific implementations of the trait.
     ///
+    /// See also [`retain_matching_counted`](#method.retain_matching_counted).
     fn retain_matching<S, P, O>(
         &mut self,
         ms: &S,
         mp: &P,
         mo: &O,
     ) -> Result<(), Self::MutationError>
+    where
+        S: TermMatcher + ?Sized,
+        P: TermMatcher + ?Sized,
+        O: TermMatcher + ?Sized,
+        <Self as Graph>::Error: Into<Self::MutationError>,
+        Infallible: Into<Self::MutationError>,
+    {
+        self.retain_matching_counted(ms, mp, mo).map(|_| ())
+    }
+
+    /// Keep only the triples matching the given matchers,
+    /// and return the number of triples that were removed.
+    ///
+    /// Note that the default implementation is rather naive,
+    /// and could be improved in specific implementations of the trait.
+    fn retain_matching_counted<S, P, O>(
+        &mut self,
+        ms: &S,
+        mp: &P,
+        mo: &O,
+    ) -> Result<usize, Self::MutationError>
     where
         S: TermMatcher + ?Sized,
         P: TermMatcher + ?Sized,
@@ -536,9 +957,138 @@ pub trait MutableGraph: Graph {
             .map_err(Into::into)?;
         let mut to_remove = to_remove.into_iter().as_triple_source();
         self.remove_all(&mut to_remove)
-            .map_err(|err| err.inner_into())?;
+            .map_err(|err| err.inner_into())
+    }
+
+    /// Remove all triples from this graph.
+    ///
+    /// Note that the default implementation is rather naive,
+    /// and could be improved in specific implementations of the trait.
+    fn clear(&mut self) -> MGResult<Self, ()>
+    where
+        <Self as Graph>::Error: Into<Self::MutationError>,
+    {
+        let mut to_remove = vec![];
+        for t in self.triples() {
+            let t = t.map_err(Into::<Self::MutationError>::into)?;
+            to_remove.push([
+                BoxTerm::from(t.s()),
+                BoxTerm::from(t.p()),
+                BoxTerm::from(t.o()),
+            ]);
+        }
+        for [s, p, o] in to_remove.iter() {
+            self.remove(s, p, o)?;
+        }
         Ok(())
     }
+
+    /// Rewrite every IRI in this graph with the function `f`,
+    /// and return the number of triples that were affected.
+    ///
+    /// `f` is applied to the subject, predicate and object of each triple,
+    /// as well as to the datatype of literals;
+    /// IRIs for which `f` returns `None` (or an invalid IRI) are kept unchanged.
+    ///
+    /// If several triples are rewritten into the same triple
+    /// (or into a triple already present in the graph),
+    /// the graph contains it only once afterwards,
+    /// even if it is not a [`SetGraph`](trait.SetGraph.html).
+    fn rewrite_iris<F>(&mut self, f: F) -> MGResult<Self, usize>
+    where
+        F: Fn(&str) -> Option<String>,
+        <Self as Graph>::Error: Into<Self::MutationError>,
+    {
+        replace_terms(self, |t| rewrite_term(t, &f))
+    }
+
+    /// Replace by `replacement` the lexical form of every literal in this graph
+    /// for which `pred` returns `true`,
+    /// and return the number of triples that were affected.
+    ///
+    /// The datatype or language tag of redacted literals is preserved,
+    /// as well as the rest of the graph structure.
+    ///
+    /// If several triples are redacted into the same triple
+    /// (or into a triple already present in the graph),
+    /// the graph contains it only once afterwards,
+    /// even if it is not a [`SetGraph`](trait.SetGraph.html).
+    fn redact_literals<F>(&mut self, pred: F, replacement: &str) -> MGResult<Self, usize>
+    where
+        F: Fn(&str) -> bool,
+        <Self as Graph>::Error: Into<Self::MutationError>,
+    {
+        replace_terms(self, |t| redact_term(t, &pred, replacement))
+    }
+
+    /// Change into `to` the datatype of every literal of datatype `from` in this graph,
+    /// keeping their lexical form,
+    /// and return the number of triples that were affected.
+    ///
+    /// If several triples are retyped into the same triple
+    /// (or into a triple already present in the graph),
+    /// the graph contains it only once afterwards,
+    /// even if it is not a [`SetGraph`](trait.SetGraph.html).
+    fn retype_literals<T, U>(&mut self, from: &Iri<T>, to: &Iri<U>) -> MGResult<Self, usize>
+    where
+        T: TermData,
+        U: TermData,
+        <Self as Graph>::Error: Into<Self::MutationError>,
+    {
+        if from == to {
+            return Ok(0);
+        }
+        let to = Iri::<Box<str>>::from(to);
+        replace_terms(self, |t| retype_term(t, from, &to))
+    }
+
+    /// Remove every triple whose subject is a blank node
+    /// that can not be reached from a resource that is not a blank node
+    /// (e.g. an IRI), and return the number of triples that were removed.
+    ///
+    /// A blank node is reachable if it is the object of a triple
+    /// whose subject is not a blank node,
+    /// or the object of a triple whose subject is a reachable blank node.
+    /// This is useful to clean up dangling blank node structures
+    /// (such as orphaned RDF lists) after partial deletions.
+    fn prune_orphan_bnodes(&mut self) -> MGResult<Self, usize>
+    where
+        <Self as Graph>::Error: Into<Self::MutationError>,
+    {
+        let mut candidates = vec![];
+        let mut edges: HashMap<BoxTerm, Vec<BoxTerm>> = HashMap::new();
+        let mut reachable = HashSet::new();
+        let mut to_visit = vec![];
+        for t in self.triples() {
+            let t = t.map_err(Into::<Self::MutationError>::into)?;
+            let s = BoxTerm::from(t.s());
+            let o = BoxTerm::from(t.o());
+            if let Term::BNode(_) = s {
+                if let Term::BNode(_) = o {
+                    edges.entry(s.clone()).or_default().push(o.clone());
+                }
+                candidates.push([s, BoxTerm::from(t.p()), o]);
+            } else if let Term::BNode(_) = o {
+                if reachable.insert(o.clone()) {
+                    to_visit.push(o);
+                }
+            }
+        }
+        while let Some(node) = to_visit.pop() {
+            for next in edges.get(&node).into_iter().flatten() {
+                if reachable.insert(next.clone()) {
+                    to_visit.push(next.clone());
+                }
+            }
+        }
+        let mut removed = 0;
+        for [s, p, o] in candidates.iter() {
+            if !reachable.contains(s) && self.remove(s, p, o)? {
+                removed += 1;
+            }
+        }
+        Ok(removed)
+    }
 }
 
 /// Marker trait constraining the semantics of
@@ -553,6 +1103,169 @@ pub(crate) fn insert_if_absent<T: Clone + Eq + Hash>(set: &mut HashSet<T>, val:
     }
 }
 
+/// The prefix of `iri` up to (and including) its last `#` or `/`, if any.
+///
+/// IRIs whose last `/` belongs to their authority separator
+/// (such as `http://example.org`) have no namespace.
+pub(crate) fn iri_namespace(iri: &str) -> Option<&str> {
+    let pos = iri.rfind(['#', '/'])?;
+    let ns = &iri[..=pos];
+    if ns.ends_with("//") {
+        None
+    } else {
+        Some(ns)
+    }
+}
+
+/// Shared implementation of `Graph::describe` and `Graph::describe_symmetric`.
+fn concise_bounded_description<G, T>(
+    g: &G,
+    node: &Term<T>,
+    symmetric: bool,
+) -> GResult<G, FastGraph>
+where
+    G: Graph + ?Sized,
+    T: TermData,
+{
+    let mut res = FastGraph::new();
+    let mut described = HashSet::new();
+    let mut to_describe = vec![BoxTerm::from(node)];
+    while let Some(node) = to_describe.pop() {
+        if !described.insert(node.clone()) {
+            continue;
+        }
+        for t in g.triples_with_s(&node) {
+            let t = t?;
+            // FastGraph::MutationError is Infallible
+            res.insert(t.s(), t.p(), t.o()).unwrap();
+            if let Term::BNode(_) = t.o() {
+                to_describe.push(t.o().into());
+            }
+        }
+        if symmetric {
+            for t in g.triples_with_o(&node) {
+                let t = t?;
+                // FastGraph::MutationError is Infallible
+                res.insert(t.s(), t.p(), t.o()).unwrap();
+                if let Term::BNode(_) = t.s() {
+                    to_describe.push(t.s().into());
+                }
+            }
+        }
+    }
+    Ok(res)
+}
+
+/// Shared implementation of `MutableGraph::rewrite_iris`,
+/// `MutableGraph::redact_literals` and `MutableGraph::retype_literals`.
+///
+/// Every triple of `g` with at least one term for which `f` returns `Some`
+/// is replaced by the triple where those terms are substituted;
+/// the returned value is the number of triples that were replaced.
+/// Replacement triples are never inserted twice,
+/// nor inserted if they are already present in `g`.
+fn replace_terms<G, F>(g: &mut G, f: F) -> MGResult<G, usize>
+where
+    G: MutableGraph + ?Sized,
+    F: Fn(&GTerm<G>) -> Option<BoxTerm>,
+    <G as Graph>::Error: Into<G::MutationError>,
+{
+    let mut to_replace = vec![];
+    for t in g.triples() {
+        let t = t.map_err(Into::<G::MutationError>::into)?;
+        let new = [f(t.s()), f(t.p()), f(t.o())];
+        if new.iter().any(Option::is_some) {
+            let old = [
+                BoxTerm::from(t.s()),
+                BoxTerm::from(t.p()),
+                BoxTerm::from(t.o()),
+            ];
+            to_replace.push((old, new));
+        }
+    }
+    // remove all old triples first,
+    // as some of them may be the result of replacing other triples
+    for (old, _) in to_replace.iter() {
+        g.remove(&old[0], &old[1], &old[2])?;
+    }
+    let mut inserted = HashSet::new();
+    for (old, new) in to_replace.iter() {
+        let [s, p, o] = new;
+        let s = s.as_ref().unwrap_or(&old[0]);
+        let p = p.as_ref().unwrap_or(&old[1]);
+        let o = o.as_ref().unwrap_or(&old[2]);
+        if inserted.contains(&[s, p, o]) || g.contains(s, p, o).map_err(Into::into)? {
+            continue;
+        }
+        g.insert(s, p, o)?;
+        inserted.insert([s, p, o]);
+    }
+    Ok(to_replace.len())
+}
+
+/// Whether `t` is an IRI starting with `ns`.
+fn is_in_namespace<T>(t: &Term<T>, ns: &str) -> bool
+where
+    T: TermData,
+{
+    match t {
+        Term::Iri(iri) => iri.to_string_value().starts_with(ns),
+        _ => false,
+    }
+}
+
+/// Apply `f` to the IRI of `t` (or to its datatype, if it is a literal),
+/// and return the rewritten term, if any.
+fn rewrite_term<T, F>(t: &Term<T>, f: &F) -> Option<BoxTerm>
+where
+    T: TermData,
+    F: Fn(&str) -> Option<String>,
+{
+    let rewrite = |iri: &str| f(iri).and_then(|new| Iri::<Box<str>>::new(new).ok());
+    match t {
+        Term::Iri(iri) => rewrite(&iri.value()).map(Into::into),
+        Term::Literal(lit) if lit.lang().is_none() => {
+            let dt = rewrite(&lit.dt().value())?;
+            Some(Literal::<Box<str>>::new_dt(lit.txt().as_ref(), dt).into())
+        }
+        _ => None,
+    }
+}
+
+/// If `t` is a literal whose lexical form satisfies `pred`,
+/// return a copy of it with `replacement` as its lexical form.
+fn redact_term<T, F>(t: &Term<T>, pred: &F, replacement: &str) -> Option<BoxTerm>
+where
+    T: TermData,
+    F: Fn(&str) -> bool,
+{
+    match t {
+        Term::Literal(lit) if pred(lit.txt().as_ref()) => Some(
+            match lit.lang() {
+                Some(tag) => Literal::<Box<str>>::new_lang_unchecked(replacement, tag.as_ref()),
+                None => Literal::<Box<str>>::new_dt(replacement, &lit.dt()),
+            }
+            .into(),
+        ),
+        _ => None,
+    }
+}
+
+/// If `t` is a literal of datatype `from`,
+/// return a copy of it with `to` as its datatype.
+fn retype_term<T, U>(t: &Term<T>, from: &Iri<U>, to: &Iri<Box<str>>) -> Option<BoxTerm>
+where
+    T: TermData,
+    U: TermData,
+{
+    match t {
+        Term::Literal(lit) if lit.lang().is_none() && lit.dt() == *from => {
+            Some(Literal::<Box<str>>::new_dt(lit.txt().as_ref(), to.clone()).into())
+        }
+        _ => None,
+    }
+}
+
 #[cfg(test)]
 mod test {
     // The code from this module is tested through its use in other modules
@@ -43,6 +43,14 @@ where
         Box::new(self.0.borrow().triples().map_ok(StreamedQuad::from_triple))
     }
     #[inline]
+    fn len(&self) -> DResult<Self, usize> {
+        self.0.borrow().len()
+    }
+    #[inline]
+    fn is_empty(&self) -> DResult<Self, bool> {
+        self.0.borrow().is_empty()
+    }
+    #[inline]
     fn quads_with_s<'s, T>(&'s self, s: &'s Term<T>) -> DQuadSource<'s, Self>
     where
         T: TermData,
@@ -2,10 +2,13 @@
 
 use std::collections::hash_map::Entry;
 use std::collections::HashMap;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 
 use sophia_term::*;
 
+use crate::graph::Graph;
+use crate::triple::Triple;
+
 /// A utility trait for implementing [`Graph`] and [`MutableGraph`]
 /// based on an internal [`TermIndexMap`] for efficient storage.
 ///
@@ -57,6 +60,29 @@ pub trait IndexedGraph {
         U: TermData,
         V: TermData;
 
+    /// Remove all triples from this Graph.
+    ///
+    /// The default implementation removes the triples one by one,
+    /// and should be overridden by implementations that can do better.
+    /// It panics if [`triples`](../trait.Graph.html#tymethod.triples) yields an error,
+    /// so implementations whose `triples` may fail must override it.
+    fn clear_indexed(&mut self)
+    where
+        Self: Graph + Sized,
+    {
+        // errors can not be reported here,
+        // but in-memory graphs built on IndexedGraph never raise any
+        // (their Graph::Error is Infallible)
+        let to_remove: Vec<[BoxTerm; 3]> = self
+            .triples()
+            .map(Result::unwrap)
+            .map(|t| [t.s().into(), t.p().into(), t.o().into()])
+            .collect();
+        for [s, p, o] in to_remove.iter() {
+            self.remove_indexed(s, p, o);
+        }
+    }
+
     fn shrink_to_fit(&mut self);
 }
 
@@ -88,6 +114,10 @@ macro_rules! impl_mutable_graph_for_indexed_graph {
         {
             Ok(self.remove_indexed(s, p, o).is_some())
         }
+        fn clear(&mut self) -> MGResult<Self, ()> {
+            self.clear_indexed();
+            Ok(())
+        }
     };
 }
 
@@ -99,10 +129,11 @@ macro_rules! impl_mutable_graph_for_indexed_graph {
 /// `true` if the Vec was created,
 ///  meaning that "parent" indexes need to be updated.
 ///
-pub(crate) fn insert_in_index<K, W>(hm: &mut HashMap<K, Vec<W>>, k: K, w: W) -> bool
+pub(crate) fn insert_in_index<K, W, S>(hm: &mut HashMap<K, Vec<W>, S>, k: K, w: W) -> bool
 where
     K: Eq + Hash,
     W: Copy + Eq,
+    S: BuildHasher,
 {
     let mut ret = false;
     hm.entry(k)
@@ -127,10 +158,11 @@ where
 /// This function will panic if either
 /// * `k` is not a key of `hm`, or
 /// * `w` is not contained in the value associated to `k`.
-pub(crate) fn remove_from_index<K, W>(hm: &mut HashMap<K, Vec<W>>, k: K, w: W) -> bool
+pub(crate) fn remove_from_index<K, W, S>(hm: &mut HashMap<K, Vec<W>, S>, k: K, w: W) -> bool
 where
     K: Eq + Hash,
     W: Copy + Eq,
+    S: BuildHasher,
 {
     match hm.entry(k) {
         Entry::Occupied(mut e) => {
@@ -32,8 +32,15 @@
 //! [`FastGraph`]: type.FastGraph.html
 //! [`LightGraph`]: type.LightGraph.html
 
+use std::collections::hash_map::RandomState;
+use std::convert::Infallible;
+use std::fmt;
+use std::hash::BuildHasher;
+use std::iter::{Extend, FromIterator};
+
 use super::_traits::*;
 use super::indexed::*;
+use crate::triple::Triple;
 use sophia_term::factory::*;
 use sophia_term::*;
 
@@ -46,6 +53,8 @@ mod _spo_wrapper;
 pub use self::_spo_wrapper::*;
 mod _ops_wrapper;
 pub use self::_ops_wrapper::*;
+mod _sorted_vec;
+pub use self::_sorted_vec::*;
 mod _term_index_map_u;
 pub use self::_term_index_map_u::*;
 
@@ -57,23 +66,383 @@ pub use self::_term_index_map_u::*;
 ///
 /// `F` must implement [`TermFactory`](../../term/factory/trait.TermFactory.html).
 ///
-pub type GenericGraph<I, F> = HashGraph<TermIndexMapU<I, F>>;
+/// The optional parameter `S` is the hashing algorithm used to store terms and triples.
+pub type GenericGraph<I, F, S = RandomState> = HashGraph<TermIndexMapU<I, F, S>, S>;
 
-type FastWrapper<T> = OpsWrapper<SpoWrapper<T>>;
+type FastWrapper<T, S> = OpsWrapper<SpoWrapper<T, S>, S>;
 
 /// A heavily indexed graph.
 /// Fast to query but slow to load, with a relatively high memory footprint.
-pub type FastGraph = FastWrapper<GenericGraph<u32, RcTermFactory>>;
+///
+/// The optional parameter `S` is the hashing algorithm used by the graph and its indexes.
+/// For example, `FastGraph::<BuildHasherDefault<MyHasher>>::default()`
+/// builds a graph whose hash maps all use `MyHasher`.
+pub type FastGraph<S = RandomState> = FastWrapper<GenericGraph<u32, RcTermFactory, S>, S>;
 
 /// A graph with no triple index.
 /// Fast to load but slow to query, with a relatively low memory footprint.
-pub type LightGraph = GenericGraph<u32, RcTermFactory>;
+///
+/// The optional parameter `S` is the hashing algorithm used by the graph.
+pub type LightGraph<S = RandomState> = GenericGraph<u32, RcTermFactory, S>;
+
+impl<S> FromIterator<[BoxTerm; 3]> for FastGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = [BoxTerm; 3]>>(iter: T) -> Self {
+        let mut g = Self::default();
+        g.extend(iter);
+        g
+    }
+}
+
+impl<S> Extend<[BoxTerm; 3]> for FastGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn extend<T: IntoIterator<Item = [BoxTerm; 3]>>(&mut self, iter: T) {
+        for t in iter {
+            // FastGraph::MutationError is Infallible
+            self.insert(&t[0], &t[1], &t[2]).unwrap();
+        }
+    }
+}
+
+impl<'a, S> Extend<&'a [BoxTerm; 3]> for FastGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn extend<T: IntoIterator<Item = &'a [BoxTerm; 3]>>(&mut self, iter: T) {
+        for t in iter {
+            // FastGraph::MutationError is Infallible
+            self.insert(&t[0], &t[1], &t[2]).unwrap();
+        }
+    }
+}
+
+impl<'a, S> IntoIterator for &'a FastGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    type Item = GTriple<'a, FastGraph<S>>;
+    type IntoIter = Box<dyn Iterator<Item = Self::Item> + 'a>;
+
+    /// Iterate over the triples of this graph.
+    ///
+    /// Unlike [`Graph::triples`](../trait.Graph.html#tymethod.triples),
+    /// triples are not wrapped in a `Result`, as iterating over a `FastGraph` never fails.
+    fn into_iter(self) -> Self::IntoIter {
+        // FastGraph::Error is Infallible
+        Box::new(self.triples().map(Result::unwrap))
+    }
+}
+
+impl<S> FastGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    /// Insert all the given triples,
+    /// and return the number of triples that were not already in this graph.
+    ///
+    /// Unlike repeated calls to [`insert`](../trait.MutableGraph.html#method.insert),
+    /// triples are first all stored in the underlying graph,
+    /// and the indexes are only updated once all triples are stored,
+    /// in a single pass grouping the new triples by index entry.
+    /// As this method borrows the graph mutably until the indexes are built,
+    /// the graph can never be queried with incomplete indexes.
+    pub fn bulk_load<I>(&mut self, triples: I) -> usize
+    where
+        I: IntoIterator,
+        I::Item: Triple,
+    {
+        let spo = self.get_wrapped_mut();
+        let inserted: Vec<_> = {
+            let base = spo.get_wrapped_mut();
+            triples
+                .into_iter()
+                .filter_map(|t| base.insert_indexed(t.s(), t.p(), t.o()))
+                .collect()
+        };
+        spo.igw_hook_bulk_insert_indexed(&inserted);
+        self.igw_hook_bulk_insert_indexed(&inserted);
+        inserted.len()
+    }
+}
+
+impl<S> FromIterator<[BoxTerm; 3]> for LightGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn from_iter<T: IntoIterator<Item = [BoxTerm; 3]>>(iter: T) -> Self {
+        let mut g = Self::default();
+        g.extend(iter);
+        g
+    }
+}
+
+impl<S> Extend<[BoxTerm; 3]> for LightGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn extend<T: IntoIterator<Item = [BoxTerm; 3]>>(&mut self, iter: T) {
+        for t in iter {
+            // LightGraph::MutationError is Infallible
+            self.insert(&t[0], &t[1], &t[2]).unwrap();
+        }
+    }
+}
+
+impl<'a, S> Extend<&'a [BoxTerm; 3]> for LightGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn extend<T: IntoIterator<Item = &'a [BoxTerm; 3]>>(&mut self, iter: T) {
+        for t in iter {
+            // LightGraph::MutationError is Infallible
+            self.insert(&t[0], &t[1], &t[2]).unwrap();
+        }
+    }
+}
+
+/// Graph equality, as defined by [`Graph::graph_eq`](../trait.Graph.html#method.graph_eq).
+///
+/// Note that, unless the `isomorphism` feature is enabled,
+/// a graph containing blank nodes is not even equal to itself.
+impl<S, G> PartialEq<G> for FastGraph<S>
+where
+    S: BuildHasher + Default,
+    G: Graph,
+    G::Error: Into<Infallible>,
+{
+    fn eq(&self, other: &G) -> bool {
+        // FastGraph::Error is Infallible
+        self.graph_eq(other).unwrap()
+    }
+}
+
+impl<S> fmt::Debug for FastGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            .entries(
+                self.into_iter()
+                    .map(|t| [t.s().clone(), t.p().clone(), t.o().clone()]),
+            )
+            .finish()
+    }
+}
+
+/// Graph equality, as defined by [`Graph::graph_eq`](../trait.Graph.html#method.graph_eq).
+///
+/// Note that, unless the `isomorphism` feature is enabled,
+/// a graph containing blank nodes is not even equal to itself.
+impl<S, G> PartialEq<G> for LightGraph<S>
+where
+    S: BuildHasher + Default,
+    G: Graph,
+    G::Error: Into<Infallible>,
+{
+    fn eq(&self, other: &G) -> bool {
+        // LightGraph::Error is Infallible
+        self.graph_eq(other).unwrap()
+    }
+}
+
+impl<S> fmt::Debug for LightGraph<S>
+where
+    S: BuildHasher + Default,
+{
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_set()
+            // LightGraph::Error is Infallible
+            .entries(
+                self.triples()
+                    .map(Result::unwrap)
+                    .map(|t| [t.s().clone(), t.p().clone(), t.o().clone()]),
+            )
+            .finish()
+    }
+}
 
 #[cfg(test)]
 test_graph_impl!(test_fastg, FastGraph);
 #[cfg(test)]
 test_graph_impl!(test_lightg, LightGraph);
 
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::rdf;
+    use crate::triple::Triple;
+    use std::collections::hash_map::DefaultHasher;
+    use std::hash::{BuildHasherDefault, Hasher};
+
+    /// A (poor) custom hasher, only meant for testing.
+    #[derive(Default)]
+    struct XorHasher(u64);
+
+    impl Hasher for XorHasher {
+        fn finish(&self) -> u64 {
+            self.0
+        }
+        fn write(&mut self, bytes: &[u8]) {
+            for b in bytes {
+                self.0 = self.0.rotate_left(8) ^ u64::from(*b);
+            }
+        }
+    }
+
+    fn check_custom_hasher<G: MutableGraph>(mut g: G) {
+        let s = StaticTerm::new_iri("http://example.org/s").unwrap();
+        let o1 = StaticTerm::new_iri("http://example.org/o1").unwrap();
+        let o2 = StaticTerm::new_literal_lang("o2", "en").unwrap();
+        assert!(g.insert(&s, &rdf::type_, &o1).unwrap());
+        assert!(g.insert(&s, &rdf::value, &o2).unwrap());
+        assert!(g.insert(&o1, &rdf::type_, &o1).unwrap());
+        assert!(!g.insert(&s, &rdf::type_, &o1).unwrap());
+        assert_eq!(g.triples_with_s(&s).count(), 2);
+        assert_eq!(g.triples_with_po(&rdf::type_, &o1).count(), 2);
+        assert_eq!(g.triples_with_o(&o2).count(), 1);
+        assert!(g.remove(&s, &rdf::type_, &o1).unwrap());
+        assert_eq!(g.triples_with_s(&s).count(), 1);
+        assert_eq!(g.triples_with_p(&rdf::type_).count(), 1);
+    }
+
+    #[test]
+    fn from_iterator() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let o = BoxTerm::new_iri("http://example.org/o").unwrap();
+        let triples = vec![
+            [s.clone(), BoxTerm::from(&rdf::type_), o.clone()],
+            [s.clone(), BoxTerm::from(&rdf::value), o.clone()],
+            [s.clone(), BoxTerm::from(&rdf::type_), o.clone()],
+        ];
+        let g: FastGraph = triples.iter().cloned().collect();
+        assert_eq!(g.triples().count(), 2);
+        assert_eq!(g.triples_with_p(&rdf::type_).count(), 1);
+        let mut g2: LightGraph<BuildHasherDefault<XorHasher>> =
+            triples.iter().take(1).cloned().collect();
+        g2.extend(triples.iter().skip(1));
+        assert_eq!(g2.triples().count(), 2);
+        let g: LightGraph = triples.into_iter().collect();
+        assert_eq!(g.triples().count(), 2);
+        assert!(g2 == g);
+    }
+
+    #[test]
+    fn extend() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let o = BoxTerm::new_iri("http://example.org/o").unwrap();
+        let mut g: FastGraph = vec![[s.clone(), BoxTerm::from(&rdf::type_), o.clone()]]
+            .into_iter()
+            .collect();
+        let triples = vec![
+            [s.clone(), BoxTerm::from(&rdf::type_), o.clone()],
+            [s.clone(), BoxTerm::from(&rdf::value), o.clone()],
+        ];
+        g.extend(&triples);
+        assert_eq!(g.triples().count(), 2);
+        g.extend(vec![[o.clone(), BoxTerm::from(&rdf::value), s.clone()]]);
+        assert_eq!(g.triples().count(), 3);
+        assert_eq!(g.triples_with_s(&o).count(), 1);
+    }
+
+    #[test]
+    fn into_iterator() {
+        let mut g = FastGraph::new();
+        crate::graph::test::populate(&mut g).unwrap();
+        let mut count = 0;
+        for t in &g {
+            assert!(g.contains(t.s(), t.p(), t.o()).unwrap());
+            count += 1;
+        }
+        assert_eq!(count, g.triples().count());
+    }
+
+    #[test]
+    fn partial_eq() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let o = BoxTerm::new_literal_lang("o", "en").unwrap();
+        let triples = [
+            [s.clone(), BoxTerm::from(&rdf::type_), s.clone()],
+            [s.clone(), BoxTerm::from(&rdf::value), o.clone()],
+        ];
+        let g1: FastGraph = triples.iter().cloned().collect();
+        let g2: FastGraph = triples.iter().rev().cloned().collect();
+        assert_eq!(g1, g2);
+        let g3: LightGraph = triples.iter().cloned().collect();
+        assert!(g1 == g3);
+        let g4: FastGraph = triples.iter().take(1).cloned().collect();
+        assert_ne!(g1, g4);
+
+        let b = BoxTerm::new_bnode("b").unwrap();
+        let g5: FastGraph = vec![[b.clone(), BoxTerm::from(&rdf::value), o]]
+            .into_iter()
+            .collect();
+        assert_eq!(g5 == g5, cfg!(feature = "isomorphism"));
+    }
+
+    #[test]
+    fn bulk_load() {
+        let mut g1 = FastGraph::new();
+        crate::graph::test::populate(&mut g1).unwrap();
+        let triples = g1.sample(100).unwrap();
+        assert_eq!(triples.len(), 18);
+
+        let mut g2 = FastGraph::new();
+        assert_eq!(g2.bulk_load(triples.iter().take(5)), 5);
+        assert_eq!(g2.bulk_load(triples.clone()), 13);
+        assert_eq!(g2.bulk_load(triples.clone()), 0);
+
+        // compare with a graph built by repeated calls to insert
+        let mut g3 = FastGraph::new();
+        for [s, p, o] in &triples {
+            g3.insert(s, p, o).unwrap();
+        }
+        assert_eq!(g2, g3);
+        assert_eq!(g2.len().unwrap(), g3.len().unwrap());
+        assert_eq!(g2.subjects().unwrap(), g3.subjects().unwrap());
+        assert_eq!(g2.objects().unwrap(), g3.objects().unwrap());
+        assert_eq!(g2, g1);
+        for [s, p, o] in &triples {
+            assert!(g2.contains(s, p, o).unwrap());
+            assert_eq!(g2.triples_with_s(s).count(), g1.triples_with_s(s).count());
+            assert_eq!(
+                g2.triples_with_sp(s, p).count(),
+                g1.triples_with_sp(s, p).count()
+            );
+            assert_eq!(g2.triples_with_o(o).count(), g1.triples_with_o(o).count());
+            assert_eq!(
+                g2.triples_with_po(p, o).count(),
+                g1.triples_with_po(p, o).count()
+            );
+        }
+
+        // the indexes are kept consistent by subsequent mutations
+        let [s, p, o] = &triples[0];
+        assert!(g2.remove(s, p, o).unwrap());
+        assert_eq!(
+            g2.triples_with_s(s).count() + 1,
+            g1.triples_with_s(s).count()
+        );
+        assert_eq!(
+            g2.triples_with_o(o).count() + 1,
+            g1.triples_with_o(o).count()
+        );
+    }
+
+    #[test]
+    fn custom_hasher() {
+        check_custom_hasher(FastGraph::<BuildHasherDefault<XorHasher>>::default());
+        check_custom_hasher(FastGraph::<BuildHasherDefault<DefaultHasher>>::default());
+        check_custom_hasher(small::FastGraph::<BuildHasherDefault<XorHasher>>::default());
+        check_custom_hasher(LightGraph::<BuildHasherDefault<XorHasher>>::default());
+        check_custom_hasher(small::LightGraph::<BuildHasherDefault<XorHasher>>::default());
+    }
+}
+
 /// Flavours of Graph implementations with a smaller memory-footprint.
 ///
 /// The trade-off is that these implementations can only contain a small number (2^16) of terms.
@@ -83,10 +452,10 @@ pub mod small {
 
     /// A heavily indexed graph.
     /// Fast to query but slow to load, with a relatively high memory footprint.
-    pub type FastGraph = FastWrapper<GenericGraph<u16, RcTermFactory>>;
+    pub type FastGraph<S = RandomState> = FastWrapper<GenericGraph<u16, RcTermFactory, S>, S>;
     /// A graph with no triple index.
     /// Fast to load but slow to query, with a relatively low memory footprint.
-    pub type LightGraph = GenericGraph<u16, RcTermFactory>;
+    pub type LightGraph<S = RandomState> = GenericGraph<u16, RcTermFactory, S>;
 
     #[cfg(test)]
     test_graph_impl!(test_fastg, FastGraph);
@@ -100,10 +469,10 @@ pub mod sync {
 
     /// A heavily indexed graph.
     /// Fast to query but slow to load, with a relatively high memory footprint.
-    pub type FastGraph = FastWrapper<GenericGraph<u32, ArcTermFactory>>;
+    pub type FastGraph<S = RandomState> = FastWrapper<GenericGraph<u32, ArcTermFactory, S>, S>;
     /// A graph with no triple index.
     /// Fast to load but slow to query, with a relatively low memory footprint.
-    pub type LightGraph = GenericGraph<u32, ArcTermFactory>;
+    pub type LightGraph<S = RandomState> = GenericGraph<u32, ArcTermFactory, S>;
 
     #[cfg(test)]
     test_graph_impl!(test_fastg, FastGraph);
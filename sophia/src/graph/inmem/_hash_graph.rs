@@ -1,8 +1,9 @@
 // this module is transparently re-exported by its parent `graph::inmem`
 
+use std::collections::hash_map::RandomState;
 use std::collections::HashSet;
 use std::convert::Infallible;
-use std::hash::Hash;
+use std::hash::{BuildHasher, Hash};
 
 use crate::graph::indexed::IndexedGraph;
 use crate::graph::*;
@@ -19,15 +20,17 @@ use sophia_term::{RefTerm, Term, TermData};
 /// [`MutableGraph`]: ../trait.MutableGraph.html
 /// [`TermIndexMap`]: ../../term/index_map/trait.TermIndexMap.html
 /// [`HashSet`]: https://doc.rust-lang.org/std/collections/struct.HashSet.html
+///
+/// The optional parameter `S` is the hashing algorithm used by the `HashSet`.
 #[derive(Default)]
-pub struct HashGraph<I>
+pub struct HashGraph<I, S = RandomState>
 where
     I: TermIndexMap,
     I::Index: Hash,
     <I::Factory as TermFactory>::TermData: 'static,
 {
     terms: I,
-    triples: HashSet<[I::Index; 3]>,
+    triples: HashSet<[I::Index; 3], S>,
 }
 
 impl<I> HashGraph<I>
@@ -41,21 +44,14 @@ where
             triples: HashSet::new(),
         }
     }
-
-    pub fn len(&self) -> usize {
-        self.triples.len()
-    }
-
-    pub fn is_empty(&self) -> bool {
-        self.triples.is_empty()
-    }
 }
 
-impl<I> IndexedGraph for HashGraph<I>
+impl<I, S> IndexedGraph for HashGraph<I, S>
 where
     I: TermIndexMap,
     I::Index: Hash,
     <I::Factory as TermFactory>::TermData: 'static,
+    S: BuildHasher + Default,
 {
     type Index = I::Index;
     type TermData = <I::Factory as TermFactory>::TermData;
@@ -124,17 +120,24 @@ where
         None
     }
 
+    fn clear_indexed(&mut self) {
+        // the capacity of self.triples is kept, in case the graph is re-populated
+        self.terms = I::default();
+        self.triples.clear();
+    }
+
     fn shrink_to_fit(&mut self) {
         self.terms.shrink_to_fit();
         self.triples.shrink_to_fit();
     }
 }
 
-impl<I> Graph for HashGraph<I>
+impl<I, S> Graph for HashGraph<I, S>
 where
     I: TermIndexMap,
     I::Index: Hash,
     <I::Factory as TermFactory>::TermData: 'static,
+    S: BuildHasher + Default,
 {
     type Triple = ByTermRefs<<Self as IndexedGraph>::TermData>;
     type Error = Infallible;
@@ -148,21 +151,31 @@ where
             ))
         }))
     }
+
+    fn len(&self) -> GResult<Self, usize> {
+        Ok(self.triples.len())
+    }
+
+    fn is_empty(&self) -> GResult<Self, bool> {
+        Ok(self.triples.is_empty())
+    }
 }
 
-impl<I> MutableGraph for HashGraph<I>
+impl<I, S> MutableGraph for HashGraph<I, S>
 where
     I: TermIndexMap,
     I::Index: Hash,
     <I::Factory as TermFactory>::TermData: 'static,
+    S: BuildHasher + Default,
 {
     impl_mutable_graph_for_indexed_graph!();
 }
 
-impl<I> SetGraph for HashGraph<I>
+impl<I, S> SetGraph for HashGraph<I, S>
 where
     I: TermIndexMap,
     I::Index: Hash,
+    S: BuildHasher,
 {
 }
 
@@ -1,6 +1,8 @@
 // this module is transparently re-exported by its parent `graph::inmem`
 
+use std::collections::hash_map::{Entry, RandomState};
 use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
 use std::iter::empty;
 
 use crate::triple::streaming_mode::{ByTermRefs, StreamedTriple};
@@ -16,14 +18,17 @@ use super::*;
 /// Since it must be able to produce triples instead of the underlying graphs,
 /// it is limited to wrapping graphs whose triples are `[&Term<H>;3]`.
 ///
+/// The hash maps of the index are parameterized by `S`,
+/// so that a faster hashing algorithm than the default one can be used.
+///
 #[derive(Default)]
-pub struct OpsWrapper<T>
+pub struct OpsWrapper<T, S = RandomState>
 where
     T: IndexedGraph,
 {
     wrapped: T,
-    o2p: HashMap<T::Index, Vec<T::Index>>,
-    po2s: HashMap<[T::Index; 2], Vec<T::Index>>,
+    o2p: HashMap<T::Index, Vec<T::Index>, S>,
+    po2s: HashMap<[T::Index; 2], Vec<T::Index>, S>,
 }
 
 impl<T> OpsWrapper<T>
@@ -36,8 +41,9 @@ where
     }
 }
 
-impl<T> GraphWrapper for OpsWrapper<T>
+impl<T, S> GraphWrapper for OpsWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     type Wrapped = T;
@@ -104,8 +110,9 @@ where
     }
 }
 
-impl<T> IndexedGraphWrapper<T> for OpsWrapper<T>
+impl<T, S> IndexedGraphWrapper<T> for OpsWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph,
 {
     #[inline]
@@ -117,6 +124,24 @@ where
         }
     }
 
+    fn igw_hook_bulk_insert_indexed(&mut self, inserted: &[[T::Index; 3]]) {
+        // group the new triples by key, so that each entry of the index is updated once
+        let mut groups: HashMap<[T::Index; 2], Vec<T::Index>> = HashMap::new();
+        for [si, pi, oi] in inserted.iter().copied() {
+            groups.entry([pi, oi]).or_default().push(si);
+        }
+        self.po2s.reserve(groups.len());
+        for ([pi, oi], sis) in groups {
+            match self.po2s.entry([pi, oi]) {
+                Entry::Occupied(mut e) => e.get_mut().extend(sis),
+                Entry::Vacant(e) => {
+                    e.insert(sis);
+                    insert_in_index(&mut self.o2p, oi, pi);
+                }
+            }
+        }
+    }
+
     #[inline]
     fn igw_hook_remove_indexed(&mut self, modified: &Option<[T::Index; 3]>) {
         if let Some([si, pi, oi]) = *modified {
@@ -126,6 +151,12 @@ where
         }
     }
 
+    #[inline]
+    fn igw_hook_clear_indexed(&mut self) {
+        self.o2p.clear();
+        self.po2s.clear();
+    }
+
     #[inline]
     fn igw_hook_shrink_to_fit(&mut self) {
         self.o2p.shrink_to_fit();
@@ -133,28 +164,31 @@ where
     }
 }
 
-impl<T> Graph for OpsWrapper<T>
+impl<T, S> Graph for OpsWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     impl_graph_for_wrapper!();
 }
 
-impl<T> IndexedGraph for OpsWrapper<T>
+impl<T, S> IndexedGraph for OpsWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     impl_indexed_graph_for_wrapper!();
 }
 
-impl<T> MutableGraph for OpsWrapper<T>
+impl<T, S> MutableGraph for OpsWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     impl_mutable_graph_for_indexed_graph!();
 }
 
-impl<T> SetGraph for OpsWrapper<T> where T: IndexedGraph + SetGraph {}
+impl<T, S> SetGraph for OpsWrapper<T, S> where T: IndexedGraph + SetGraph {}
 
 #[cfg(test)]
 type OpsGraph = OpsWrapper<LightGraph>;
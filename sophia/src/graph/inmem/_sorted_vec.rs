@@ -0,0 +1,71 @@
+// this module is transparently re-exported by its parent `graph::inmem`
+
+use std::convert::Infallible;
+
+use crate::triple::streaming_mode::ByRef;
+use crate::triple::{sort_triples, TripleOrder};
+
+use super::*;
+
+/// An immutable graph storing its triples in a vector,
+/// sorted according to a given [`TripleOrder`](../../triple/enum.TripleOrder.html).
+///
+/// It is typically used as an input of
+/// [`merge_join`](../../query/join/fn.merge_join.html).
+#[derive(Clone, Debug)]
+pub struct SortedVecGraph {
+    triples: Vec<[BoxTerm; 3]>,
+    order: TripleOrder,
+}
+
+impl SortedVecGraph {
+    /// Build a graph from the given triples, sorting them according to `order`.
+    pub fn new(mut triples: Vec<[BoxTerm; 3]>, order: TripleOrder) -> SortedVecGraph {
+        sort_triples(&mut triples, order);
+        SortedVecGraph { triples, order }
+    }
+
+    /// The order of the triples in this graph.
+    pub fn order(&self) -> TripleOrder {
+        self.order
+    }
+
+    /// The sorted triples of this graph.
+    pub fn as_slice(&self) -> &[[BoxTerm; 3]] {
+        &self.triples
+    }
+}
+
+impl Graph for SortedVecGraph {
+    type Triple = ByRef<[BoxTerm; 3]>;
+    type Error = Infallible;
+
+    #[inline]
+    fn triples(&self) -> GTripleSource<'_, Self> {
+        self.triples.triples()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::rdf;
+
+    #[test]
+    fn test_sorted_vec_graph() {
+        let a = BoxTerm::new_iri("http://example.org/a").unwrap();
+        let b = BoxTerm::new_iri("http://example.org/b").unwrap();
+        let typ = BoxTerm::from(&rdf::type_);
+        let g = SortedVecGraph::new(
+            vec![
+                [b.clone(), typ.clone(), a.clone()],
+                [a.clone(), typ.clone(), b.clone()],
+            ],
+            TripleOrder::SPO,
+        );
+        assert_eq!(g.order(), TripleOrder::SPO);
+        assert_eq!(g.as_slice()[0][0], a);
+        assert_eq!(g.triples().count(), 2);
+        assert!(g.contains(&b, &typ, &a).unwrap());
+    }
+}
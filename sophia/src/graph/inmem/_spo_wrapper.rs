@@ -1,6 +1,8 @@
 // this module is transparently re-exported by its parent `graph::inmem`
 
+use std::collections::hash_map::{Entry, RandomState};
 use std::collections::{HashMap, HashSet};
+use std::hash::BuildHasher;
 use std::iter::empty;
 
 use crate::triple::streaming_mode::{ByTermRefs, StreamedTriple};
@@ -16,14 +18,17 @@ use super::*;
 /// Since it must be able to produce triples instead of the underlying graphs,
 /// it is limited to wrapping graphs whose triples are `[&Term<H>;3]`.
 ///
+/// The hash maps of the index are parameterized by `S`,
+/// so that a faster hashing algorithm than the default one can be used.
+///
 #[derive(Default)]
-pub struct SpoWrapper<T>
+pub struct SpoWrapper<T, S = RandomState>
 where
     T: IndexedGraph,
 {
     wrapped: T,
-    s2p: HashMap<T::Index, Vec<T::Index>>,
-    sp2o: HashMap<[T::Index; 2], Vec<T::Index>>,
+    s2p: HashMap<T::Index, Vec<T::Index>, S>,
+    sp2o: HashMap<[T::Index; 2], Vec<T::Index>, S>,
 }
 
 impl<T> SpoWrapper<T>
@@ -36,8 +41,9 @@ where
     }
 }
 
-impl<T> GraphWrapper for SpoWrapper<T>
+impl<T, S> GraphWrapper for SpoWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     type Wrapped = T;
@@ -104,8 +110,9 @@ where
     }
 }
 
-impl<T> IndexedGraphWrapper<T> for SpoWrapper<T>
+impl<T, S> IndexedGraphWrapper<T> for SpoWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph,
 {
     #[inline]
@@ -117,6 +124,24 @@ where
         }
     }
 
+    fn igw_hook_bulk_insert_indexed(&mut self, inserted: &[[T::Index; 3]]) {
+        // group the new triples by key, so that each entry of the index is updated once
+        let mut groups: HashMap<[T::Index; 2], Vec<T::Index>> = HashMap::new();
+        for [si, pi, oi] in inserted.iter().copied() {
+            groups.entry([si, pi]).or_default().push(oi);
+        }
+        self.sp2o.reserve(groups.len());
+        for ([si, pi], ois) in groups {
+            match self.sp2o.entry([si, pi]) {
+                Entry::Occupied(mut e) => e.get_mut().extend(ois),
+                Entry::Vacant(e) => {
+                    e.insert(ois);
+                    insert_in_index(&mut self.s2p, si, pi);
+                }
+            }
+        }
+    }
+
     #[inline]
     fn igw_hook_remove_indexed(&mut self, modified: &Option<[T::Index; 3]>) {
         if let Some([si, pi, oi]) = *modified {
@@ -126,6 +151,12 @@ where
         }
     }
 
+    #[inline]
+    fn igw_hook_clear_indexed(&mut self) {
+        self.s2p.clear();
+        self.sp2o.clear();
+    }
+
     #[inline]
     fn igw_hook_shrink_to_fit(&mut self) {
         self.s2p.shrink_to_fit();
@@ -133,28 +164,31 @@ where
     }
 }
 
-impl<T> Graph for SpoWrapper<T>
+impl<T, S> Graph for SpoWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     impl_graph_for_wrapper!();
 }
 
-impl<T> IndexedGraph for SpoWrapper<T>
+impl<T, S> IndexedGraph for SpoWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     impl_indexed_graph_for_wrapper!();
 }
 
-impl<T> MutableGraph for SpoWrapper<T>
+impl<T, S> MutableGraph for SpoWrapper<T, S>
 where
+    S: BuildHasher,
     T: IndexedGraph + Graph<Triple = ByTermRefs<<T as IndexedGraph>::TermData>>,
 {
     impl_mutable_graph_for_indexed_graph!();
 }
 
-impl<T> SetGraph for SpoWrapper<T> where T: IndexedGraph + SetGraph {}
+impl<T, S> SetGraph for SpoWrapper<T, S> where T: IndexedGraph + SetGraph {}
 
 #[cfg(test)]
 type SpoGraph = super::SpoWrapper<super::LightGraph>;
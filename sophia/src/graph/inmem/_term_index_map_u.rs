@@ -1,6 +1,8 @@
 // this module is transparently re-exported by its parent `graph::inmem`
 use std::borrow::Borrow;
+use std::collections::hash_map::RandomState;
 use std::collections::HashMap;
+use std::hash::BuildHasher;
 
 use sophia_term::factory::{FTerm, TermFactory};
 use sophia_term::index_map::TermIndexMap;
@@ -8,14 +10,16 @@ use sophia_term::*;
 
 /// An in-memory implementation of [`TermIndexMap`](../../term/index_map/trait.TermIndexMap.html)
 /// with unsigned integers as indices.
-pub struct TermIndexMapU<I, F>
+///
+/// The optional parameter `S` is the hashing algorithm used to look up terms.
+pub struct TermIndexMapU<I, F, S = RandomState>
 where
     F: TermFactory,
 {
     factory: F,
     i2t: Vec<Option<FTerm<F>>>,
     i2c: Vec<I>,
-    t2i: HashMap<StaticTerm, I>,
+    t2i: HashMap<StaticTerm, I, S>,
     // factory is used to make new terms (used by make_term)
     // i2t (index to term) maps
     // - each used index to Some(Term)
@@ -48,12 +52,32 @@ where
     }
 }
 
-impl<I, F> Default for TermIndexMapU<I, F>
+impl<I, F, S> TermIndexMapU<I, F, S>
 where
     I: Unsigned,
     F: TermFactory + Default,
+    S: BuildHasher + Default,
 {
-    fn default() -> TermIndexMapU<I, F> {
+    /// The number of distinct terms currently stored in this map.
+    #[inline]
+    pub fn len(&self) -> usize {
+        self.t2i.len()
+    }
+
+    /// Whether this map stores no term.
+    #[inline]
+    pub fn is_empty(&self) -> bool {
+        self.t2i.is_empty()
+    }
+}
+
+impl<I, F, S> Default for TermIndexMapU<I, F, S>
+where
+    I: Unsigned,
+    F: TermFactory + Default,
+    S: BuildHasher + Default,
+{
+    fn default() -> TermIndexMapU<I, F, S> {
         TermIndexMapU {
             factory: F::default(),
             i2c: vec![I::ONE],
@@ -63,10 +87,11 @@ where
     }
 }
 
-impl<I, F> TermIndexMapU<I, F>
+impl<I, F, S> TermIndexMapU<I, F, S>
 where
     I: Unsigned,
     F: TermFactory + Default,
+    S: BuildHasher + Default,
 {
     #[inline]
     fn next_free(&self) -> I {
@@ -86,10 +111,11 @@ where
 /// where uXX is one of u16, u32...
 /// I would prefer to define a generic implementation using traits,
 /// but I found this to be non trivial.
-impl<T, F> TermIndexMap for TermIndexMapU<T, F>
+impl<T, F, S> TermIndexMap for TermIndexMapU<T, F, S>
 where
     T: Unsigned,
     F: TermFactory + Default,
+    S: BuildHasher + Default,
 {
     type Index = T;
     type Factory = F;
@@ -102,12 +128,13 @@ where
     }
 
     fn make_index(&mut self, t: &RefTerm) -> T {
-        let t = self.factory.clone_term(&t);
-        let rt = unsafe { fake_static(&t) };
-        if let Some(i) = self.get_index(&rt) {
+        // only copy t through the factory if it is not already known
+        if let Some(i) = self.get_index(t) {
             self.i2c[i.as_usize()].inc();
             return i;
         }
+        let t = self.factory.clone_term(t);
+        let rt = unsafe { fake_static(&t) };
         let i = self.next_free();
         self.t2i.insert(rt, i);
         let i = i.as_usize();
@@ -126,6 +126,18 @@ pub trait GraphWrapper {
         self.get_wrapped().contains(s, p, o)
     }
 
+    #[inline]
+    /// Mimmic the [`len`](../trait.Graph.html#method.len) method.
+    fn gw_len(&self) -> GResult<Self::Wrapped, usize> {
+        self.get_wrapped().len()
+    }
+
+    #[inline]
+    /// Mimmic the [`is_empty`](../trait.Graph.html#method.is_empty) method.
+    fn gw_is_empty(&self) -> GResult<Self::Wrapped, bool> {
+        self.get_wrapped().is_empty()
+    }
+
     #[inline]
     /// Mimmic the [`subjects`](../trait.Graph.html#method.subjects) method.
     fn gw_subjects(&self) -> GResultTermSet<Self::Wrapped> {
@@ -284,6 +296,16 @@ macro_rules! impl_graph_for_wrapper {
             $crate::graph::inmem::GraphWrapper::gw_contains(self, s, p, o)
         }
 
+        #[inline]
+        fn len(&self) -> GResult<Self, usize> {
+            $crate::graph::inmem::GraphWrapper::gw_len(self)
+        }
+
+        #[inline]
+        fn is_empty(&self) -> GResult<Self, bool> {
+            $crate::graph::inmem::GraphWrapper::gw_is_empty(self)
+        }
+
         #[inline]
         fn subjects(&self) -> GResult<Self, std::collections::HashSet<$crate::graph::GTerm<Self>>> {
             $crate::graph::inmem::GraphWrapper::gw_subjects(self)
@@ -342,6 +364,27 @@ where
     /// [`IndexedGraph::remove_indexed`](../indexed/trait.IndexedGraph.html#tymethod.remove_indexed).
     fn igw_hook_remove_indexed(&mut self, modified: &Option<[T::Index; 3]>);
 
+    /// Hook to be executed once a batch of triples has been inserted in the wrapped graph
+    /// (see [`FastGraph::bulk_load`](./type.FastGraph.html#method.bulk_load)),
+    /// `inserted` containing the indices of the triples that were not already in it.
+    ///
+    /// The default implementation calls
+    /// [`igw_hook_insert_indexed`](#tymethod.igw_hook_insert_indexed) for each triple;
+    /// wrappers maintaining their own indexes should override it
+    /// to build them in a single pass.
+    fn igw_hook_bulk_insert_indexed(&mut self, inserted: &[[T::Index; 3]]) {
+        for modified in inserted {
+            self.igw_hook_insert_indexed(&Some(*modified));
+        }
+    }
+
+    /// Hook to be executed at the end of
+    /// [`IndexedGraph::clear_indexed`](../indexed/trait.IndexedGraph.html#method.clear_indexed).
+    ///
+    /// The default implementation does nothing;
+    /// wrappers maintaining their own indexes must override it.
+    fn igw_hook_clear_indexed(&mut self) {}
+
     /// Hook to be executed at the end of
     /// [`IndexedGraph::shrink_to_fit`](../indexed/trait.IndexedGraph.html#tymethod.shrink_to_fit).
     fn igw_hook_shrink_to_fit(&mut self);
@@ -402,6 +445,11 @@ macro_rules! impl_indexed_graph_for_wrapper {
             modified
         }
 
+        fn clear_indexed(&mut self) {
+            self.get_wrapped_mut().clear_indexed();
+            self.igw_hook_clear_indexed();
+        }
+
         fn shrink_to_fit(&mut self) {
             self.get_wrapped_mut().shrink_to_fit();
             self.igw_hook_shrink_to_fit();
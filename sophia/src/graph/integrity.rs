@@ -0,0 +1,416 @@
+//! Stable digests of graphs and datasets, for detecting changes.
+//!
+//! Only digests are provided:
+//! the canonical blank node labelling computed to produce them is not exposed.
+//!
+//! This module is only available when the feature `integrity` is enabled.
+
+use std::collections::{BTreeMap, HashMap, HashSet};
+
+use sha2::{Digest, Sha256};
+use sophia_term::*;
+
+use crate::dataset::{DResult, Dataset};
+use crate::graph::*;
+use crate::quad::Quad;
+use crate::triple::Triple;
+
+/// Type of the digests produced by this module.
+pub type GraphDigest = [u8; 32];
+
+/// How blank nodes are scoped when computing the digest of a dataset
+/// (see [`dataset_hash`](fn.dataset_hash.html)).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum HashBnodeScope {
+    /// Blank nodes are shared by all the graphs of the dataset,
+    /// as in RDF 1.1 datasets (and URDNA2015):
+    /// a blank node identifier occurring in two graphs denotes a single node,
+    /// labelled according to its triples in both graphs.
+    Dataset,
+    /// Each graph has its own blank nodes, as if each graph was a separate document:
+    /// a blank node identifier occurring in two graphs denotes two distinct nodes,
+    /// each labelled according to its triples in its own graph only.
+    ///
+    /// Blank nodes used as graph names are still scoped to the whole dataset.
+    Graph,
+}
+
+/// Compute a SHA-256 digest of graph `g`.
+///
+/// The digest does not depend on the order in which `g` yields its triples,
+/// nor on duplicate triples, nor on the identifiers of blank nodes:
+/// isomorphic graphs always produce the same digest,
+/// and (barring SHA-256 collisions) non-isomorphic graphs produce different digests.
+///
+/// Blank nodes are canonically labelled by iteratively hashing their neighbourhood
+/// (a.k.a. colour refinement);
+/// as in URDNA2015, blank nodes that this can not distinguish
+/// are then distinguished by trying each of them in turn,
+/// and keeping the smallest resulting serialization.
+/// Consequently, this may take exponential time
+/// on graphs with large and highly symmetric blank node structures.
+pub fn graph_hash<G>(g: &G) -> GResult<G, GraphDigest>
+where
+    G: Graph + ?Sized,
+{
+    let mut triples = Vec::new();
+    for t in g.triples() {
+        let t = t?;
+        triples.push([encode(t.s()), encode(t.p()), encode(t.o())]);
+    }
+    Ok(digest(triples))
+}
+
+/// Compute a SHA-256 digest of dataset `d`,
+/// with blank nodes scoped according to `scope`.
+///
+/// As with [`graph_hash`](fn.graph_hash.html),
+/// the digest does not depend on the order in which `d` yields its quads,
+/// nor on duplicate quads, nor on the identifiers of blank nodes.
+/// However, two datasets using the same blank node in several graphs,
+/// and distinct blank nodes in each graph, respectively,
+/// have different digests with `HashBnodeScope::Dataset`,
+/// but may have the same digest with `HashBnodeScope::Graph`.
+pub fn dataset_hash<D>(d: &D, scope: HashBnodeScope) -> DResult<D, GraphDigest>
+where
+    D: Dataset + ?Sized,
+{
+    let mut quads = Vec::new();
+    for q in d.quads() {
+        let q = q?;
+        // the default graph is encoded as the empty string, which is never a valid term
+        let g = q
+            .g()
+            .map(encode)
+            .unwrap_or_else(|| Encoded::Ground(String::new()));
+        quads.push([
+            scoped(encode(q.s()), &g, scope),
+            scoped(encode(q.p()), &g, scope),
+            scoped(encode(q.o()), &g, scope),
+            g,
+        ]);
+    }
+    Ok(digest(quads))
+}
+
+/// Hash the given rows of encoded terms,
+/// regardless of their order, duplicates, and blank node identifiers.
+fn digest<R>(mut rows: Vec<R>) -> GraphDigest
+where
+    R: AsRef<[Encoded]> + Ord,
+{
+    rows.sort();
+    rows.dedup();
+
+    let neighbourhood = neighbourhood(&rows);
+    let colors = neighbourhood
+        .keys()
+        .map(|id| (*id, String::new()))
+        .collect();
+    let colors = refine_colors(&neighbourhood, colors);
+    let lines = canonical_lines(&rows, &neighbourhood, colors);
+
+    let mut hasher = Sha256::new();
+    for line in lines {
+        hasher.update(line.as_bytes());
+        hasher.update(b"\n");
+    }
+    let mut digest = [0; 32];
+    digest.copy_from_slice(&hasher.finalize());
+    digest
+}
+
+/// Make `e` specific to graph `g` if it is a blank node and `scope` is `HashBnodeScope::Graph`.
+fn scoped(e: Encoded, g: &Encoded, scope: HashBnodeScope) -> Encoded {
+    match (scope, e) {
+        // blank node identifiers contain no space, so this can not be ambiguous;
+        // and ground graph names are IRIs (starting with '<') or the default graph (""),
+        // so they can not be confused with blank node graph names (starting with "_:")
+        (HashBnodeScope::Graph, Encoded::BNode(id)) => Encoded::BNode(match g {
+            Encoded::Ground(txt) => format!("{} {}", txt, id),
+            Encoded::BNode(gid) => format!("_:{} {}", gid, id),
+        }),
+        (_, e) => e,
+    }
+}
+
+/// A term, encoded in N-Triples (`Ground`), or a blank node identifier.
+#[derive(Clone, Debug, Eq, Hash, Ord, PartialEq, PartialOrd)]
+enum Encoded {
+    Ground(String),
+    BNode(String),
+}
+
+impl Encoded {
+    /// The label of this term, where blank nodes are replaced by their color,
+    /// except for `this` (if any).
+    fn label(&self, colors: &HashMap<&str, String>, this: Option<&str>) -> String {
+        match self {
+            Encoded::Ground(txt) => txt.clone(),
+            Encoded::BNode(id) if Some(id.as_str()) == this => "_:#".to_string(),
+            Encoded::BNode(id) => format!("_:{}", colors[id.as_str()]),
+        }
+    }
+}
+
+fn encode<T: TermData>(t: &Term<T>) -> Encoded {
+    match t {
+        Term::BNode(bn) => Encoded::BNode(bn.value().to_string()),
+        Term::Literal(lit) if lit.lang().is_some() => {
+            // language tags are case-insensitive
+            let txt = t.to_string();
+            let at = txt.rfind('@').unwrap();
+            Encoded::Ground(format!("{}{}", &txt[..at], txt[at..].to_ascii_lowercase()))
+        }
+        _ => Encoded::Ground(t.to_string()),
+    }
+}
+
+/// The rows (triples or quads) in which each blank node occurs.
+type Neighbourhood<'a, R> = HashMap<&'a str, Vec<&'a R>>;
+
+fn neighbourhood<R>(rows: &[R]) -> Neighbourhood<'_, R>
+where
+    R: AsRef<[Encoded]> + PartialEq,
+{
+    let mut neighbourhood: Neighbourhood<R> = HashMap::new();
+    for t in rows {
+        for e in t.as_ref().iter() {
+            if let Encoded::BNode(id) = e {
+                let triples_of_id = neighbourhood.entry(id.as_str()).or_default();
+                if triples_of_id.last() != Some(&t) {
+                    triples_of_id.push(t);
+                }
+            }
+        }
+    }
+    neighbourhood
+}
+
+/// Refine `colors` so that blank nodes with the same color
+/// have the same colors around them,
+/// depending only on the structure of the graph (and on the initial `colors`).
+fn refine_colors<'a, R>(
+    neighbourhood: &Neighbourhood<'a, R>,
+    mut colors: HashMap<&'a str, String>,
+) -> HashMap<&'a str, String>
+where
+    R: AsRef<[Encoded]>,
+{
+    let mut nb_colors = colors.values().collect::<HashSet<_>>().len();
+    loop {
+        let new_colors: HashMap<&str, String> = neighbourhood
+            .iter()
+            .map(|(id, triples_of_id)| {
+                let mut lines: Vec<String> = triples_of_id
+                    .iter()
+                    .map(|t| {
+                        let t: Vec<_> = t
+                            .as_ref()
+                            .iter()
+                            .map(|e| e.label(&colors, Some(*id)))
+                            .collect();
+                        t.join(" ")
+                    })
+                    .collect();
+                lines.sort();
+                let mut hasher = Sha256::new();
+                hasher.update(colors[id].as_bytes());
+                for line in lines {
+                    hasher.update(b"\n");
+                    hasher.update(line.as_bytes());
+                }
+                (*id, to_hex(&hasher.finalize()))
+            })
+            .collect();
+        let new_nb_colors = new_colors.values().collect::<HashSet<_>>().len();
+        colors = new_colors;
+        if new_nb_colors <= nb_colors {
+            return colors;
+        }
+        nb_colors = new_nb_colors;
+    }
+}
+
+/// Serialize `rows` (sorted), with each blank node replaced by its color
+/// in the canonical labelling refining `colors` (which must be stable).
+///
+/// If several blank nodes share the smallest non-unique color,
+/// each of them is given a distinct color in turn,
+/// and the smallest of the resulting serializations is kept.
+fn canonical_lines<'a, R>(
+    rows: &[R],
+    neighbourhood: &Neighbourhood<'a, R>,
+    colors: HashMap<&'a str, String>,
+) -> Vec<String>
+where
+    R: AsRef<[Encoded]>,
+{
+    let mut classes: BTreeMap<&str, Vec<&'a str>> = BTreeMap::new();
+    for (id, color) in colors.iter() {
+        classes.entry(color.as_str()).or_default().push(*id);
+    }
+    let tie = classes
+        .into_iter()
+        .find(|(_, ids)| ids.len() > 1)
+        .map(|(color, ids)| (format!("{}!", color), ids));
+    match tie {
+        None => {
+            let mut lines: Vec<String> = rows
+                .iter()
+                .map(|r| {
+                    let r: Vec<_> = r.as_ref().iter().map(|e| e.label(&colors, None)).collect();
+                    r.join(" ")
+                })
+                .collect();
+            lines.sort();
+            lines
+        }
+        Some((distinct_color, ids)) => ids
+            .into_iter()
+            .map(|id| {
+                let mut colors = colors.clone();
+                colors.insert(id, distinct_color.clone());
+                let colors = refine_colors(neighbourhood, colors);
+                canonical_lines(rows, neighbourhood, colors)
+            })
+            .min()
+            .unwrap(),
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::inmem::FastGraph;
+    use crate::ns::{rdf, rdfs};
+
+    fn sample(b1: &str, b2: &str) -> FastGraph {
+        let ex = |s: &'static str| StaticTerm::new_iri_suffixed("http://example.org/", s).unwrap();
+        let b1 = BoxTerm::new_bnode(b1).unwrap();
+        let b2 = BoxTerm::new_bnode(b2).unwrap();
+        let mut g = FastGraph::new();
+        g.insert(&b1, &rdf::type_, &ex("Person")).unwrap();
+        g.insert(&b2, &rdf::type_, &ex("Person")).unwrap();
+        g.insert(&b1, &ex("knows"), &b2).unwrap();
+        g.insert(
+            &b1,
+            &rdfs::label,
+            &StaticTerm::new_literal_lang("Alice", "en").unwrap(),
+        )
+        .unwrap();
+        g
+    }
+
+    #[test]
+    fn isomorphic_graphs_have_same_digest() {
+        let g1 = sample("a", "b");
+        let g2 = sample("x", "y");
+        let g3 = sample("b", "a");
+        assert_eq!(graph_hash(&g1).unwrap(), graph_hash(&g2).unwrap());
+        assert_eq!(graph_hash(&g1).unwrap(), graph_hash(&g3).unwrap());
+    }
+
+    #[test]
+    fn modified_graph_has_different_digest() {
+        let g1 = sample("a", "b");
+        let mut g2 = sample("a", "b");
+        g2.remove(
+            &BoxTerm::new_bnode("a").unwrap(),
+            &StaticTerm::new_iri("http://example.org/knows").unwrap(),
+            &BoxTerm::new_bnode("b").unwrap(),
+        )
+        .unwrap();
+        g2.insert(
+            &BoxTerm::new_bnode("b").unwrap(),
+            &StaticTerm::new_iri("http://example.org/knows").unwrap(),
+            &BoxTerm::new_bnode("a").unwrap(),
+        )
+        .unwrap();
+        assert_ne!(graph_hash(&g1).unwrap(), graph_hash(&g2).unwrap());
+    }
+
+    #[test]
+    fn order_and_duplicates_do_not_matter() {
+        let t1 = [
+            StaticTerm::new_iri("http://example.org/s").unwrap(),
+            rdf::type_,
+            StaticTerm::new_literal_lang("x", "EN").unwrap(),
+        ];
+        let t2 = [
+            StaticTerm::new_iri("http://example.org/s").unwrap(),
+            rdfs::label,
+            StaticTerm::new_literal_lang("x", "en").unwrap(),
+        ];
+        let g1 = vec![t1, t2];
+        let g2 = vec![t2, t1, t2];
+        assert_eq!(graph_hash(&g1).unwrap(), graph_hash(&g2).unwrap());
+    }
+
+    #[test]
+    fn regular_graphs_do_not_collide() {
+        // colour refinement alone can not distinguish those graphs
+        let cycles = |prefix: &str, lengths: &[usize]| {
+            let p = StaticTerm::new_iri("http://example.org/p").unwrap();
+            let mut g = FastGraph::new();
+            for (c, len) in lengths.iter().enumerate() {
+                for i in 0..*len {
+                    let b1 = BoxTerm::new_bnode(format!("{}{}n{}", prefix, c, i)).unwrap();
+                    let b2 =
+                        BoxTerm::new_bnode(format!("{}{}n{}", prefix, c, (i + 1) % len)).unwrap();
+                    g.insert(&b1, &p, &b2).unwrap();
+                }
+            }
+            g
+        };
+        assert_ne!(
+            graph_hash(&cycles("c", &[6])).unwrap(),
+            graph_hash(&cycles("c", &[3, 3])).unwrap()
+        );
+        assert_eq!(
+            graph_hash(&cycles("c", &[6])).unwrap(),
+            graph_hash(&cycles("x", &[6])).unwrap()
+        );
+        assert_eq!(
+            graph_hash(&cycles("c", &[3, 3])).unwrap(),
+            graph_hash(&cycles("x", &[3, 3])).unwrap()
+        );
+    }
+
+    #[test]
+    fn dataset_bnode_scope() {
+        let ex = |s: &str| BoxTerm::new_iri(format!("http://example.org/{}", s)).unwrap();
+        let quads = |b1: &str, b2: &str| {
+            let b1 = BoxTerm::new_bnode(b1).unwrap();
+            let b2 = BoxTerm::new_bnode(b2).unwrap();
+            let a = BoxTerm::from(&rdf::type_);
+            vec![
+                ([b1.clone(), a.clone(), ex("Person")], Some(ex("g1"))),
+                ([b1, ex("name"), ex("alice")], None),
+                ([b2, a, ex("Person")], Some(ex("g2"))),
+            ]
+        };
+        // the same blank node is used in both named graphs
+        let shared = quads("b", "b");
+        let renamed = quads("x", "x");
+        // distinct blank nodes are used in each named graph
+        let separate = quads("b", "c");
+
+        let hash = |d: &Vec<_>, scope| dataset_hash(d, scope).unwrap();
+        for scope in &[HashBnodeScope::Dataset, HashBnodeScope::Graph] {
+            assert_eq!(hash(&shared, *scope), hash(&renamed, *scope));
+        }
+        assert_ne!(
+            hash(&shared, HashBnodeScope::Dataset),
+            hash(&separate, HashBnodeScope::Dataset)
+        );
+        assert_eq!(
+            hash(&shared, HashBnodeScope::Graph),
+            hash(&separate, HashBnodeScope::Graph)
+        );
+    }
+}
@@ -0,0 +1,144 @@
+//! Graph isomorphism,
+//! i.e. equality of graphs modulo a renaming of their blank nodes.
+
+use std::collections::{HashMap, HashSet};
+
+use sophia_term::{BoxTerm, Term};
+
+use crate::graph::*;
+use crate::triple::Triple;
+
+type TripleSet = HashSet<[BoxTerm; 3]>;
+
+/// Check whether `g1` and `g2` are isomorphic,
+/// i.e. whether they are equal modulo a renaming of their blank nodes.
+///
+/// This is intended for tests on small graphs:
+/// the worst case complexity of the algorithm is exponential in the number of blank nodes.
+///
+/// # Panics
+///
+/// This function panics if either graph fails to provide its triples.
+pub fn isomorphic_graphs<G1, G2>(g1: &G1, g2: &G2) -> bool
+where
+    G1: Graph,
+    G2: Graph,
+{
+    let (ground1, bnode1) = split_ground(g1);
+    let (ground2, bnode2) = split_ground(g2);
+    if ground1 != ground2 || bnode1.len() != bnode2.len() {
+        return false;
+    }
+    let todo: Vec<_> = bnode1.iter().collect();
+    let candidates: Vec<_> = bnode2.iter().collect();
+    find_bijection(
+        &todo,
+        &candidates,
+        &mut vec![false; candidates.len()],
+        &mut HashMap::new(),
+        &mut HashMap::new(),
+    )
+}
+
+/// Split the triples of `g` into those without and with blank nodes.
+fn split_ground<G: Graph>(g: &G) -> (TripleSet, TripleSet) {
+    let mut ground = TripleSet::new();
+    let mut bnode = TripleSet::new();
+    for t in g.triples() {
+        let t = t.expect("failed to read graph");
+        let t = [t.s().into(), t.p().into(), t.o().into()];
+        if t.iter().any(|term| matches!(term, Term::BNode(_))) {
+            bnode.insert(t);
+        } else {
+            ground.insert(t);
+        }
+    }
+    (ground, bnode)
+}
+
+/// Try to map each triple of `todo` to a distinct triple of `candidates`,
+/// consistently with the (bijective) blank node mapping `map`/`rev`.
+fn find_bijection(
+    todo: &[&[BoxTerm; 3]],
+    candidates: &[&[BoxTerm; 3]],
+    used: &mut [bool],
+    map: &mut HashMap<BoxTerm, BoxTerm>,
+    rev: &mut HashMap<BoxTerm, BoxTerm>,
+) -> bool {
+    let (t1, rest) = match todo.split_first() {
+        None => return true,
+        Some(split) => split,
+    };
+    for (i, t2) in candidates.iter().enumerate() {
+        if used[i] {
+            continue;
+        }
+        let mut added = vec![];
+        if (0..3).all(|k| match_terms(&t1[k], &t2[k], map, rev, &mut added)) {
+            used[i] = true;
+            if find_bijection(rest, candidates, used, map, rev) {
+                return true;
+            }
+            used[i] = false;
+        }
+        for b1 in added {
+            let b2 = map.remove(&b1).unwrap();
+            rev.remove(&b2);
+        }
+    }
+    false
+}
+
+/// Check whether `t1` matches `t2` under the blank node mapping,
+/// extending the mapping (and recording it in `added`) if necessary.
+fn match_terms(
+    t1: &BoxTerm,
+    t2: &BoxTerm,
+    map: &mut HashMap<BoxTerm, BoxTerm>,
+    rev: &mut HashMap<BoxTerm, BoxTerm>,
+    added: &mut Vec<BoxTerm>,
+) -> bool {
+    match (t1, t2) {
+        (Term::BNode(_), Term::BNode(_)) => match (map.get(t1), rev.get(t2)) {
+            (Some(mapped), _) => mapped == t2,
+            (None, Some(_)) => false,
+            (None, None) => {
+                map.insert(t1.clone(), t2.clone());
+                rev.insert(t2.clone(), t1.clone());
+                added.push(t1.clone());
+                true
+            }
+        },
+        (Term::BNode(_), _) | (_, Term::BNode(_)) => false,
+        _ => t1 == t2,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::turtle;
+    use crate::triple::stream::TripleSource;
+
+    fn parse(txt: &str) -> TripleSet {
+        let mut g = TripleSet::new();
+        turtle::parse_str(txt).in_graph(&mut g).unwrap();
+        g
+    }
+
+    #[test]
+    fn isomorphic() {
+        let g1 = parse("_:a <tag:p> _:b. _:b <tag:p> _:a. _:a <tag:q> <tag:x>.");
+        let g2 = parse("_:y <tag:p> _:x. _:x <tag:p> _:y. _:y <tag:q> <tag:x>.");
+        assert!(isomorphic_graphs(&g1, &g2));
+    }
+
+    #[test]
+    fn not_isomorphic() {
+        let g1 = parse("_:a <tag:p> _:b. _:b <tag:p> _:a.");
+        let g2 = parse("_:a <tag:p> _:a. _:b <tag:p> _:b.");
+        assert!(!isomorphic_graphs(&g1, &g2));
+        let g3 = parse("_:a <tag:p> _:b. _:b <tag:p> <tag:x>.");
+        assert!(!isomorphic_graphs(&g1, &g3));
+    }
+}
@@ -142,6 +142,7 @@ macro_rules! test_graph_impl {
             use $crate::graph::test::*;
             use $crate::graph::*;
             use $crate::ns::*;
+            use $crate::triple::{Triple, TripleOrder};
 
             #[allow(unused_imports)]
             use super::*;
@@ -212,6 +213,25 @@ macro_rules! test_graph_impl {
                 Ok(())
             }
 
+            #[test]
+            fn test_insert_canonical() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                let plus_five = StaticTerm::new_literal_dt("+5", xsd::integer).unwrap();
+                let five = StaticTerm::new_literal_dt("5", xsd::integer).unwrap();
+                assert!(g.insert_canonical(&C1, &P1, &plus_five)?);
+                assert!(Graph::contains(&g, &C1, &P1, &five).unwrap());
+                assert!(!Graph::contains(&g, &C1, &P1, &plus_five).unwrap());
+                if $is_set {
+                    assert!(!g.insert_canonical(&C1, &P1, &five)?);
+                    assert_eq!(g.triples().count(), 1);
+                }
+
+                // not canonicalized by default
+                MutableGraph::insert(&mut g, &C1, &P1, &plus_five)?;
+                assert!(Graph::contains(&g, &C1, &P1, &plus_five).unwrap());
+                Ok(())
+            }
+
             #[test]
             fn test_x_all_mutations() {
                 let mut g = $mutable_graph_factory();
@@ -250,8 +270,448 @@ macro_rules! test_graph_impl {
                 Ok(())
             }
 
+            #[test]
+            fn test_retain_matching_counted() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+
+                let removed = g.retain_matching_counted(&ANY, &rdf::type_, &ANY)?;
+                assert_eq!(removed, 9);
+                assert_eq!(g.triples().count(), 9);
+                assert_eq!(g.retain_matching_counted(&ANY, &rdf::type_, &ANY)?, 0);
+                assert_eq!(g.triples().count(), 9);
+                Ok(())
+            }
+
+            #[test]
+            fn test_clear() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+
+                MutableGraph::clear(&mut g)?;
+                assert_eq!(g.triples().count(), 0);
+                assert_eq!(g.triples_with_s(&*C1).count(), 0);
+                assert!(!Graph::contains(&g, &*C1, &rdf::type_, &rdfs::Class).unwrap());
+
+                MutableGraph::insert(&mut g, &*C1, &rdf::type_, &rdfs::Class)?;
+                assert_eq!(g.triples().count(), 1);
+                assert_eq!(g.triples_with_p(&rdf::type_).count(), 1);
+                assert!(Graph::contains(&g, &*C1, &rdf::type_, &rdfs::Class).unwrap());
+                MutableGraph::clear(&mut g)?;
+                assert_eq!(g.triples().count(), 0);
+                Ok(())
+            }
+
+            #[test]
+            fn test_redact_literals() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                let phone = StaticTerm::new_iri_suffixed(NS, "phone").unwrap();
+                let name = StaticTerm::new_iri_suffixed(NS, "name").unwrap();
+                let tel = StaticTerm::new_literal_dt("+33 1 23 45 67 89", xsd::string).unwrap();
+                let addr = StaticTerm::new_literal_lang("12 rue de Paris", "fr").unwrap();
+                let alice = StaticTerm::from("Alice");
+                MutableGraph::insert(&mut g, &I1A, &phone, &tel)?;
+                MutableGraph::insert(&mut g, &I1A, &rdfs::comment, &addr)?;
+                MutableGraph::insert(&mut g, &I1A, &name, &alice)?;
+                MutableGraph::insert(&mut g, &I1A, &rdf::type_, &C1)?;
+
+                let redacted =
+                    g.redact_literals(|txt| txt.chars().any(|c| c.is_ascii_digit()), "***")?;
+                assert_eq!(redacted, 2);
+                assert_eq!(g.triples().count(), 4);
+                let tel = StaticTerm::new_literal_dt("***", xsd::string).unwrap();
+                let addr = StaticTerm::new_literal_lang("***", "fr").unwrap();
+                assert!(Graph::contains(&g, &I1A, &phone, &tel).unwrap());
+                assert!(Graph::contains(&g, &I1A, &rdfs::comment, &addr).unwrap());
+                assert!(Graph::contains(&g, &I1A, &name, &alice).unwrap());
+                assert!(Graph::contains(&g, &I1A, &rdf::type_, &C1).unwrap());
+
+                // two phone numbers are redacted into the same triple,
+                // which is not duplicated, even in graphs that are not sets
+                let tel1 = StaticTerm::new_literal_dt("+33 1 11 11 11 11", xsd::string).unwrap();
+                let tel2 = StaticTerm::new_literal_dt("+33 2 22 22 22 22", xsd::string).unwrap();
+                MutableGraph::insert(&mut g, &I1B, &phone, &tel1)?;
+                MutableGraph::insert(&mut g, &I1B, &phone, &tel2)?;
+                let redacted = g.redact_literals(|txt| txt.starts_with("+33"), "***")?;
+                assert_eq!(redacted, 2);
+                assert_eq!(g.triples().count(), 5);
+                assert_eq!(g.triples_with_sp(&I1B, &phone).count(), 1);
+                assert!(Graph::contains(&g, &I1B, &phone, &tel).unwrap());
+                Ok(())
+            }
+
+            #[test]
+            fn test_retype_literals() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                let age = StaticTerm::new_iri_suffixed(NS, "age").unwrap();
+                let int42 = StaticTerm::new_literal_dt("42", xsd::int).unwrap();
+                let int43 = StaticTerm::new_literal_dt("43", xsd::int).unwrap();
+                let integer42 = StaticTerm::new_literal_dt("42", xsd::integer).unwrap();
+                let integer43 = StaticTerm::new_literal_dt("43", xsd::integer).unwrap();
+                MutableGraph::insert(&mut g, &I1A, &age, &int42)?;
+                MutableGraph::insert(&mut g, &I1A, &age, &integer42)?;
+                MutableGraph::insert(&mut g, &I1B, &age, &int43)?;
+                MutableGraph::insert(&mut g, &I1B, &rdf::type_, &C1)?;
+
+                let retyped = g.retype_literals(&xsd::iri::int, &xsd::iri::integer)?;
+                assert_eq!(retyped, 2);
+                assert!(Graph::contains(&g, &I1A, &age, &integer42).unwrap());
+                assert!(!Graph::contains(&g, &I1A, &age, &int42).unwrap());
+                assert!(Graph::contains(&g, &I1B, &age, &integer43).unwrap());
+                assert!(!Graph::contains(&g, &I1B, &age, &int43).unwrap());
+                assert!(Graph::contains(&g, &I1B, &rdf::type_, &C1).unwrap());
+                // I1A age 42 is not duplicated, even in graphs that are not sets
+                assert_eq!(g.triples().count(), 3);
+                Ok(())
+            }
+
+            #[test]
+            fn test_rewrite_iris() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+
+                let rewritten = g.rewrite_iris(|iri| {
+                    iri.strip_prefix(NS)
+                        .map(|suffix| format!("http://example.com/{}", suffix))
+                })?;
+                assert_eq!(rewritten, 18);
+                assert_eq!(g.triples().count(), 18);
+                let c1 = StaticTerm::new_iri("http://example.com/C1").unwrap();
+                let p1 = StaticTerm::new_iri("http://example.com/p1").unwrap();
+                let i1a = StaticTerm::new_iri("http://example.com/I1A").unwrap();
+                let i2a = StaticTerm::new_iri("http://example.com/I2A").unwrap();
+                assert!(Graph::contains(&g, &c1, &rdf::type_, &rdfs::Class).unwrap());
+                assert!(Graph::contains(&g, &i1a, &p1, &i2a).unwrap());
+                assert!(!Graph::contains(&g, &*C1, &rdf::type_, &rdfs::Class).unwrap());
+                assert_eq!(g.triples_with_s(&*C1).count(), 0);
+
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                let i1a = I1A.value().to_string();
+                let i1b = I1B.value().to_string();
+                let rewritten =
+                    g.rewrite_iris(|iri| if iri == i1b { Some(i1a.clone()) } else { None })?;
+                assert_eq!(rewritten, 2);
+                assert!(Graph::contains(&g, &*I1A, &P1, &*I2B).unwrap());
+                // (I1A type C1) and (I1B type C1) now coincide,
+                // and are not duplicated, even in graphs that are not sets
+                assert_eq!(g.triples().count(), 17);
+                Ok(())
+            }
+
             // Test Graph
 
+            #[test]
+            fn test_partition_by() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+
+                let parts = g.partition_by(|t| BoxTerm::from(t.p())).unwrap();
+                assert_eq!(parts.len(), 6);
+                assert_eq!(
+                    parts.values().map(|p| p.triples().count()).sum::<usize>(),
+                    18
+                );
+                let p1 = &parts[&BoxTerm::from(&*P1)];
+                assert_eq!(p1.triples().count(), 2);
+                assert!(p1.contains(&I1A, &P1, &I2A).unwrap());
+                assert!(p1.contains(&I1B, &P1, &I2B).unwrap());
+                assert_eq!(parts[&BoxTerm::from(&rdf::type_)].triples().count(), 9);
+                Ok(())
+            }
+
+            #[test]
+            fn test_map() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+
+                let mapped = g
+                    .map(|[s, p, o]| {
+                        if p == &rdfs::subClassOf {
+                            None
+                        } else if p == &rdfs::domain {
+                            Some([s.into(), BoxTerm::from(&rdfs::range), o.into()])
+                        } else {
+                            Some([s.into(), p.into(), o.into()])
+                        }
+                    })
+                    .unwrap();
+                // 2 triples dropped, and one rewritten triple was already present
+                assert_eq!(mapped.triples().count(), 15);
+                assert_eq!(mapped.triples_with_p(&rdfs::subClassOf).count(), 0);
+                assert_eq!(mapped.triples_with_p(&rdfs::domain).count(), 0);
+                assert_eq!(mapped.triples_with_p(&rdfs::range).count(), 3);
+                assert!(mapped.contains(&P1, &rdfs::range, &C1).unwrap());
+                assert_eq!(g.triples().count(), 18);
+                Ok(())
+            }
+
+            #[test]
+            fn test_sample() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate_nodes_types(&mut g)?;
+
+                let sample = g.sample(2).unwrap();
+                assert_eq!(sample.len(), 2);
+                for [s, p, o] in sample.iter() {
+                    assert!(Graph::contains(&g, s, p, o).unwrap());
+                }
+                assert_eq!(g.sample(10).unwrap().len(), 5);
+                assert_eq!(g.sample(0).unwrap().len(), 0);
+                Ok(())
+            }
+
+            #[test]
+            fn test_bnode_index() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate_nodes_types(&mut g)?;
+
+                let index = g.bnode_index().unwrap();
+                assert_eq!(index.len(), 2);
+                assert_eq!(index[&BoxTerm::new_bnode("b1").unwrap()], 0);
+                assert_eq!(index[&BoxTerm::new_bnode("b2").unwrap()], 1);
+                assert_eq!(g.bnode_index().unwrap(), index);
+
+                let mut h = $mutable_graph_factory();
+                populate_nodes_types(&mut h)?;
+                assert_eq!(h.bnode_index().unwrap(), index);
+                Ok(())
+            }
+
+            #[test]
+            fn test_has_cycle() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                assert!(!g.has_bnode_cycle().unwrap());
+                assert!(!g.has_cycle(true).unwrap());
+
+                MutableGraph::insert(&mut g, &I2B, &P2, &I2A)?;
+                assert!(!g.has_bnode_cycle().unwrap());
+                assert!(g.has_cycle(true).unwrap());
+
+                let mut h = $mutable_graph_factory();
+                populate_nodes_types(&mut h)?;
+                assert!(h.has_bnode_cycle().unwrap());
+                assert!(h.has_cycle(true).unwrap());
+                Ok(())
+            }
+
+            #[test]
+            fn test_prune_orphan_bnodes() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                let b1 = StaticTerm::new_bnode("b1").unwrap();
+                let b2 = StaticTerm::new_bnode("b2").unwrap();
+                let b3 = StaticTerm::new_bnode("b3").unwrap();
+                let b4 = StaticTerm::new_bnode("b4").unwrap();
+                let lit = StaticTerm::from("lit");
+                // I1A -> b1 -> b2 (reachable through two steps)
+                MutableGraph::insert(&mut g, &I1A, &P1, &b1)?;
+                MutableGraph::insert(&mut g, &b1, &P1, &b2)?;
+                MutableGraph::insert(&mut g, &b2, &P2, &lit)?;
+                // b3 -> b4 -> b3 (orphaned subtree, including a cycle)
+                MutableGraph::insert(&mut g, &b3, &P1, &b4)?;
+                MutableGraph::insert(&mut g, &b3, &P2, &I1A)?;
+                MutableGraph::insert(&mut g, &b4, &P1, &b3)?;
+                MutableGraph::insert(&mut g, &b4, &P2, &lit)?;
+
+                assert_eq!(g.prune_orphan_bnodes()?, 4);
+                assert_eq!(g.triples().count(), 21);
+                assert!(Graph::contains(&g, &b1, &P1, &b2).unwrap());
+                assert!(Graph::contains(&g, &b2, &P2, &lit).unwrap());
+                assert_eq!(g.triples_with_s(&b3).count(), 0);
+                assert_eq!(g.triples_with_s(&b4).count(), 0);
+
+                assert_eq!(g.prune_orphan_bnodes()?, 0);
+                Ok(())
+            }
+
+            #[test]
+            fn test_triples_sorted_by() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                MutableGraph::insert(&mut g, &I2A, &P2, &I1A)?;
+                MutableGraph::insert(&mut g, &I1B, &rdf::type_, &C1)?;
+                MutableGraph::insert(&mut g, &I1A, &P2, &I2B)?;
+                MutableGraph::insert(&mut g, &I1A, &P1, &I2A)?;
+                MutableGraph::insert(&mut g, &I1A, &rdf::type_, &C1)?;
+
+                let sorted = g.triples_sorted_by(TripleOrder::POS).unwrap();
+                let b = |t: &StaticTerm| BoxTerm::from(t);
+                let expected = vec![
+                    [b(&I1A), b(&P1), b(&I2A)],
+                    [b(&I2A), b(&P2), b(&I1A)],
+                    [b(&I1A), b(&P2), b(&I2B)],
+                    [b(&I1A), b(&rdf::type_), b(&C1)],
+                    [b(&I1B), b(&rdf::type_), b(&C1)],
+                ];
+                assert_eq!(sorted, expected);
+                Ok(())
+            }
+
+            #[test]
+            fn test_predicate_histogram() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                assert!(g.predicate_histogram().unwrap().is_empty());
+                populate(&mut g)?;
+                let histogram = g.predicate_histogram().unwrap();
+                let expected = vec![
+                    (BoxTerm::from(&rdf::type_), 9),
+                    (BoxTerm::from(&*P1), 2),
+                    (BoxTerm::from(&rdfs::domain), 2),
+                    (BoxTerm::from(&rdfs::range), 2),
+                    (BoxTerm::from(&rdfs::subClassOf), 2),
+                    (BoxTerm::from(&*P2), 1),
+                ];
+                assert_eq!(histogram, expected);
+                Ok(())
+            }
+
+            #[test]
+            fn test_triples_in_namespace() -> MGResult<$mutable_graph_impl, ()> {
+                use $crate::triple::NamespacePositions;
+
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                let foaf = "http://xmlns.com/foaf/0.1/";
+                let name = StaticTerm::new_iri_suffixed(foaf, "name").unwrap();
+                let person = StaticTerm::new_iri_suffixed(foaf, "Person").unwrap();
+                let alice = StaticTerm::from("Alice");
+                let typed = StaticTerm::new_literal_dt("x", person).unwrap();
+                MutableGraph::insert(&mut g, &I1A, &name, &alice)?;
+                MutableGraph::insert(&mut g, &I1A, &rdf::type_, &person)?;
+                MutableGraph::insert(&mut g, &I1B, &P1, &typed)?;
+
+                let count = |positions| g.triples_in_namespace(foaf, positions).count();
+                let default = NamespacePositions::default();
+                assert_eq!(count(default), 2);
+                let predicate_only = NamespacePositions {
+                    subject: false,
+                    object: false,
+                    ..default
+                };
+                assert_eq!(count(predicate_only), 1);
+                let with_datatype = NamespacePositions {
+                    datatype: true,
+                    ..default
+                };
+                assert_eq!(count(with_datatype), 3);
+                assert!(g
+                    .triples_in_namespace(foaf, default)
+                    .all(|t| t.unwrap().s() == &*I1A));
+                Ok(())
+            }
+
+            #[test]
+            fn test_build_text_index() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                let alice_smith = StaticTerm::from("Alice Smith");
+                let alice = StaticTerm::from("ALICE");
+                MutableGraph::insert(&mut g, &I1A, &rdfs::label, &alice_smith)?;
+                MutableGraph::insert(&mut g, &I1B, &rdfs::label, &alice)?;
+                MutableGraph::insert(&mut g, &I2A, &rdfs::comment, &alice)?;
+
+                let index = g.build_text_index(&[rdfs::label]).unwrap();
+                let i1a = BoxTerm::from(&*I1A);
+                let i1b = BoxTerm::from(&*I1B);
+                assert_eq!(index.search("alice"), vec![i1a.clone(), i1b]);
+                assert_eq!(index.search("Smith, alice"), vec![i1a]);
+                assert!(index.search("bob").is_empty());
+                assert!(index.search("").is_empty());
+                Ok(())
+            }
+
+            #[test]
+            fn test_describe() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                let b1 = StaticTerm::new_bnode("b1").unwrap();
+                let b2 = StaticTerm::new_bnode("b2").unwrap();
+                let b3 = StaticTerm::new_bnode("b3").unwrap();
+                let lit = StaticTerm::from("lit");
+                MutableGraph::insert(&mut g, &I1A, &P1, &b1)?;
+                MutableGraph::insert(&mut g, &b1, &P2, &b2)?;
+                MutableGraph::insert(&mut g, &b2, &P1, &b1)?;
+                MutableGraph::insert(&mut g, &b2, &P2, &lit)?;
+                MutableGraph::insert(&mut g, &b3, &P1, &I1A)?;
+
+                let d = g.describe(&*I1A).unwrap();
+                assert_eq!(d.triples().count(), 6);
+                assert!(Graph::contains(&d, &*I1A, &rdf::type_, &*C1).unwrap());
+                assert!(Graph::contains(&d, &*I1A, &*P1, &*I2A).unwrap());
+                assert!(Graph::contains(&d, &*I1A, &*P1, &b1).unwrap());
+                assert!(Graph::contains(&d, &b1, &*P2, &b2).unwrap());
+                assert!(Graph::contains(&d, &b2, &*P1, &b1).unwrap());
+                assert!(Graph::contains(&d, &b2, &*P2, &lit).unwrap());
+
+                // IRI objects are not described
+                let d = g.describe(&b3).unwrap();
+                assert_eq!(d.triples().count(), 1);
+                Ok(())
+            }
+
+            #[test]
+            fn test_describe_symmetric() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+                let b1 = StaticTerm::new_bnode("b1").unwrap();
+                let lit = StaticTerm::from("lit");
+                MutableGraph::insert(&mut g, &b1, &P1, &I2A)?;
+                MutableGraph::insert(&mut g, &b1, &P2, &lit)?;
+                MutableGraph::insert(&mut g, &I2A, &P1, &I2A)?;
+
+                let d = g.describe_symmetric(&*I2A).unwrap();
+                assert_eq!(d.triples().count(), 6);
+                // outbound
+                assert!(Graph::contains(&d, &*I2A, &rdf::type_, &*C2).unwrap());
+                assert!(Graph::contains(&d, &*I2A, &*P2, &*I2B).unwrap());
+                // inbound
+                assert!(Graph::contains(&d, &*I1A, &*P1, &*I2A).unwrap());
+                assert!(Graph::contains(&d, &b1, &*P1, &*I2A).unwrap());
+                // both
+                assert!(Graph::contains(&d, &*I2A, &*P1, &*I2A).unwrap());
+                // through blank node
+                assert!(Graph::contains(&d, &b1, &*P2, &lit).unwrap());
+
+                assert_eq!(g.describe(&*I2A).unwrap().triples().count(), 3);
+                Ok(())
+            }
+
+            #[test]
+            fn test_estimated_heap_size() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                assert_eq!(g.estimated_heap_size().unwrap(), 0);
+                populate(&mut g)?;
+                let naive: usize = g
+                    .triples()
+                    .map(|t| {
+                        let t = t.unwrap();
+                        t.s().heap_size() + t.p().heap_size() + t.o().heap_size()
+                    })
+                    .sum();
+                let estimate = g.estimated_heap_size().unwrap();
+                assert!(estimate > 0);
+                assert!(estimate < naive);
+                Ok(())
+            }
+
+            #[test]
+            fn test_namespaces() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                populate(&mut g)?;
+
+                let namespaces = g.namespaces().unwrap();
+                assert_eq!(
+                    namespaces,
+                    vec![
+                        (NS.to_string(), 33),
+                        (rdf::PREFIX.to_string(), 11),
+                        (rdfs::PREFIX.to_string(), 10),
+                    ]
+                );
+                Ok(())
+            }
+
             #[test]
             fn test_triples() -> MGResult<$mutable_graph_impl, ()> {
                 let mut g = $mutable_graph_factory();
@@ -436,6 +896,18 @@ macro_rules! test_graph_impl {
                 Ok(())
             }
 
+            #[test]
+            fn test_len() -> MGResult<$mutable_graph_impl, ()> {
+                let mut g = $mutable_graph_factory();
+                assert_eq!(Graph::len(&g).unwrap(), 0);
+                assert!(Graph::is_empty(&g).unwrap());
+
+                populate(&mut g)?;
+                assert_eq!(Graph::len(&g).unwrap(), g.triples().count());
+                assert!(!Graph::is_empty(&g).unwrap());
+                Ok(())
+            }
+
             #[test]
             fn test_subjects() -> MGResult<$mutable_graph_impl, ()> {
                 let mut g = $mutable_graph_factory();
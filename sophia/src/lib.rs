@@ -64,16 +64,17 @@
 
 pub mod dataset;
 pub mod graph;
+pub mod loader;
+pub mod ns;
 pub mod parser;
 pub mod quad;
 pub mod query;
 pub mod serializer;
+pub mod statement;
+#[cfg(feature = "test-util")]
+pub mod test_util;
 pub mod triple;
 
-/// See [`sophia_term::ns`](https://docs.rs/sophia_term/latest/sophia_term/ns/index.html)
-pub mod ns {
-    pub use sophia_term::ns::*;
-}
 /// See [`sophia_term`](https://docs.rs/sophia_term/latest/sophia_term/)
 pub mod term {
     pub use sophia_term::*;
@@ -0,0 +1,5 @@
+//! Loaders, building RDF graphs from non-RDF data formats,
+//! or from several RDF documents.
+
+pub mod csv;
+pub mod imports;
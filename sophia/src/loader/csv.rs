@@ -0,0 +1,352 @@
+//! Loader for [CSV] files, mapping each row to a subject of the resulting graph.
+//!
+//! The mapping is described by a [`CsvMapping`], which specifies
+//! * a template for the subject IRI of each row,
+//!   where `{column}` is replaced by the value of that column;
+//! * for each mapped column, a predicate and a datatype
+//!   (`xsd:string` by default).
+//!
+//! Empty cells produce no triple,
+//! and rows where a column used in the subject template is empty are skipped altogether.
+//! Columns of the file which are not mapped are ignored.
+//!
+//! The first line of the file must contain the names of the columns.
+//! Fields are separated by `,`, and may be enclosed in double quotes
+//! (in which case they may contain `,`, line breaks, and `""` for an escaped `"`).
+//!
+//! # Example
+//! ```
+//! use sophia::graph::Graph;
+//! use sophia::loader::csv::{load_csv, CsvMapping};
+//! use sophia::ns::xsd;
+//! use sophia::term::BoxTerm;
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let data = "id,age\nalice,42\nbob,\n";
+//! let mut mapping = CsvMapping::new("http://example.org/person/{id}");
+//! mapping.add_typed_column("age", &BoxTerm::new_iri("http://example.org/age")?, &xsd::integer)?;
+//! let g = load_csv(data.as_bytes(), &mapping)?;
+//! assert_eq!(g.triples().count(), 1);
+//! # Ok(()) }
+//! ```
+//!
+//! [CSV]: https://tools.ietf.org/html/rfc4180
+//! [`CsvMapping`]: struct.CsvMapping.html
+
+use std::io::{self, Read};
+use std::mem;
+
+use crate::graph::inmem::FastGraph;
+use crate::graph::MutableGraph;
+use sophia_term::literal::LiteralBuilder;
+use sophia_term::{BoxTerm, Result as TermResult, Term, TermData, TermError};
+
+/// Describes how the rows of a CSV file are mapped to triples.
+#[derive(Clone, Debug)]
+pub struct CsvMapping {
+    subject_template: String,
+    columns: Vec<ColumnMapping>,
+}
+
+#[derive(Clone, Debug)]
+struct ColumnMapping {
+    name: String,
+    predicate: BoxTerm,
+    literals: LiteralBuilder,
+}
+
+impl CsvMapping {
+    /// Build a new mapping, where the IRI of the subject of each row
+    /// is built from `subject_template`.
+    ///
+    /// In the template, `{column}` is replaced by the value of the cell of that column.
+    /// Values are inserted as is, and must therefore be valid in an IRI.
+    pub fn new(subject_template: &str) -> Self {
+        CsvMapping {
+            subject_template: subject_template.to_string(),
+            columns: vec![],
+        }
+    }
+
+    /// Map the given column to `predicate`, with `xsd:string` literals.
+    pub fn add_column<T>(&mut self, column: &str, predicate: &Term<T>) -> &mut Self
+    where
+        T: TermData,
+    {
+        self.columns.push(ColumnMapping {
+            name: column.to_string(),
+            predicate: predicate.into(),
+            literals: LiteralBuilder::new(),
+        });
+        self
+    }
+
+    /// Map the given column to `predicate`, with literals of the given datatype.
+    ///
+    /// # Error
+    ///
+    /// An error is raised if `datatype` is not an IRI.
+    pub fn add_typed_column<T, U>(
+        &mut self,
+        column: &str,
+        predicate: &Term<T>,
+        datatype: &Term<U>,
+    ) -> TermResult<&mut Self>
+    where
+        T: TermData,
+        U: TermData,
+    {
+        self.columns.push(ColumnMapping {
+            name: column.to_string(),
+            predicate: predicate.into(),
+            literals: LiteralBuilder::new().default_datatype(datatype)?,
+        });
+        Ok(self)
+    }
+}
+
+/// The error type of [`load_csv`](fn.load_csv.html).
+#[derive(Debug, thiserror::Error)]
+pub enum CsvError {
+    /// An error occurred while reading.
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    /// The file has no header line.
+    #[error("missing header line")]
+    MissingHeader,
+    /// A double-quoted field is not closed.
+    #[error("unterminated quoted field starting at line {0}")]
+    UnterminatedQuote(usize),
+    /// A row does not have the same number of fields as the header.
+    #[error("line {line}: expected {expected} fields, found {found}")]
+    RowLength {
+        /// The line where the row starts
+        line: usize,
+        /// The number of columns in the header
+        expected: usize,
+        /// The number of fields in the row
+        found: usize,
+    },
+    /// The mapping refers to a column that does not exist in the file.
+    #[error("unknown column {0:?}")]
+    UnknownColumn(String),
+    /// The subject template does not produce a valid IRI.
+    #[error("line {line}: invalid subject IRI: {source}")]
+    InvalidSubject {
+        /// The line where the row starts
+        line: usize,
+        /// The source of this error
+        source: TermError,
+    },
+}
+
+/// Load the CSV data from `r` into a new graph, according to `mapping`.
+///
+/// See the [module documentation](index.html) for details.
+pub fn load_csv<R: Read>(mut r: R, mapping: &CsvMapping) -> Result<FastGraph, CsvError> {
+    let mut txt = String::new();
+    r.read_to_string(&mut txt)?;
+    let mut records = parse_records(&txt)?.into_iter();
+    let (_, header) = records.next().ok_or(CsvError::MissingHeader)?;
+    let column_index = |name: &str| {
+        header
+            .iter()
+            .position(|h| h == name)
+            .ok_or_else(|| CsvError::UnknownColumn(name.to_string()))
+    };
+    let template = parse_template(&mapping.subject_template, column_index)?;
+    let columns = mapping
+        .columns
+        .iter()
+        .map(|c| column_index(&c.name).map(|i| (i, c)))
+        .collect::<Result<Vec<_>, CsvError>>()?;
+
+    let mut g = FastGraph::new();
+    for (line, row) in records {
+        if row.len() != header.len() {
+            return Err(CsvError::RowLength {
+                line,
+                expected: header.len(),
+                found: row.len(),
+            });
+        }
+        if template.iter().any(|part| match part {
+            TemplatePart::Column(i) => row[*i].is_empty(),
+            TemplatePart::Text(_) => false,
+        }) {
+            continue;
+        }
+        let mut iri = String::new();
+        for part in &template {
+            match part {
+                TemplatePart::Text(txt) => iri.push_str(txt),
+                TemplatePart::Column(i) => iri.push_str(&row[*i]),
+            }
+        }
+        let s =
+            BoxTerm::new_iri(iri).map_err(|source| CsvError::InvalidSubject { line, source })?;
+        for (i, column) in &columns {
+            let value = &row[*i];
+            if value.is_empty() {
+                continue;
+            }
+            let o: BoxTerm = column.literals.term(value);
+            // FastGraph::MutationError is Infallible
+            g.insert(&s, &column.predicate, &o).unwrap();
+        }
+    }
+    Ok(g)
+}
+
+/// A part of a subject template.
+enum TemplatePart<'a> {
+    Text(&'a str),
+    Column(usize),
+}
+
+/// Split `template` into text and `{column}` placeholders,
+/// using `column_index` to resolve column names.
+fn parse_template<F>(template: &str, column_index: F) -> Result<Vec<TemplatePart<'_>>, CsvError>
+where
+    F: Fn(&str) -> Result<usize, CsvError>,
+{
+    let mut parts = vec![];
+    let mut rest = template;
+    while let Some(start) = rest.find('{') {
+        let end = match rest[start..].find('}') {
+            Some(end) => start + end,
+            None => break,
+        };
+        parts.push(TemplatePart::Text(&rest[..start]));
+        parts.push(TemplatePart::Column(column_index(&rest[start + 1..end])?));
+        rest = &rest[end + 1..];
+    }
+    parts.push(TemplatePart::Text(rest));
+    Ok(parts)
+}
+
+/// Split `txt` into records, each with the line where it starts.
+///
+/// Empty lines are ignored.
+fn parse_records(txt: &str) -> Result<Vec<(usize, Vec<String>)>, CsvError> {
+    let mut records = vec![];
+    let mut record = vec![];
+    let mut field = String::new();
+    let mut line = 1;
+    let mut record_line = 1;
+    let mut quoted = false;
+    let mut chars = txt.chars().peekable();
+    while let Some(c) = chars.next() {
+        if quoted {
+            match c {
+                '"' if chars.peek() == Some(&'"') => {
+                    chars.next();
+                    field.push('"');
+                }
+                '"' => quoted = false,
+                c => {
+                    if c == '\n' {
+                        line += 1;
+                    }
+                    field.push(c);
+                }
+            }
+        } else {
+            match c {
+                '"' if field.is_empty() => quoted = true,
+                ',' => record.push(mem::take(&mut field)),
+                '\r' if chars.peek() == Some(&'\n') => {}
+                '\n' => {
+                    record.push(mem::take(&mut field));
+                    if record.len() > 1 || !record[0].is_empty() {
+                        records.push((record_line, mem::take(&mut record)));
+                    } else {
+                        record.clear();
+                    }
+                    line += 1;
+                    record_line = line;
+                }
+                c => field.push(c),
+            }
+        }
+    }
+    if quoted {
+        return Err(CsvError::UnterminatedQuote(record_line));
+    }
+    if !field.is_empty() || !record.is_empty() {
+        record.push(field);
+        records.push((record_line, record));
+    }
+    Ok(records)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::xsd;
+
+    #[test]
+    fn two_rows_two_columns() -> Result<(), Box<dyn std::error::Error>> {
+        let data = "name,age\r\nalice,42\r\nbob,\r\n";
+        let name = BoxTerm::new_iri("http://example.org/name")?;
+        let age = BoxTerm::new_iri("http://example.org/age")?;
+        let mut mapping = CsvMapping::new("http://example.org/person/{name}");
+        mapping
+            .add_column("name", &name)
+            .add_typed_column("age", &age, &xsd::integer)?;
+        let g = load_csv(data.as_bytes(), &mapping)?;
+
+        let alice = BoxTerm::new_iri("http://example.org/person/alice")?;
+        let bob = BoxTerm::new_iri("http://example.org/person/bob")?;
+        let expected: FastGraph = vec![
+            [
+                alice.clone(),
+                name.clone(),
+                BoxTerm::new_literal_dt("alice", &xsd::string)?,
+            ],
+            [alice, age, BoxTerm::new_literal_dt("42", &xsd::integer)?],
+            [bob, name, BoxTerm::new_literal_dt("bob", &xsd::string)?],
+        ]
+        .into_iter()
+        .collect();
+        assert_eq!(g, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn quoted_fields_and_empty_keys() -> Result<(), Box<dyn std::error::Error>> {
+        let data = "id,label\n1,\"a, \"\"quoted\"\"\nlabel\"\n,no id\n\n";
+        let label = BoxTerm::new_iri("http://example.org/label")?;
+        let mut mapping = CsvMapping::new("http://example.org/{id}#it");
+        mapping.add_column("label", &label);
+        let g = load_csv(data.as_bytes(), &mapping)?;
+
+        let expected: FastGraph = vec![[
+            BoxTerm::new_iri("http://example.org/1#it")?,
+            label,
+            BoxTerm::new_literal_dt("a, \"quoted\"\nlabel", &xsd::string)?,
+        ]]
+        .into_iter()
+        .collect();
+        assert_eq!(g, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() {
+        let p = BoxTerm::new_iri("http://example.org/p").unwrap();
+        let mut mapping = CsvMapping::new("http://example.org/{id}");
+        mapping.add_column("value", &p);
+
+        let res = load_csv("".as_bytes(), &mapping);
+        assert!(matches!(res, Err(CsvError::MissingHeader)));
+        let res = load_csv("id,val\n1,2\n".as_bytes(), &mapping);
+        assert!(matches!(res, Err(CsvError::UnknownColumn(c)) if c == "value"));
+        let res = load_csv("id,value\n1,2\n3\n".as_bytes(), &mapping);
+        assert!(matches!(res, Err(CsvError::RowLength { line: 3, .. })));
+        let res = load_csv("id,value\n1,\"2\n".as_bytes(), &mapping);
+        assert!(matches!(res, Err(CsvError::UnterminatedQuote(2))));
+        let res = load_csv("id,value\na b,2\n".as_bytes(), &mapping);
+        assert!(matches!(res, Err(CsvError::InvalidSubject { line: 2, .. })));
+    }
+}
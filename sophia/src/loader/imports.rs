@@ -0,0 +1,193 @@
+//! Loader assembling an ontology together with all the ontologies it imports,
+//! directly or indirectly, through `owl:imports`.
+//!
+//! Documents are parsed as [Turtle](../../parser/turtle/index.html),
+//! using their IRI as the base IRI,
+//! and retrieved through a user-provided *fetcher*,
+//! so that this loader makes no assumption on how (or whether) IRIs are dereferenced.
+//!
+//! Each document is fetched at most once,
+//! so that cyclic imports are supported.
+//! Blank nodes from distinct documents are kept distinct.
+//!
+//! # Example
+//! ```
+//! use sophia::graph::Graph;
+//! use sophia::loader::imports::load_with_imports;
+//! use sophia::term::iri::Iri;
+//!
+//! fn fetch(iri: &Iri<Box<str>>) -> std::io::Result<String> {
+//!     Ok(match &*iri.value() {
+//!         "http://example.org/onto1" => "<> <http://www.w3.org/2002/07/owl#imports> <onto2>.",
+//!         _ => "<> a <http://www.w3.org/2002/07/owl#Ontology>.",
+//!     }
+//!     .to_string())
+//! }
+//!
+//! # fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! let g = load_with_imports(&Iri::<&str>::new("http://example.org/onto1")?, fetch)?;
+//! assert_eq!(g.triples().count(), 2);
+//! # Ok(()) }
+//! ```
+
+use std::collections::HashSet;
+use std::convert::TryFrom;
+use std::error::Error;
+
+use rio_turtle::TurtleError;
+
+use crate::graph::inmem::FastGraph;
+use crate::graph::Graph;
+use crate::ns::owl;
+use crate::parser::turtle::TurtleParser;
+use crate::parser::{ScopedSource, TripleParser};
+use crate::triple::stream::{StreamError, TripleSource};
+use crate::triple::Triple;
+use sophia_term::iri::Iri;
+use sophia_term::TermData;
+
+/// The error type of [`load_with_imports`](fn.load_with_imports.html),
+/// where `E` is the error type of the fetcher.
+#[derive(Debug, thiserror::Error)]
+pub enum ImportError<E>
+where
+    E: Error + 'static,
+{
+    /// The fetcher failed to retrieve a document.
+    #[error("could not fetch <{iri}>: {source}")]
+    Fetch {
+        /// The IRI of the document
+        iri: String,
+        /// The error returned by the fetcher
+        source: E,
+    },
+    /// A document is not valid Turtle.
+    #[error("could not parse <{iri}>: {source}")]
+    Parse {
+        /// The IRI of the document
+        iri: String,
+        /// The error returned by the parser
+        source: TurtleError,
+    },
+}
+
+/// Load the ontology identified by `entry`,
+/// merged with all the ontologies it imports, directly or indirectly.
+///
+/// `fetcher` is called once for `entry` and once for each imported IRI,
+/// and must return the Turtle source of the corresponding document.
+pub fn load_with_imports<T, F, E>(
+    entry: &Iri<T>,
+    mut fetcher: F,
+) -> Result<FastGraph, ImportError<E>>
+where
+    T: TermData,
+    F: FnMut(&Iri<Box<str>>) -> Result<String, E>,
+    E: Error + 'static,
+{
+    let mut g = FastGraph::new();
+    let mut loaded = HashSet::new();
+    let mut to_load: Vec<Iri<Box<str>>> = vec![entry.into()];
+    while let Some(iri) = to_load.pop() {
+        if loaded.contains(&iri) {
+            continue;
+        }
+        let txt = fetcher(&iri).map_err(|source| ImportError::Fetch {
+            iri: iri.value().to_string(),
+            source,
+        })?;
+        let parser = TurtleParser {
+            base: Some(iri.value().to_string()),
+        };
+        // blank nodes of each document get their own scope
+        ScopedSource::new(parser.parse_str(&txt), loaded.len())
+            .in_graph(&mut g)
+            .map_err(|err| match err {
+                StreamError::SourceError(source) => ImportError::Parse {
+                    iri: iri.value().to_string(),
+                    source,
+                },
+                StreamError::SinkError(err) => match err {},
+            })?;
+        loaded.insert(iri);
+        for t in g.triples_with_p(&owl::imports) {
+            let t = t.unwrap(); // FastGraph::Error is Infallible
+            if let Ok(imported) = Iri::try_from(t.o()) {
+                if !loaded.contains(&imported) {
+                    to_load.push(imported);
+                }
+            }
+        }
+    }
+    Ok(g)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::rdf;
+    use sophia_term::BoxTerm;
+    use std::io;
+
+    const ONTO_A: &str = r#"
+        @prefix owl: <http://www.w3.org/2002/07/owl#>.
+        <> a owl:Ontology; owl:imports <b>.
+        <#C> a owl:Class.
+        _:x a owl:Class.
+    "#;
+    const ONTO_B: &str = r#"
+        @prefix owl: <http://www.w3.org/2002/07/owl#>.
+        <> a owl:Ontology; owl:imports <a>.
+        <#D> a owl:Class.
+        _:x a owl:Class.
+    "#;
+
+    fn fetch(iri: &Iri<Box<str>>) -> io::Result<String> {
+        match &*iri.value() {
+            "http://example.org/a" => Ok(ONTO_A.to_string()),
+            "http://example.org/b" => Ok(ONTO_B.to_string()),
+            _ => Err(io::Error::new(
+                io::ErrorKind::NotFound,
+                iri.value().to_string(),
+            )),
+        }
+    }
+
+    #[test]
+    fn cyclic_imports() -> Result<(), Box<dyn std::error::Error>> {
+        let mut fetched = vec![];
+        let g = load_with_imports(&Iri::<&str>::new("http://example.org/a")?, |iri| {
+            fetched.push(iri.value().to_string());
+            fetch(iri)
+        })?;
+        assert_eq!(
+            fetched,
+            vec!["http://example.org/a", "http://example.org/b"]
+        );
+        assert_eq!(g.triples().count(), 8);
+        for iri in &["http://example.org/a#C", "http://example.org/b#D"] {
+            let c = BoxTerm::new_iri(*iri)?;
+            assert!(g.contains(&c, &rdf::type_, &owl::Class)?);
+        }
+        // the blank nodes of each document are kept distinct
+        assert_eq!(g.bnodes()?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn errors() -> Result<(), Box<dyn std::error::Error>> {
+        let err = load_with_imports(&Iri::<&str>::new("http://example.org/c")?, fetch).unwrap_err();
+        assert!(matches!(err, ImportError::Fetch { ref iri, .. } if iri == "http://example.org/c"));
+
+        let err = load_with_imports(
+            &Iri::<&str>::new("http://example.org/a")?,
+            |iri| match &*iri.value() {
+                "http://example.org/b" => Ok("<> a".to_string()),
+                _ => fetch(iri),
+            },
+        )
+        .unwrap_err();
+        assert!(matches!(err, ImportError::Parse { ref iri, .. } if iri == "http://example.org/b"));
+        Ok(())
+    }
+}
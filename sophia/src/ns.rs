@@ -0,0 +1,61 @@
+//! Standard and custom namespaces.
+//!
+//! This module re-exports everything from
+//! [`sophia_term::ns`](https://docs.rs/sophia_term/latest/sophia_term/ns/index.html),
+//! and extends [`PrefixMap`](struct.PrefixMap.html) with
+//! [`PrefixMapExt`](trait.PrefixMapExt.html).
+
+pub use sophia_term::ns::*;
+
+use crate::graph::{GResult, Graph};
+
+/// Extension of [`PrefixMap`](struct.PrefixMap.html) with methods relying on graphs.
+pub trait PrefixMapExt: Sized {
+    /// Build a prefix map for all the namespaces used in `g`
+    /// (see [`Graph::namespaces`](../graph/trait.Graph.html#method.namespaces)),
+    /// so that it can be used to serialize `g` without manual configuration.
+    ///
+    /// Well-known namespaces get their conventional prefix,
+    /// and the other ones get `ns0`, `ns1`, ...
+    /// from the most used to the least used
+    /// (see [`PrefixMap::auto_from_namespaces`](struct.PrefixMap.html#method.auto_from_namespaces)).
+    fn auto_from_graph<G: Graph>(g: &G) -> GResult<G, Self>;
+}
+
+impl PrefixMapExt for PrefixMap {
+    fn auto_from_graph<G: Graph>(g: &G) -> GResult<G, Self> {
+        let namespaces = g.namespaces()?;
+        Ok(PrefixMap::auto_from_namespaces(
+            namespaces.iter().map(|(ns, _)| ns),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::inmem::FastGraph;
+    use crate::graph::MutableGraph;
+    use sophia_term::StaticTerm;
+
+    #[test]
+    fn auto_from_graph() {
+        let ex = Namespace::new("http://example.org/ns#").unwrap();
+        let mut g = FastGraph::new();
+        g.insert(&ex.get("s").unwrap(), &rdf::type_, &ex.get("C").unwrap())
+            .unwrap();
+        g.insert(
+            &ex.get("s").unwrap(),
+            &ex.get("p").unwrap(),
+            &StaticTerm::new_literal_dt("42", xsd::integer).unwrap(),
+        )
+        .unwrap();
+
+        let pm = PrefixMap::auto_from_graph(&g).unwrap();
+        let prefixes: Vec<_> = pm.iter().map(|(p, _)| p).collect();
+        assert_eq!(prefixes, vec!["ns0", "rdf", "xsd"]);
+        assert_eq!(pm.expand("rdf:type").unwrap(), rdf::iri::type_);
+        assert_eq!(pm.expand("xsd:integer").unwrap(), xsd::iri::integer);
+        assert_eq!(&pm.expand("ns0:C").unwrap(), "http://example.org/ns#C");
+    }
+}
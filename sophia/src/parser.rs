@@ -1,10 +1,26 @@
 //! API for parsing RDF syntaxes.
+//!
+//! **Important**: blank node identifiers are yielded as they appear in the parsed data,
+//! so the same identifier (e.g. `_:b`) in two documents parsed into the same graph
+//! denotes the same blank node.
+//! Parsers can be wrapped in a [`ScopedParser`](struct.ScopedParser.html)
+//! to give each parsed document its own blank node scope.
 
 use crate::quad::stream::QuadSource;
 use crate::triple::stream::TripleSource;
 
+mod _bnode_scope;
+pub use _bnode_scope::*;
+mod _line_source;
+pub use _line_source::*;
 mod _location;
 pub use _location::*;
+mod _metrics;
+pub use _metrics::*;
+
+/// The default maximum nesting depth of the parsers supporting such a limit
+/// (nested blank nodes and collections in Turtle, nested elements in RDF/XML).
+pub const DEFAULT_MAX_DEPTH: usize = 256;
 
 /// A parser takes some data of type `T`,
 /// and returns a [`TripleSource`].
@@ -92,10 +108,24 @@ macro_rules! def_mod_functions_for_bufread_parser {
         ) -> <$parser_type as $crate::parser::$parser_trait<&[u8]>>::Source {
             $parser_type::default().parse_str(txt)
         }
+
+        /// Convenience function for parsing a file with the default parser,
+        /// transparently decompressing it if its name ends with `.gz`
+        /// (requires the `gzip` feature).
+        #[cfg(feature = "gzip")]
+        pub fn parse_gz_path<P: AsRef<std::path::Path>>(
+            path: P,
+        ) -> std::io::Result<
+            <$parser_type as $crate::parser::$parser_trait<Box<dyn std::io::BufRead>>>::Source,
+        > {
+            $crate::parser::gzip::open(path).map(|bufread| $parser_type::default().parse(bufread))
+        }
     };
 }
 
 pub mod gtrig;
+#[cfg(feature = "gzip")]
+pub mod gzip;
 pub mod nq;
 pub mod nt;
 pub mod rio_common;
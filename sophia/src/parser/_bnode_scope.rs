@@ -0,0 +1,198 @@
+// this module is transparently re-exported by its parent `parser`
+
+use std::error::Error;
+use std::fmt::Write;
+use std::sync::atomic::{AtomicUsize, Ordering};
+
+use crate::parser::{QuadParser, TripleParser};
+use crate::quad::stream::QuadSource;
+use crate::quad::streaming_mode::StreamedQuad;
+use crate::quad::Quad;
+use crate::triple::stream::{StreamResult, TripleSource};
+use crate::triple::streaming_mode::StreamedTriple;
+use crate::triple::Triple;
+use sophia_term::{RefTerm, Term, TermData};
+
+/// A parser wrapper giving each invocation of the wrapped parser its own blank node scope.
+///
+/// Blank node identifiers are local to the document where they appear,
+/// but parsers of this crate yield them as they are written,
+/// so `_:b` in two separately parsed documents yields the same blank node.
+/// With this wrapper, the `n`-th invocation of `parse` (starting at 0)
+/// renames the blank node identified by `id` into `id_n`,
+/// so that blank nodes from different invocations are distinct.
+///
+/// The labels produced therefore only depend on the data
+/// and on the number of previous invocations of this particular wrapper.
+#[derive(Debug, Default)]
+pub struct ScopedParser<P> {
+    parser: P,
+    invocations: AtomicUsize,
+}
+
+impl<P> ScopedParser<P> {
+    /// Wrap `parser`.
+    pub fn new(parser: P) -> Self {
+        ScopedParser {
+            parser,
+            invocations: AtomicUsize::new(0),
+        }
+    }
+
+    fn next_scope(&self) -> usize {
+        self.invocations.fetch_add(1, Ordering::Relaxed)
+    }
+}
+
+impl<P, T> TripleParser<T> for ScopedParser<P>
+where
+    P: TripleParser<T>,
+{
+    type Source = ScopedSource<P::Source>;
+    fn parse(&self, data: T) -> Self::Source {
+        ScopedSource::new(self.parser.parse(data), self.next_scope())
+    }
+}
+
+impl<P, T> QuadParser<T> for ScopedParser<P>
+where
+    P: QuadParser<T>,
+{
+    type Source = ScopedSource<P::Source>;
+    fn parse(&self, data: T) -> Self::Source {
+        ScopedSource::new(self.parser.parse(data), self.next_scope())
+    }
+}
+
+/// The source returned by [`ScopedParser`](struct.ScopedParser.html).
+pub struct ScopedSource<S> {
+    source: S,
+    scope: usize,
+    /// Buffers for the scoped identifiers of the current triple (or quad)
+    buffers: [String; 4],
+}
+
+impl<S> ScopedSource<S> {
+    /// Wrap `source`, renaming each blank node identified by `id` into `id_{scope}`.
+    ///
+    /// This is useful when the parser changes from one invocation to another
+    /// (e.g. because of a different base IRI),
+    /// which prevents from using a single [`ScopedParser`](struct.ScopedParser.html).
+    pub fn new(source: S, scope: usize) -> Self {
+        ScopedSource {
+            source,
+            scope,
+            buffers: Default::default(),
+        }
+    }
+}
+
+impl<S: TripleSource> TripleSource for ScopedSource<S> {
+    type Error = S::Error;
+    type Triple = crate::triple::streaming_mode::ByRefTerms;
+
+    fn try_for_some_triple<F, E>(&mut self, f: &mut F) -> StreamResult<bool, S::Error, E>
+    where
+        F: FnMut(StreamedTriple<Self::Triple>) -> Result<(), E>,
+        E: Error,
+    {
+        let scope = self.scope;
+        let [bs, bp, bo, _] = &mut self.buffers;
+        self.source.try_for_some_triple(&mut |t| {
+            f(StreamedTriple::by_ref_terms(
+                scoped(t.s(), scope, bs),
+                scoped(t.p(), scope, bp),
+                scoped(t.o(), scope, bo),
+            ))
+        })
+    }
+}
+
+impl<S: QuadSource> QuadSource for ScopedSource<S> {
+    type Error = S::Error;
+    type Quad = crate::quad::streaming_mode::ByRefTerms;
+
+    fn try_for_some_quad<F, E>(&mut self, f: &mut F) -> StreamResult<bool, S::Error, E>
+    where
+        F: FnMut(StreamedQuad<Self::Quad>) -> Result<(), E>,
+        E: Error,
+    {
+        let scope = self.scope;
+        let [bs, bp, bo, bg] = &mut self.buffers;
+        self.source.try_for_some_quad(&mut |q| {
+            f(StreamedQuad::by_ref_terms(
+                scoped(q.s(), scope, bs),
+                scoped(q.p(), scope, bp),
+                scoped(q.o(), scope, bo),
+                q.g().map(|g| scoped(g, scope, bg)),
+            ))
+        })
+    }
+}
+
+/// Borrow `t` as a `RefTerm`,
+/// renaming it into `scope` (using `buffer` to store the new identifier) if it is a blank node.
+fn scoped<'a, TD: TermData>(t: &'a Term<TD>, scope: usize, buffer: &'a mut String) -> RefTerm<'a> {
+    match t {
+        Term::BNode(b) => {
+            buffer.clear();
+            write!(buffer, "{}_{}", b.as_ref(), scope).unwrap();
+            RefTerm::new_bnode_unchecked(buffer.as_str())
+        }
+        _ => t.into(),
+    }
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dataset::inmem::FastDataset;
+    use crate::dataset::Dataset;
+    use crate::graph::inmem::FastGraph;
+    use crate::graph::Graph;
+    use crate::parser::nq::NQuadsParser;
+    use crate::parser::turtle::TurtleParser;
+
+    const TURTLE: &str = r#"
+        @prefix : <http://example.org/ns/> .
+
+        _:b a :Person ; :knows [ a :Person ].
+    "#;
+
+    #[test]
+    fn scope_per_parse() -> Result<(), Box<dyn std::error::Error>> {
+        let mut g = FastGraph::new();
+        let p = ScopedParser::new(TurtleParser::default());
+        assert_eq!(p.parse_str(TURTLE).in_graph(&mut g)?, 3);
+        assert_eq!(p.parse_str(TURTLE).in_graph(&mut g)?, 3);
+        assert_eq!(g.triples().count(), 6);
+        assert_eq!(g.bnodes()?.len(), 4);
+
+        // without the wrapper, blank nodes are merged
+        let mut g = FastGraph::new();
+        let p = TurtleParser::default();
+        p.parse_str(TURTLE).in_graph(&mut g)?;
+        p.parse_str(TURTLE).in_graph(&mut g)?;
+        assert_eq!(g.bnodes()?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn deterministic_labels() -> Result<(), Box<dyn std::error::Error>> {
+        let nq = "_:s <tag:p> _:o _:g.\n";
+        let p = ScopedParser::new(NQuadsParser::default());
+        for n in 0..2 {
+            let mut d = FastDataset::new();
+            p.parse_str(nq).in_dataset(&mut d)?;
+            let q = d.quads().next().unwrap()?;
+            assert_eq!(q.s().value().to_string(), format!("s_{}", n));
+            assert_eq!(q.o().value().to_string(), format!("o_{}", n));
+            assert_eq!(q.g().unwrap().value().to_string(), format!("g_{}", n));
+        }
+        Ok(())
+    }
+}
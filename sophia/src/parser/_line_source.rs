@@ -0,0 +1,129 @@
+// this module is transparently re-exported by its parent `parser`
+
+use std::error::Error;
+use std::io::{self, BufRead};
+use std::marker::PhantomData;
+
+use rio_turtle::{NQuadsParser as RioNQParser, NTriplesParser as RioNTParser};
+
+use crate::parser::nq::NQuadsParser;
+use crate::parser::nt::NTriplesParser;
+use crate::parser::rio_common::StrictRioSource;
+use crate::quad::stream::QuadSource;
+use crate::quad::streaming_mode::StreamedQuad;
+use crate::triple::stream::{SinkError, SourceError, StreamResult, TripleSource};
+use crate::triple::streaming_mode::StreamedTriple;
+
+/// A source parsing a line-based syntax (N-Triples or N-Quads) one line at a time,
+/// reporting invalid lines to a callback instead of failing.
+///
+/// It is returned by `parse_with_error_handler` on
+/// [`NTriplesParser`](nt/struct.NTriplesParser.html#method.parse_with_error_handler) and
+/// [`NQuadsParser`](nq/struct.NQuadsParser.html#method.parse_with_error_handler);
+/// the type parameter `P` is the parser that produced it.
+///
+/// The callback receives the line number (starting at 1) and a description of the error.
+/// Invalid lines are then skipped.
+/// The only errors raised by this source are I/O errors from the underlying reader.
+pub struct LineSource<B, F, P> {
+    data: B,
+    on_error: F,
+    line_number: usize,
+    line: String,
+    _parser: PhantomData<P>,
+}
+
+impl<B, F, P> LineSource<B, F, P>
+where
+    B: BufRead,
+    F: FnMut(usize, &str),
+{
+    pub(crate) fn new(data: B, on_error: F) -> Self {
+        LineSource {
+            data,
+            on_error,
+            line_number: 0,
+            line: String::new(),
+            _parser: PhantomData,
+        }
+    }
+
+    /// Read the next line into `self.line`.
+    ///
+    /// Return `false` at the end of the data.
+    fn next_line(&mut self) -> io::Result<bool> {
+        self.line.clear();
+        self.line_number += 1;
+        Ok(self.data.read_line(&mut self.line)? > 0)
+    }
+}
+
+impl<B, F> TripleSource for LineSource<B, F, NTriplesParser>
+where
+    B: BufRead,
+    F: FnMut(usize, &str),
+{
+    type Error = io::Error;
+    type Triple = crate::triple::streaming_mode::ByRefTerms;
+
+    fn try_for_some_triple<G, E>(&mut self, f: &mut G) -> StreamResult<bool, io::Error, E>
+    where
+        G: FnMut(StreamedTriple<Self::Triple>) -> Result<(), E>,
+        E: Error,
+    {
+        if !self.next_line().map_err(SourceError)? {
+            return Ok(false);
+        }
+        let mut source = match RioNTParser::new(self.line.as_bytes()) {
+            Ok(parser) => StrictRioSource::Parser(parser),
+            Err(err) => StrictRioSource::Error(Some(err)),
+        };
+        loop {
+            match source.try_for_some_triple(f) {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(SourceError(err)) => {
+                    (self.on_error)(self.line_number, &err.to_string());
+                    break;
+                }
+                Err(SinkError(err)) => return Err(SinkError(err)),
+            }
+        }
+        Ok(true)
+    }
+}
+
+impl<B, F> QuadSource for LineSource<B, F, NQuadsParser>
+where
+    B: BufRead,
+    F: FnMut(usize, &str),
+{
+    type Error = io::Error;
+    type Quad = crate::quad::streaming_mode::ByRefTerms;
+
+    fn try_for_some_quad<G, E>(&mut self, f: &mut G) -> StreamResult<bool, io::Error, E>
+    where
+        G: FnMut(StreamedQuad<Self::Quad>) -> Result<(), E>,
+        E: Error,
+    {
+        if !self.next_line().map_err(SourceError)? {
+            return Ok(false);
+        }
+        let mut source = match RioNQParser::new(self.line.as_bytes()) {
+            Ok(parser) => StrictRioSource::Parser(parser),
+            Err(err) => StrictRioSource::Error(Some(err)),
+        };
+        loop {
+            match source.try_for_some_quad(f) {
+                Ok(true) => (),
+                Ok(false) => break,
+                Err(SourceError(err)) => {
+                    (self.on_error)(self.line_number, &err.to_string());
+                    break;
+                }
+                Err(SinkError(err)) => return Err(SinkError(err)),
+            }
+        }
+        Ok(true)
+    }
+}
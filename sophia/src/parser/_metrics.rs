@@ -0,0 +1,210 @@
+// this module is transparently re-exported by its parent `parser`
+
+use std::error::Error;
+use std::io::{self, BufRead, Read};
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+
+use crate::parser::{QuadParser, TripleParser};
+use crate::quad::stream::QuadSource;
+use crate::quad::streaming_mode::StreamedQuad;
+use crate::triple::stream::{StreamResult, TripleSource};
+use crate::triple::streaming_mode::StreamedTriple;
+
+/// Throughput metrics of a parser, as returned by [`MeteredSource::metrics`].
+///
+/// [`MeteredSource::metrics`]: struct.MeteredSource.html#method.metrics
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseMetrics {
+    /// The number of bytes consumed by the parser so far.
+    pub bytes: u64,
+    /// The number of triples (or quads) produced by the parser so far.
+    pub triples: usize,
+    /// The time spent in the parser (and in the callbacks it called) so far.
+    pub elapsed: Duration,
+}
+
+/// A source wrapping the source of a parser,
+/// and measuring its throughput (see [`metrics`](#method.metrics)).
+///
+/// It is built with [`from_triple_parser`](#method.from_triple_parser)
+/// or [`from_quad_parser`](#method.from_quad_parser).
+/// Parsers used without this wrapper do not pay any cost for metrics.
+pub struct MeteredSource<S> {
+    source: S,
+    bytes: Arc<AtomicU64>,
+    triples: usize,
+    elapsed: Duration,
+}
+
+impl<S> MeteredSource<S> {
+    /// Parse `data` with the given triple parser, measuring its throughput.
+    pub fn from_triple_parser<P, B>(parser: &P, data: B) -> Self
+    where
+        P: TripleParser<CountingRead<B>, Source = S>,
+        B: BufRead,
+    {
+        let (data, bytes) = CountingRead::new(data);
+        Self::new(parser.parse(data), bytes)
+    }
+
+    /// Parse `data` with the given quad parser, measuring its throughput.
+    pub fn from_quad_parser<P, B>(parser: &P, data: B) -> Self
+    where
+        P: QuadParser<CountingRead<B>, Source = S>,
+        B: BufRead,
+    {
+        let (data, bytes) = CountingRead::new(data);
+        Self::new(parser.parse(data), bytes)
+    }
+
+    fn new(source: S, bytes: Arc<AtomicU64>) -> Self {
+        MeteredSource {
+            source,
+            bytes,
+            triples: 0,
+            elapsed: Duration::default(),
+        }
+    }
+
+    /// The metrics collected so far.
+    pub fn metrics(&self) -> ParseMetrics {
+        ParseMetrics {
+            bytes: self.bytes.load(Ordering::Relaxed),
+            triples: self.triples,
+            elapsed: self.elapsed,
+        }
+    }
+}
+
+impl<S: TripleSource> TripleSource for MeteredSource<S> {
+    type Error = S::Error;
+    type Triple = S::Triple;
+
+    fn try_for_some_triple<F, E>(&mut self, f: &mut F) -> StreamResult<bool, S::Error, E>
+    where
+        F: FnMut(StreamedTriple<Self::Triple>) -> Result<(), E>,
+        E: Error,
+    {
+        let start = Instant::now();
+        let triples = &mut self.triples;
+        let res = self.source.try_for_some_triple(&mut |t| {
+            *triples += 1;
+            f(t)
+        });
+        self.elapsed += start.elapsed();
+        res
+    }
+}
+
+impl<S: QuadSource> QuadSource for MeteredSource<S> {
+    type Error = S::Error;
+    type Quad = S::Quad;
+
+    fn try_for_some_quad<F, E>(&mut self, f: &mut F) -> StreamResult<bool, S::Error, E>
+    where
+        F: FnMut(StreamedQuad<Self::Quad>) -> Result<(), E>,
+        E: Error,
+    {
+        let start = Instant::now();
+        let triples = &mut self.triples;
+        let res = self.source.try_for_some_quad(&mut |q| {
+            *triples += 1;
+            f(q)
+        });
+        self.elapsed += start.elapsed();
+        res
+    }
+}
+
+/// A reader counting the bytes consumed from the underlying reader,
+/// used by [`MeteredSource`](struct.MeteredSource.html).
+pub struct CountingRead<B> {
+    inner: B,
+    count: Arc<AtomicU64>,
+}
+
+impl<B> CountingRead<B> {
+    fn new(inner: B) -> (Self, Arc<AtomicU64>) {
+        let count = Arc::new(AtomicU64::new(0));
+        let reader = CountingRead {
+            inner,
+            count: count.clone(),
+        };
+        (reader, count)
+    }
+
+    fn add(&self, n: usize) {
+        self.count.fetch_add(n as u64, Ordering::Relaxed);
+    }
+}
+
+impl<B: BufRead> Read for CountingRead<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.add(n);
+        Ok(n)
+    }
+}
+
+impl<B: BufRead> BufRead for CountingRead<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.add(amt);
+        self.inner.consume(amt)
+    }
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::parser::trig::TriGParser;
+    use crate::parser::turtle::TurtleParser;
+
+    #[test]
+    fn triple_metrics() {
+        let ttl = r#"
+            @prefix : <http://example.org/>.
+            :alice a :Person; :name "Alice".
+            :bob a :Person; :name "Bob"; :knows :alice.
+        "#;
+        let mut source =
+            MeteredSource::from_triple_parser(&TurtleParser::default(), ttl.as_bytes());
+        assert_eq!(source.metrics(), ParseMetrics::default());
+        let mut count = 0;
+        source.for_each_triple(|_| count += 1).unwrap();
+        let metrics = source.metrics();
+        assert_eq!(metrics.triples, 5);
+        assert_eq!(metrics.triples, count);
+        assert_eq!(metrics.bytes, ttl.len() as u64);
+    }
+
+    #[test]
+    fn metered_source_is_send() {
+        fn assert_send<T: Send>(_: &T) {}
+        let source = MeteredSource::from_triple_parser(&TurtleParser::default(), &b""[..]);
+        assert_send(&source);
+    }
+
+    #[test]
+    fn quad_metrics() {
+        let trig = r#"
+            @prefix : <http://example.org/>.
+            :alice a :Person.
+            GRAPH :g { :bob a :Person; :name "Bob". }
+        "#;
+        let mut source = MeteredSource::from_quad_parser(&TriGParser::default(), trig.as_bytes());
+        source.for_each_quad(|_| ()).unwrap();
+        let metrics = source.metrics();
+        assert_eq!(metrics.triples, 3);
+        assert_eq!(metrics.bytes, trig.len() as u64);
+    }
+}
@@ -0,0 +1,107 @@
+//! Transparent decompression of gzipped RDF data.
+//!
+//! Many RDF dumps are distributed as `.nt.gz`, `.ttl.gz`...
+//! This module provides helpers to feed such data to any parser
+//! accepting a [`BufRead`].
+//! Every parser module also provides a `parse_gz_path` convenience function,
+//! built on top of [`open`](fn.open.html).
+//!
+//! This module requires the `gzip` feature.
+//!
+//! [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+
+use std::ffi::OsStr;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use flate2::read::GzDecoder;
+
+/// Wrap `read` into a gzip decoder, suitable for any parser accepting a `BufRead`.
+pub fn decode<R: Read>(read: R) -> BufReader<GzDecoder<R>> {
+    BufReader::new(GzDecoder::new(read))
+}
+
+/// Return `true` if the name of the given file ends with `.gz`.
+pub fn is_gz_path<P: AsRef<Path>>(path: P) -> bool {
+    path.as_ref().extension() == Some(OsStr::new("gz"))
+}
+
+/// Open the file at `path` as a `BufRead`,
+/// decompressing it on the fly if its name ends with `.gz`.
+pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Box<dyn BufRead>> {
+    let gz = is_gz_path(&path);
+    open_with(path, gz)
+}
+
+/// Open the file at `path` as a `BufRead`,
+/// decompressing it on the fly iff `gz` is `true`,
+/// regardless of the file name.
+pub fn open_with<P: AsRef<Path>>(path: P, gz: bool) -> io::Result<Box<dyn BufRead>> {
+    let file = File::open(path)?;
+    if gz {
+        Ok(Box::new(decode(file)))
+    } else {
+        Ok(Box::new(BufReader::new(file)))
+    }
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::inmem::FastGraph;
+    use crate::parser::nt;
+    use crate::triple::stream::TripleSource;
+    use flate2::write::GzEncoder;
+    use flate2::Compression;
+    use std::io::Write;
+
+    const NT: &str = r#"
+        <http://localhost/ex#me> <http://example.org/ns/knows> _:b1.
+        _:b1 <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/ns/Person>.
+        _:b1 <http://example.org/ns/name> "Alice".
+    "#;
+
+    fn gzipped(txt: &str) -> Vec<u8> {
+        let mut enc = GzEncoder::new(Vec::new(), Compression::default());
+        enc.write_all(txt.as_bytes()).unwrap();
+        enc.finish().unwrap()
+    }
+
+    #[test]
+    fn parse_gzipped_buffer() -> Result<(), Box<dyn std::error::Error>> {
+        let data = gzipped(NT);
+        let mut g = FastGraph::new();
+        let c = nt::parse_bufread(decode(&data[..])).in_graph(&mut g)?;
+        assert_eq!(c, 3);
+        Ok(())
+    }
+
+    #[test]
+    fn parse_gz_path_and_plain_path() -> Result<(), Box<dyn std::error::Error>> {
+        let dir = std::env::temp_dir();
+        let gz_path = dir.join(format!("sophia-gzip-test-{}.nt.gz", std::process::id()));
+        let nt_path = dir.join(format!("sophia-gzip-test-{}.nt", std::process::id()));
+        std::fs::write(&gz_path, gzipped(NT))?;
+        std::fs::write(&nt_path, NT)?;
+
+        assert!(is_gz_path(&gz_path));
+        assert!(!is_gz_path(&nt_path));
+
+        let mut g = FastGraph::new();
+        let c = nt::parse_gz_path(&gz_path)?.in_graph(&mut g)?;
+        assert_eq!(c, 3);
+
+        let mut g = FastGraph::new();
+        let c = nt::parse_gz_path(&nt_path)?.in_graph(&mut g)?;
+        assert_eq!(c, 3);
+
+        std::fs::remove_file(gz_path)?;
+        std::fs::remove_file(nt_path)?;
+        Ok(())
+    }
+}
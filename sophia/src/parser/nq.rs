@@ -2,17 +2,42 @@
 //!
 //! [N-Quads]: https://www.w3.org/TR/n-quads/
 
-use std::io::BufRead;
+use std::error::Error;
+use std::io::{BufRead, Read, Result as IoResult};
 
 use rio_turtle::{NQuadsParser as RioNQParser, TurtleError};
 
 use crate::parser::rio_common::*;
-use crate::parser::QuadParser;
+use crate::parser::{LineSource, QuadParser};
+use crate::quad::stream::QuadSource;
+use crate::quad::streaming_mode::{ByRefTerms, StreamedQuad};
+use crate::quad::Quad;
+use crate::triple::stream::StreamResult;
+use sophia_term::{same_graph_name, BoxTerm};
 
 /// N-Quads parser based on RIO.
+///
+/// See [`ConfiguredNQuadsParser`](struct.ConfiguredNQuadsParser.html)
+/// for a parser with non-default options.
 #[derive(Clone, Debug, Default)]
 pub struct NQuadsParser {}
 
+impl NQuadsParser {
+    /// Parse `data` leniently, one line at a time.
+    ///
+    /// Instead of failing, the returned source calls `on_error`
+    /// with the line number (starting at 1) and the error message of each invalid line,
+    /// and skips that line.
+    /// See [`LineSource`](../struct.LineSource.html).
+    pub fn parse_with_error_handler<B, F>(&self, data: B, on_error: F) -> LineSource<B, F, Self>
+    where
+        B: BufRead,
+        F: FnMut(usize, &str),
+    {
+        LineSource::new(data, on_error)
+    }
+}
+
 impl<B: BufRead> QuadParser<B> for NQuadsParser {
     type Source = StrictRioSource<RioNQParser<B>, TurtleError>;
     fn parse(&self, data: B) -> Self::Source {
@@ -20,6 +45,200 @@ impl<B: BufRead> QuadParser<B> for NQuadsParser {
     }
 }
 
+/// N-Quads parser based on RIO, with non-default options.
+#[derive(Clone, Debug, Default)]
+pub struct ConfiguredNQuadsParser {
+    lenient: bool,
+    coalesce_adjacent: bool,
+}
+
+impl ConfiguredNQuadsParser {
+    /// If true, accept a missing ` .` at the end of the last statement of the document.
+    /// The dot is still required after every other statement.
+    ///
+    /// Defaults to false.
+    pub fn set_lenient(&mut self, lenient: bool) -> &mut Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// If true, skip any quad identical to the quad immediately preceding it.
+    ///
+    /// This cheaply reduces the output of documents repeating the same quad many times,
+    /// but is not a full deduplication: non-adjacent duplicates are still yielded.
+    ///
+    /// Defaults to false.
+    pub fn set_coalesce_adjacent(&mut self, coalesce_adjacent: bool) -> &mut Self {
+        self.coalesce_adjacent = coalesce_adjacent;
+        self
+    }
+}
+
+impl<B: BufRead> QuadParser<B> for ConfiguredNQuadsParser {
+    type Source = NqSource<B>;
+    fn parse(&self, data: B) -> Self::Source {
+        NqSource::new(data, self.lenient, self.coalesce_adjacent)
+    }
+}
+
+/// The source returned by [`ConfiguredNQuadsParser`](struct.ConfiguredNQuadsParser.html).
+pub struct NqSource<B: BufRead> {
+    source: StrictRioSource<RioNQParser<LenientReader<B>>, TurtleError>,
+    coalesce_adjacent: bool,
+    last: Option<([BoxTerm; 3], Option<BoxTerm>)>,
+}
+
+impl<B: BufRead> NqSource<B> {
+    fn new(data: B, lenient: bool, coalesce_adjacent: bool) -> Self {
+        NqSource {
+            source: StrictRioSource::from(RioNQParser::new(LenientReader::new(data, lenient))),
+            coalesce_adjacent,
+            last: None,
+        }
+    }
+}
+
+impl<B: BufRead> QuadSource for NqSource<B> {
+    type Error = TurtleError;
+    type Quad = ByRefTerms;
+
+    fn try_for_some_quad<F, E>(&mut self, f: &mut F) -> StreamResult<bool, TurtleError, E>
+    where
+        F: FnMut(StreamedQuad<Self::Quad>) -> Result<(), E>,
+        E: Error,
+    {
+        if !self.coalesce_adjacent {
+            return self.source.try_for_some_quad(f);
+        }
+        let last = &mut self.last;
+        self.source.try_for_some_quad(&mut |q| {
+            if let Some(([s, p, o], g)) = last {
+                if s == q.s() && p == q.p() && o == q.o() && same_graph_name(g.as_ref(), q.g()) {
+                    return Ok(());
+                }
+            }
+            *last = Some((
+                [q.s().into(), q.p().into(), q.o().into()],
+                q.g().map(BoxTerm::from),
+            ));
+            f(q)
+        })
+    }
+}
+
+/// A `BufRead` adapter adding the final dot of a document when it is missing.
+///
+/// When not lenient, this simply passes the underlying data through.
+pub(crate) struct LenientReader<B> {
+    inner: B,
+    lenient: bool,
+    buf: Vec<u8>,
+    pos: usize,
+}
+
+impl<B: BufRead> LenientReader<B> {
+    fn new(inner: B, lenient: bool) -> Self {
+        LenientReader {
+            inner,
+            lenient,
+            buf: Vec::new(),
+            pos: 0,
+        }
+    }
+
+    /// Read the next line into `self.buf`,
+    /// adding a dot to it if it is the last statement and lacks one.
+    fn next_line(&mut self) -> IoResult<()> {
+        self.buf.clear();
+        self.pos = 0;
+        self.inner.read_until(b'\n', &mut self.buf)?;
+        let end = significant_len(&self.buf);
+        if end == 0 || self.buf[end - 1] == b'.' {
+            return Ok(());
+        }
+        // look ahead for another statement before EOF
+        let mut tail = Vec::new();
+        loop {
+            let start = tail.len();
+            if self.inner.read_until(b'\n', &mut tail)? == 0 {
+                self.buf.splice(end..end, b" .".iter().cloned());
+                break;
+            }
+            if significant_len(&tail[start..]) > 0 {
+                break;
+            }
+        }
+        self.buf.extend_from_slice(&tail);
+        Ok(())
+    }
+}
+
+impl<B: BufRead> Read for LenientReader<B> {
+    fn read(&mut self, out: &mut [u8]) -> IoResult<usize> {
+        let available = self.fill_buf()?;
+        let n = available.len().min(out.len());
+        out[..n].copy_from_slice(&available[..n]);
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<B: BufRead> BufRead for LenientReader<B> {
+    fn fill_buf(&mut self) -> IoResult<&[u8]> {
+        if !self.lenient {
+            return self.inner.fill_buf();
+        }
+        if self.pos >= self.buf.len() {
+            self.next_line()?;
+        }
+        Ok(&self.buf[self.pos..])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if self.lenient {
+            self.pos += amt;
+        } else {
+            self.inner.consume(amt);
+        }
+    }
+}
+
+/// Length of `line` up to its last byte that is neither whitespace nor part of a comment.
+fn significant_len(line: &[u8]) -> usize {
+    let mut end = 0;
+    let mut in_iri = false;
+    let mut in_str = false;
+    let mut escaped = false;
+    for (i, b) in line.iter().enumerate() {
+        if in_str {
+            if escaped {
+                escaped = false;
+            } else if *b == b'\\' {
+                escaped = true;
+            } else if *b == b'"' {
+                in_str = false;
+            }
+            end = i + 1;
+            continue;
+        }
+        if in_iri {
+            in_iri = *b != b'>';
+            end = i + 1;
+            continue;
+        }
+        match b {
+            b'#' => break,
+            b'<' => in_iri = true,
+            b'"' => in_str = true,
+            _ => {}
+        }
+        if !b.is_ascii_whitespace() {
+            end = i + 1;
+        }
+    }
+    end
+}
+
 def_mod_functions_for_bufread_parser!(NQuadsParser, QuadParser);
 
 // ---------------------------------------------------------------------------------
@@ -77,4 +296,85 @@ mod test {
             .is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_lenient_missing_last_dot() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let nq = r#"<tag:s> <tag:p> "a#b" <tag:g> .
+<tag:s> <tag:p> <tag:o1> .
+<tag:s> <tag:p> <tag:o2> <tag:g> # no dot here
+
+"#;
+
+        let mut d = FastDataset::new();
+        let mut p = ConfiguredNQuadsParser::default();
+        p.set_lenient(true);
+        let c = p.parse_str(nq).in_dataset(&mut d)?;
+        assert_eq!(c, 3);
+        assert!(d
+            .quads_matching(
+                &ANY,
+                &ANY,
+                &StaticTerm::new_iri("tag:o2").unwrap(),
+                &Some(&StaticTerm::new_iri("tag:g").unwrap()),
+            )
+            .next()
+            .is_some());
+
+        let mut d = FastDataset::new();
+        assert!(NQuadsParser::default()
+            .parse_str(nq)
+            .in_dataset(&mut d)
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_lenient_still_requires_inner_dots() {
+        let nq = "<tag:s> <tag:p> <tag:o1>\n<tag:s> <tag:p> <tag:o2> .\n";
+        let mut d = FastDataset::new();
+        let mut p = ConfiguredNQuadsParser::default();
+        p.set_lenient(true);
+        assert!(p.parse_str(nq).in_dataset(&mut d).is_err());
+    }
+
+    #[test]
+    fn test_coalesce_adjacent() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let nq = "<tag:s> <tag:p> <tag:o> <tag:g>.\n\
+                  <tag:s> <tag:p> <tag:o> <tag:g>.\n\
+                  <tag:s> <tag:p> <tag:o>.\n\
+                  <tag:s> <tag:p> <tag:o>.\n\
+                  <tag:s> <tag:p> <tag:o> <tag:g>.\n";
+
+        let mut quads = vec![];
+        let mut p = ConfiguredNQuadsParser::default();
+        p.set_coalesce_adjacent(true);
+        p.parse_str(nq).for_each_quad(|q| {
+            quads.push(q.g().is_some());
+        })?;
+        assert_eq!(quads, vec![true, false, true]);
+
+        let mut n = 0;
+        NQuadsParser::default()
+            .parse_str(nq)
+            .for_each_quad(|_| n += 1)?;
+        assert_eq!(n, 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_handler() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let nq = "<tag:s> <tag:p> <tag:o> <tag:g>.\n\
+                  <tag:s> <tag:p> <tag:o> <tag:g> <tag:h>.\n\
+                  <tag:s> <tag:p> <tag:o>.\n";
+
+        let mut lines = vec![];
+        let mut d = FastDataset::new();
+        let c = NQuadsParser::default()
+            .parse_with_error_handler(nq.as_bytes(), |line, _| lines.push(line))
+            .in_dataset(&mut d)?;
+        assert_eq!(c, 2);
+        assert_eq!(lines, vec![2]);
+        assert_eq!(d.quads().count(), 2);
+        Ok(())
+    }
 }
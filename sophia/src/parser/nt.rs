@@ -2,26 +2,383 @@
 //!
 //! [N-Triples]: https://www.w3.org/TR/n-triples/
 
-use std::io::BufRead;
+use std::collections::VecDeque;
+use std::error::Error;
+use std::io::{self, BufRead, Read};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
 
+use rio_api::parser::ParseError;
 use rio_turtle::{NTriplesParser as RioNTParser, TurtleError};
 
 use crate::parser::rio_common::*;
-use crate::parser::TripleParser;
+use crate::parser::{LineSource, TripleParser};
+use crate::triple::stream::{SinkError, SourceError, StreamResult, TripleSource};
+use crate::triple::streaming_mode::{ByRefTerms, StreamedTriple};
+use crate::triple::Triple;
+use sophia_term::BoxTerm;
 
 /// N-Triples parser based on RIO.
+///
+/// Since N-Triples is a subset of Turtle,
+/// Turtle data is easily fed to this parser by mistake.
+/// When a parse error occurs in data that looks like Turtle
+/// (i.e. containing a token that can not occur in N-Triples,
+/// such as `@prefix`, a prefixed name or a bare number),
+/// it is reported as [`NtError::LooksLikeTurtle`](enum.NtError.html#variant.LooksLikeTurtle).
+///
+/// See [`ConfiguredNTriplesParser`](struct.ConfiguredNTriplesParser.html)
+/// for a parser with non-default options.
 #[derive(Clone, Debug, Default)]
 pub struct NTriplesParser {}
 
+impl NTriplesParser {
+    /// Parse `data` leniently, one line at a time.
+    ///
+    /// Instead of failing, the returned source calls `on_error`
+    /// with the line number (starting at 1) and the error message of each invalid line,
+    /// and skips that line.
+    /// See [`LineSource`](../struct.LineSource.html).
+    pub fn parse_with_error_handler<B, F>(&self, data: B, on_error: F) -> LineSource<B, F, Self>
+    where
+        B: BufRead,
+        F: FnMut(usize, &str),
+    {
+        LineSource::new(data, on_error)
+    }
+
+    /// Parse `data`, keeping its comments.
+    ///
+    /// The returned iterator yields the triples and the comments of `data`
+    /// (as [`Item`](enum.Item.html)s) in document order;
+    /// a comment following a triple on the same line is yielded after that triple.
+    /// See [`CommentedSource`](struct.CommentedSource.html).
+    pub fn parse_with_comments<B>(&self, data: B) -> CommentedSource<B>
+    where
+        B: BufRead,
+    {
+        CommentedSource {
+            data,
+            line: String::new(),
+            pending: VecDeque::new(),
+            failed: false,
+        }
+    }
+}
+
 impl<B: BufRead> TripleParser<B> for NTriplesParser {
-    type Source = StrictRioSource<RioNTParser<B>, TurtleError>;
+    type Source = NtSource<B>;
     fn parse(&self, data: B) -> Self::Source {
-        StrictRioSource::from(RioNTParser::new(data))
+        NtSource::new(data, false)
+    }
+}
+
+/// N-Triples parser based on RIO, with non-default options.
+#[derive(Clone, Debug, Default)]
+pub struct ConfiguredNTriplesParser {
+    coalesce_adjacent: bool,
+}
+
+impl ConfiguredNTriplesParser {
+    /// If true, skip any triple identical to the triple immediately preceding it.
+    ///
+    /// This cheaply reduces the output of documents repeating the same triple many times,
+    /// but is not a full deduplication: non-adjacent duplicates are still yielded.
+    ///
+    /// Defaults to false.
+    pub fn set_coalesce_adjacent(&mut self, coalesce_adjacent: bool) -> &mut Self {
+        self.coalesce_adjacent = coalesce_adjacent;
+        self
+    }
+}
+
+impl<B: BufRead> TripleParser<B> for ConfiguredNTriplesParser {
+    type Source = NtSource<B>;
+    fn parse(&self, data: B) -> Self::Source {
+        NtSource::new(data, self.coalesce_adjacent)
     }
 }
 
 def_mod_functions_for_bufread_parser!(NTriplesParser, TripleParser);
 
+/// An item of an N-Triples document,
+/// as yielded by [`CommentedSource`](struct.CommentedSource.html).
+#[derive(Clone, Debug)]
+pub enum Item {
+    /// A triple.
+    Triple([BoxTerm; 3]),
+    /// The text of a comment, after the leading `#` and without the line ending.
+    Comment(String),
+}
+
+/// An iterator over the triples and comments of an N-Triples document,
+/// returned by [`NTriplesParser::parse_with_comments`](struct.NTriplesParser.html#method.parse_with_comments).
+///
+/// Contrarily to the [source](struct.NtSource.html) returned by `parse`,
+/// it parses the data one line at a time, and yields owned triples.
+/// It stops after the first error.
+pub struct CommentedSource<B> {
+    data: B,
+    line: String,
+    pending: VecDeque<Item>,
+    failed: bool,
+}
+
+impl<B: BufRead> CommentedSource<B> {
+    /// Parse `self.line` into `self.pending`.
+    fn parse_line(&mut self) -> Result<(), TurtleError> {
+        let line = self.line.trim_end_matches(['\n', '\r']);
+        let (statement, comment) = match comment_start(line) {
+            Some(i) => (&line[..i], Some(&line[i + 1..])),
+            None => (line, None),
+        };
+        if !statement.trim().is_empty() {
+            let parser = RioNTParser::new(statement.as_bytes())?;
+            let pending = &mut self.pending;
+            StrictRioSource::Parser(parser).for_each_triple(|t| {
+                pending.push_back(Item::Triple([t.s().into(), t.p().into(), t.o().into()]))
+            })?;
+        }
+        if let Some(comment) = comment {
+            self.pending.push_back(Item::Comment(comment.to_string()));
+        }
+        Ok(())
+    }
+}
+
+impl<B: BufRead> Iterator for CommentedSource<B> {
+    type Item = Result<Item, TurtleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(item) = self.pending.pop_front() {
+                return Some(Ok(item));
+            }
+            if self.failed {
+                return None;
+            }
+            self.line.clear();
+            let res = match self.data.read_line(&mut self.line) {
+                Ok(0) => return None,
+                Ok(_) => self.parse_line(),
+                Err(err) => Err(err.into()),
+            };
+            if let Err(err) = res {
+                self.failed = true;
+                return Some(Err(err));
+            }
+        }
+    }
+}
+
+/// The position of the `#` starting a comment in `line`, if any,
+/// ignoring `#` characters inside IRIs and literals.
+fn comment_start(line: &str) -> Option<usize> {
+    let (mut in_iri, mut in_string, mut escaped) = (false, false, false);
+    for (i, b) in line.bytes().enumerate() {
+        if in_string {
+            if escaped {
+                escaped = false;
+            } else if b == b'\\' {
+                escaped = true;
+            } else if b == b'"' {
+                in_string = false;
+            }
+        } else if in_iri {
+            in_iri = b != b'>';
+        } else {
+            match b {
+                b'<' => in_iri = true,
+                b'"' => in_string = true,
+                b'#' => return Some(i),
+                _ => (),
+            }
+        }
+    }
+    None
+}
+
+/// The error raised by [`NTriplesParser`](struct.NTriplesParser.html).
+#[derive(Debug, thiserror::Error)]
+pub enum NtError {
+    /// The data looks like Turtle, which should be parsed with
+    /// [`TurtleParser`](../turtle/struct.TurtleParser.html) instead.
+    #[error("Data looks like Turtle, not N-Triples: {0}")]
+    LooksLikeTurtle(#[source] TurtleError),
+    /// Any other error raised by the underlying parser.
+    #[error(transparent)]
+    Parse(#[from] TurtleError),
+}
+
+/// The source returned by [`NTriplesParser`](struct.NTriplesParser.html)
+/// and [`ConfiguredNTriplesParser`](struct.ConfiguredNTriplesParser.html).
+pub struct NtSource<B: BufRead> {
+    source: StrictRioSource<RioNTParser<TurtleSniffer<B>>, TurtleError>,
+    turtle_line: Arc<AtomicUsize>,
+    coalesce_adjacent: bool,
+    last: Option<[BoxTerm; 3]>,
+}
+
+impl<B: BufRead> NtSource<B> {
+    fn new(data: B, coalesce_adjacent: bool) -> Self {
+        let turtle_line = Arc::new(AtomicUsize::new(NO_LINE));
+        let sniffer = TurtleSniffer {
+            inner: data,
+            state: SniffState {
+                line: 0,
+                lex: Lex::Default,
+                turtle_line: turtle_line.clone(),
+            },
+        };
+        NtSource {
+            source: StrictRioSource::from(RioNTParser::new(sniffer)),
+            turtle_line,
+            coalesce_adjacent,
+            last: None,
+        }
+    }
+
+    fn classify(&self, err: TurtleError) -> NtError {
+        let err_line = err.textual_position().map(|pos| pos.line_number());
+        match (self.turtle_line.load(Ordering::Relaxed), err_line) {
+            (turtle_line, Some(err_line)) if turtle_line <= err_line => {
+                NtError::LooksLikeTurtle(err)
+            }
+            _ => NtError::Parse(err),
+        }
+    }
+}
+
+impl<B: BufRead> TripleSource for NtSource<B> {
+    type Error = NtError;
+    type Triple = ByRefTerms;
+
+    fn try_for_some_triple<F, E>(&mut self, f: &mut F) -> StreamResult<bool, NtError, E>
+    where
+        F: FnMut(StreamedTriple<Self::Triple>) -> Result<(), E>,
+        E: Error,
+    {
+        let res = if self.coalesce_adjacent {
+            let last = &mut self.last;
+            self.source.try_for_some_triple(&mut |t| {
+                if let Some([s, p, o]) = last {
+                    if s == t.s() && p == t.p() && o == t.o() {
+                        return Ok(());
+                    }
+                }
+                *last = Some([t.s().into(), t.p().into(), t.o().into()]);
+                f(t)
+            })
+        } else {
+            self.source.try_for_some_triple(f)
+        };
+        res.map_err(|err| match err {
+            SourceError(err) => SourceError(self.classify(err)),
+            SinkError(err) => SinkError(err),
+        })
+    }
+}
+
+/// The value of `turtle_line` when no line looks like Turtle.
+const NO_LINE: usize = usize::MAX;
+
+/// A reader passing data to the RIO parser,
+/// and recording the first line (starting at 0, as in RIO positions)
+/// containing a token that can not occur in N-Triples.
+struct TurtleSniffer<B> {
+    inner: B,
+    state: SniffState,
+}
+
+struct SniffState {
+    line: usize,
+    lex: Lex,
+    turtle_line: Arc<AtomicUsize>,
+}
+
+/// The lexical context of a [`TurtleSniffer`](struct.TurtleSniffer.html).
+#[derive(Clone, Copy)]
+enum Lex {
+    /// Between tokens
+    Default,
+    Iri,
+    Str {
+        escape: bool,
+    },
+    /// Right after the closing quote of a literal, where `@` or `^^` may occur
+    AfterStr,
+    /// Between the two `^` of a datatype
+    Caret,
+    /// A blank node label or a language tag
+    Label,
+    Comment,
+}
+
+impl SniffState {
+    fn sniff(&mut self, bytes: &[u8]) {
+        if self.turtle_line.load(Ordering::Relaxed) != NO_LINE {
+            return;
+        }
+        for &b in bytes {
+            if b == b'\n' {
+                self.line += 1;
+            }
+            self.lex = match self.lex {
+                Lex::Iri if b == b'>' => Lex::Default,
+                Lex::Iri => Lex::Iri,
+                Lex::Str { escape: true } => Lex::Str { escape: false },
+                Lex::Str { .. } if b == b'\\' => Lex::Str { escape: true },
+                Lex::Str { .. } if b == b'"' => Lex::AfterStr,
+                Lex::Str { .. } => Lex::Str { escape: false },
+                Lex::Comment if b == b'\n' || b == b'\r' => Lex::Default,
+                Lex::Comment => Lex::Comment,
+                Lex::AfterStr if b == b'@' => Lex::Label,
+                Lex::AfterStr if b == b'^' => Lex::Caret,
+                Lex::Caret if b == b'^' => Lex::Default,
+                Lex::Label if !matches!(b, b' ' | b'\t' | b'\r' | b'\n' | b'<' | b'"' | b'#') => {
+                    Lex::Label
+                }
+                Lex::Default | Lex::AfterStr | Lex::Label => match b {
+                    b'<' => Lex::Iri,
+                    b'"' => Lex::Str { escape: false },
+                    b'#' => Lex::Comment,
+                    b'_' => Lex::Label,
+                    b' ' | b'\t' | b'\r' | b'\n' | b'.' => Lex::Default,
+                    _ => {
+                        self.turtle_line.store(self.line, Ordering::Relaxed);
+                        return;
+                    }
+                },
+                Lex::Caret => {
+                    self.turtle_line.store(self.line, Ordering::Relaxed);
+                    return;
+                }
+            };
+        }
+    }
+}
+
+impl<B: BufRead> Read for TurtleSniffer<B> {
+    fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+        let n = self.inner.read(buf)?;
+        self.state.sniff(&buf[..n]);
+        Ok(n)
+    }
+}
+
+impl<B: BufRead> BufRead for TurtleSniffer<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        self.inner.fill_buf()
+    }
+
+    fn consume(&mut self, amt: usize) {
+        if let Ok(buf) = self.inner.fill_buf() {
+            self.state.sniff(&buf[..amt.min(buf.len())]);
+        }
+        self.inner.consume(amt)
+    }
+}
+
 // ---------------------------------------------------------------------------------
 //                                      tests
 // ---------------------------------------------------------------------------------
@@ -74,4 +431,129 @@ mod test {
             .is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_turtle_input() {
+        let turtle = "<http://example.org/a> <http://example.org/p> <http://example.org/b>.\n\
+                      @prefix ex: <http://example.org/>.\n\
+                      ex:a ex:p ex:b.\n";
+        let mut g = FastGraph::new();
+        let err = parse_str(turtle).in_graph(&mut g).unwrap_err();
+        assert!(matches!(err, SourceError(NtError::LooksLikeTurtle(_))));
+        assert!(err.to_string().contains("looks like Turtle"));
+
+        let turtle = "_:b1 <http://example.org/p> ex:b.\n";
+        let err = parse_str(turtle).in_graph(&mut g).unwrap_err();
+        assert!(matches!(err, SourceError(NtError::LooksLikeTurtle(_))));
+
+        let turtle = "<http://example.org/a> a <http://example.org/C>.\n";
+        let err = parse_str(turtle).in_graph(&mut g).unwrap_err();
+        assert!(matches!(err, SourceError(NtError::LooksLikeTurtle(_))));
+
+        let turtle = "<http://example.org/a> <http://example.org/p> 42.\n";
+        let err = parse_str(turtle).in_graph(&mut g).unwrap_err();
+        assert!(matches!(err, SourceError(NtError::LooksLikeTurtle(_))));
+
+        let nt = "<http://example.org/a> <http://example.org/p> .\n";
+        let err = parse_str(nt).in_graph(&mut g).unwrap_err();
+        assert!(matches!(err, SourceError(NtError::Parse(_))));
+
+        // valid N-Triples is not mistaken for Turtle
+        let nt = "_:b1 <http://example.org/p> \"a \\\" b\"@en-US.\n\
+                  _:b1 <http://example.org/p> \"42\"^^<http://example.org/int>. # c: d\n";
+        assert_eq!(parse_str(nt).in_graph(&mut g).unwrap(), 2);
+    }
+
+    #[test]
+    fn test_comments() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let nt = "# header\n\
+                  <http://example.org/a> <http://example.org/p> \"x#y\". # trailing\n\
+                  _:b <http://example.org/p> <http://example.org/c#d>.\r\n\
+                  \n\
+                  #between statements\n\
+                  _:b <http://example.org/q> \"z\".\n";
+
+        let items = NTriplesParser::default()
+            .parse_with_comments(nt.as_bytes())
+            .collect::<Result<Vec<_>, _>>()?;
+        let summary: Vec<_> = items
+            .iter()
+            .map(|item| match item {
+                Item::Triple([_, p, o]) => format!("{} {}", p, o),
+                Item::Comment(txt) => format!("#{}", txt),
+            })
+            .collect();
+        assert_eq!(
+            summary,
+            vec![
+                "# header",
+                "<http://example.org/p> \"x#y\"",
+                "# trailing",
+                "<http://example.org/p> <http://example.org/c#d>",
+                "#between statements",
+                "<http://example.org/q> \"z\"",
+            ]
+        );
+        // blank nodes are shared across lines
+        match (&items[3], &items[5]) {
+            (Item::Triple(t1), Item::Triple(t2)) => assert_eq!(t1[0], t2[0]),
+            _ => panic!("expected triples"),
+        }
+
+        let mut items =
+            NTriplesParser::default().parse_with_comments(&b"# ok\n<a> <b> .\n# never\n"[..]);
+        assert!(matches!(items.next(), Some(Ok(Item::Comment(_)))));
+        assert!(matches!(items.next(), Some(Err(_))));
+        assert!(items.next().is_none());
+        Ok(())
+    }
+
+    #[test]
+    fn test_error_handler() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let nt = "<http://localhost/ex#me> <http://example.org/ns/knows> _:b1.\n\
+                  <http://localhost/ex#me> <http://example.org/ns/knows> .\n\
+                  _:b1 <http://example.org/ns/name> \"Alice\".\n\
+                  \n\
+                  _:b1 \"name\" \"Alice\".\n\
+                  _:b1 <http://example.org/ns/age> \"42\".\n";
+
+        let mut errors = vec![];
+        let mut g = FastGraph::new();
+        let c = NTriplesParser::default()
+            .parse_with_error_handler(nt.as_bytes(), |line, msg| {
+                errors.push((line, msg.to_string()))
+            })
+            .in_graph(&mut g)?;
+        assert_eq!(c, 3);
+        assert_eq!(errors.len(), 2);
+        assert_eq!(errors[0].0, 2);
+        assert_eq!(errors[1].0, 5);
+        assert!(!errors[0].1.is_empty());
+        // blank nodes are shared across lines
+        assert_eq!(g.subjects()?.len(), 2);
+        Ok(())
+    }
+
+    #[test]
+    fn test_coalesce_adjacent() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let nt = "<tag:s> <tag:p> <tag:o1>.\n\
+                  <tag:s> <tag:p> <tag:o1>.\n\
+                  <tag:s> <tag:p> <tag:o1>.\n\
+                  <tag:s> <tag:p> <tag:o2>.\n\
+                  <tag:s> <tag:p> <tag:o1>.\n";
+
+        let mut objects = vec![];
+        let mut p = ConfiguredNTriplesParser::default();
+        p.set_coalesce_adjacent(true);
+        p.parse_str(nt)
+            .for_each_triple(|t| objects.push(t.o().value().to_string()))?;
+        assert_eq!(objects, vec!["tag:o1", "tag:o2", "tag:o1"]);
+
+        let mut n = 0;
+        NTriplesParser::default()
+            .parse_str(nt)
+            .for_each_triple(|_| n += 1)?;
+        assert_eq!(n, 5);
+        Ok(())
+    }
 }
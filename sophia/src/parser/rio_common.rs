@@ -1,6 +1,7 @@
 //! Common implementations for adapting [RIO](https://github.com/Tpt/rio/blob/master/turtle/src/turtle.rs) parsers.
 
 use std::error::Error;
+use std::io::{self, BufRead, Read};
 use std::result::Result as StdResult;
 
 use rio_api::model::*;
@@ -219,3 +220,184 @@ pub fn rio2refterm(t: GeneralizedTerm) -> RefTerm {
 pub fn rio2boxterm(t: GeneralizedTerm) -> BoxTerm {
     rio2refterm(t).clone_with(Box::from)
 }
+
+/// A [`BufRead`] wrapper for Turtle-family documents,
+/// failing with an I/O error (of kind `InvalidData`)
+/// as soon as nested `[ ]` or `( )` exceed a given depth.
+///
+/// This protects recursive parsers (such as RIO's) from stack overflows on malicious input.
+/// Brackets occurring in IRIs, strings, comments or escape sequences are ignored.
+///
+/// [`BufRead`]: https://doc.rust-lang.org/std/io/trait.BufRead.html
+pub struct DepthLimitedRead<B> {
+    inner: B,
+    lexer: DepthLexer,
+    /// Number of bytes at the start of `inner`'s buffer that have already been scanned
+    scanned: usize,
+}
+
+/// Tracks the nesting depth of a Turtle-family document, byte after byte.
+struct DepthLexer {
+    max_depth: usize,
+    depth: usize,
+    state: LexState,
+}
+
+/// The lexical context of a [`DepthLimitedRead`](struct.DepthLimitedRead.html).
+#[derive(Clone, Copy, Debug)]
+enum LexState {
+    Default,
+    Escape,
+    Iri,
+    Comment,
+    /// A quote has been read `count` times, we do not yet know if it starts a long string
+    Quotes {
+        quote: u8,
+        count: u8,
+    },
+    /// Inside a string; `closing` counts the consecutive quotes read so far
+    Str {
+        quote: u8,
+        long: bool,
+        escape: bool,
+        closing: u8,
+    },
+}
+
+impl<B: BufRead> DepthLimitedRead<B> {
+    /// Wrap `inner`, allowing at most `max_depth` nested brackets.
+    pub fn new(inner: B, max_depth: usize) -> Self {
+        DepthLimitedRead {
+            inner,
+            lexer: DepthLexer {
+                max_depth,
+                depth: 0,
+                state: LexState::Default,
+            },
+            scanned: 0,
+        }
+    }
+}
+
+impl DepthLexer {
+    /// Update the lexical state with byte `b`.
+    ///
+    /// Return false, *without* updating anything, if `b` exceeds the maximum depth.
+    fn scan(&mut self, b: u8) -> bool {
+        use LexState::*;
+        self.state = match self.state {
+            Default => match b {
+                b'[' | b'(' if self.depth >= self.max_depth => return false,
+                b'[' | b'(' => {
+                    self.depth += 1;
+                    Default
+                }
+                b']' | b')' => {
+                    self.depth = self.depth.saturating_sub(1);
+                    Default
+                }
+                b'\\' => Escape,
+                b'<' => Iri,
+                b'#' => Comment,
+                b'"' | b'\'' => Quotes { quote: b, count: 1 },
+                _ => Default,
+            },
+            Escape => Default,
+            Iri if b == b'>' => Default,
+            Comment if b == b'\n' || b == b'\r' => Default,
+            Iri | Comment => self.state,
+            Quotes { quote, count } if b == quote => {
+                if count == 2 {
+                    Str {
+                        quote,
+                        long: true,
+                        escape: false,
+                        closing: 0,
+                    }
+                } else {
+                    Quotes { quote, count: 2 }
+                }
+            }
+            Quotes { quote, count } => {
+                self.state = if count == 1 {
+                    Str {
+                        quote,
+                        long: false,
+                        escape: false,
+                        closing: 0,
+                    }
+                } else {
+                    // empty string
+                    Default
+                };
+                return self.scan(b);
+            }
+            Str {
+                quote,
+                long,
+                escape,
+                closing,
+            } => {
+                if escape {
+                    Str {
+                        quote,
+                        long,
+                        escape: false,
+                        closing: 0,
+                    }
+                } else if b == b'\\' {
+                    Str {
+                        quote,
+                        long,
+                        escape: true,
+                        closing: 0,
+                    }
+                } else if b == quote && (!long || closing == 2) {
+                    Default
+                } else {
+                    Str {
+                        quote,
+                        long,
+                        escape: false,
+                        closing: if b == quote { closing + 1 } else { 0 },
+                    }
+                }
+            }
+        };
+        true
+    }
+}
+
+impl<B: BufRead> Read for DepthLimitedRead<B> {
+    fn read(&mut self, out: &mut [u8]) -> io::Result<usize> {
+        let n = {
+            let buf = self.fill_buf()?;
+            let n = buf.len().min(out.len());
+            out[..n].copy_from_slice(&buf[..n]);
+            n
+        };
+        self.consume(n);
+        Ok(n)
+    }
+}
+
+impl<B: BufRead> BufRead for DepthLimitedRead<B> {
+    fn fill_buf(&mut self) -> io::Result<&[u8]> {
+        let buf = self.inner.fill_buf()?;
+        while self.scanned < buf.len() && self.lexer.scan(buf[self.scanned]) {
+            self.scanned += 1;
+        }
+        if self.scanned == 0 && !buf.is_empty() {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                format!("maximum nesting depth ({}) exceeded", self.lexer.max_depth),
+            ));
+        }
+        Ok(&buf[..self.scanned])
+    }
+
+    fn consume(&mut self, amt: usize) {
+        self.inner.consume(amt);
+        self.scanned -= amt;
+    }
+}
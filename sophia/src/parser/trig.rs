@@ -1,13 +1,20 @@
 //! Adapter for the TriG parser from [RIO](https://github.com/Tpt/rio/blob/master/turtle/src/turtle.rs)
 
+use std::error::Error;
 use std::io::BufRead;
 
-use rio_turtle::{TriGParser as RioTriGParser, TurtleError};
+use rio_turtle::{GTriGParser as RioGTriGParser, TriGParser as RioTriGParser, TurtleError};
 
 use crate::parser::rio_common::*;
 use crate::parser::QuadParser;
+use crate::quad::stream::QuadSource;
+use crate::quad::streaming_mode::{ByRefTerms, StreamedQuad};
+use crate::triple::stream::StreamResult;
 
 /// TriG parser based on RIO.
+///
+/// See [`ConfiguredTriGParser`](struct.ConfiguredTriGParser.html)
+/// for a parser with non-default options.
 #[derive(Clone, Debug, Default)]
 pub struct TriGParser {
     pub base: Option<String>,
@@ -24,6 +31,83 @@ impl<B: BufRead> QuadParser<B> for TriGParser {
     }
 }
 
+/// TriG parser based on RIO, with non-default options.
+#[derive(Clone, Debug)]
+pub struct ConfiguredTriGParser {
+    base: Option<String>,
+    strict: bool,
+}
+
+impl Default for ConfiguredTriGParser {
+    fn default() -> Self {
+        ConfiguredTriGParser {
+            base: None,
+            strict: true,
+        }
+    }
+}
+
+impl From<TriGParser> for ConfiguredTriGParser {
+    fn from(parser: TriGParser) -> Self {
+        ConfiguredTriGParser {
+            base: parser.base,
+            ..Self::default()
+        }
+    }
+}
+
+impl ConfiguredTriGParser {
+    /// If true, reject any statement that is not valid in strict RDF.
+    /// If false, accept generalized RDF (e.g. literals as subjects, blank nodes as predicates),
+    /// as [`GTriGParser`](../gtrig/struct.GTriGParser.html) does;
+    /// such quads can be detected with
+    /// [`Quad::is_generalized`](../../quad/trait.Quad.html#method.is_generalized).
+    ///
+    /// Defaults to true.
+    pub fn set_strict(&mut self, strict: bool) -> &mut Self {
+        self.strict = strict;
+        self
+    }
+}
+
+impl<B: BufRead> QuadParser<B> for ConfiguredTriGParser {
+    type Source = TriGSource<B>;
+    fn parse(&self, data: B) -> Self::Source {
+        let base: &str = match &self.base {
+            Some(base) => base,
+            None => "x-no-base:///",
+        };
+        if self.strict {
+            TriGSource::Strict(StrictRioSource::from(RioTriGParser::new(data, base)))
+        } else {
+            TriGSource::Generalized(GeneralizedRioSource::from(RioGTriGParser::new(data, base)))
+        }
+    }
+}
+
+/// The source produced by [`ConfiguredTriGParser`](struct.ConfiguredTriGParser.html),
+/// depending on its `strict` flag.
+pub enum TriGSource<B: BufRead> {
+    Strict(StrictRioSource<RioTriGParser<B>, TurtleError>),
+    Generalized(GeneralizedRioSource<RioGTriGParser<B>, TurtleError>),
+}
+
+impl<B: BufRead> QuadSource for TriGSource<B> {
+    type Error = TurtleError;
+    type Quad = ByRefTerms;
+
+    fn try_for_some_quad<F, E>(&mut self, f: &mut F) -> StreamResult<bool, TurtleError, E>
+    where
+        F: FnMut(StreamedQuad<Self::Quad>) -> Result<(), E>,
+        E: Error,
+    {
+        match self {
+            TriGSource::Strict(source) => source.try_for_some_quad(f),
+            TriGSource::Generalized(source) => source.try_for_some_quad(f),
+        }
+    }
+}
+
 def_mod_functions_for_bufread_parser!(TriGParser, QuadParser);
 
 // ---------------------------------------------------------------------------------
@@ -38,7 +122,7 @@ mod test {
     use crate::ns::{rdf, xsd};
     use crate::quad::stream::QuadSource;
     use sophia_term::matcher::ANY;
-    use sophia_term::StaticTerm;
+    use sophia_term::{BoxTerm, StaticTerm};
 
     #[test]
     fn test_simple_trig_string() -> std::result::Result<(), Box<dyn std::error::Error>> {
@@ -88,4 +172,67 @@ mod test {
             .is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_trig_counted() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let trig = r#"
+            @prefix : <http://example.org/ns/> .
+
+            :me :name "me".
+            <#g1> {
+                <#me> :knows _:alice.
+            }
+            <#g2> {
+                _:alice a :Person ; :name "Alice".
+            }
+            <#g1> {
+                <#me> :name "me".
+            }
+        "#;
+
+        let mut d = FastDataset::new();
+        let p = TriGParser {
+            base: Some("http://localhost/ex".into()),
+        };
+        let counts = p.parse_str(trig).in_dataset_counted(&mut d)?;
+        let g1 = BoxTerm::new_iri("http://localhost/ex#g1").unwrap();
+        let g2 = BoxTerm::new_iri("http://localhost/ex#g2").unwrap();
+        assert_eq!(counts.len(), 3);
+        assert_eq!(counts[&None], 1);
+        assert_eq!(counts[&Some(g1)], 2);
+        assert_eq!(counts[&Some(g2)], 2);
+        assert_eq!(d.quads().count(), 5);
+        Ok(())
+    }
+
+    #[test]
+    fn test_generalized_trig() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        use crate::quad::Quad;
+
+        let trig = r#"
+            @prefix : <http://example.org/ns/> .
+
+            <#g1> {
+                "Alice" _:rel :bob.
+                :bob :knows :alice.
+            }
+        "#;
+
+        let strict = TriGParser::default();
+        assert!(strict
+            .parse_str(trig)
+            .in_dataset(&mut FastDataset::new())
+            .is_err());
+
+        let mut lenient = ConfiguredTriGParser::from(TriGParser {
+            base: Some("http://localhost/ex".into()),
+        });
+        lenient.set_strict(false);
+        let mut generalized = vec![];
+        lenient.parse_str(trig).for_each_quad(|q| {
+            generalized.push(q.is_generalized());
+        })?;
+        assert_eq!(generalized, vec![true, false]);
+        Ok(())
+    }
 }
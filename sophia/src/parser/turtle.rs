@@ -6,25 +6,84 @@ use rio_api::parser::ParseError;
 use rio_turtle::{TurtleError, TurtleParser as RioTurtleParser};
 
 use crate::parser::rio_common::*;
-use crate::parser::{Location, TripleParser, WithLocation};
+use crate::parser::{Location, TripleParser, WithLocation, DEFAULT_MAX_DEPTH};
+
+mod _events;
+pub use self::_events::*;
 
 /// Turtle parser based on RIO.
+///
+/// Documents nesting blank node property lists (`[ ]`) and collections (`( )`)
+/// deeper than [`DEFAULT_MAX_DEPTH`](../constant.DEFAULT_MAX_DEPTH.html) are rejected;
+/// see [`ConfiguredTurtleParser`](struct.ConfiguredTurtleParser.html) to change that limit.
 #[derive(Clone, Debug, Default)]
 pub struct TurtleParser {
     pub base: Option<String>,
 }
 
 impl<B: BufRead> TripleParser<B> for TurtleParser {
-    type Source = StrictRioSource<RioTurtleParser<B>, TurtleError>;
+    type Source = StrictRioSource<RioTurtleParser<DepthLimitedRead<B>>, TurtleError>;
     fn parse(&self, data: B) -> Self::Source {
-        let base: &str = match &self.base {
-            Some(base) => &base,
-            None => "x-no-base:///",
-        };
-        StrictRioSource::from(RioTurtleParser::new(data, base))
+        parse(&self.base, DEFAULT_MAX_DEPTH, data)
+    }
+}
+
+/// Turtle parser based on RIO, with non-default options.
+#[derive(Clone, Debug)]
+pub struct ConfiguredTurtleParser {
+    base: Option<String>,
+    max_depth: usize,
+}
+
+impl Default for ConfiguredTurtleParser {
+    fn default() -> Self {
+        ConfiguredTurtleParser {
+            base: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
+}
+
+impl From<TurtleParser> for ConfiguredTurtleParser {
+    fn from(parser: TurtleParser) -> Self {
+        ConfiguredTurtleParser {
+            base: parser.base,
+            ..Self::default()
+        }
+    }
+}
+
+impl ConfiguredTurtleParser {
+    /// Set the maximum nesting depth of blank node property lists (`[ ]`) and collections (`( )`);
+    /// parsing fails on documents exceeding it.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`](../constant.DEFAULT_MAX_DEPTH.html).
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
     }
 }
 
+impl<B: BufRead> TripleParser<B> for ConfiguredTurtleParser {
+    type Source = StrictRioSource<RioTurtleParser<DepthLimitedRead<B>>, TurtleError>;
+    fn parse(&self, data: B) -> Self::Source {
+        parse(&self.base, self.max_depth, data)
+    }
+}
+
+fn parse<B: BufRead>(
+    base: &Option<String>,
+    max_depth: usize,
+    data: B,
+) -> StrictRioSource<RioTurtleParser<DepthLimitedRead<B>>, TurtleError> {
+    let base: &str = match base {
+        Some(base) => base,
+        None => "x-no-base:///",
+    };
+    let data = DepthLimitedRead::new(data, max_depth);
+    StrictRioSource::from(RioTurtleParser::new(data, base))
+}
+
 impl WithLocation for TurtleError {
     fn location(&self) -> Location {
         match self.textual_position() {
@@ -90,4 +149,22 @@ mod test {
             .is_some());
         Ok(())
     }
+
+    #[test]
+    fn test_max_depth() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let mut p = ConfiguredTurtleParser::default();
+        p.set_max_depth(3);
+        let nested_3 = "<s> <p> [ <p> ( [ <p> \"[(\" ] ) ] .";
+        let mut g = FastGraph::new();
+        assert_eq!(p.parse_str(nested_3).in_graph(&mut g)?, 5);
+
+        let nested_4 = "<s> <p> [ <p> ( [ <p> ( <o> ) ] ) ] .";
+        let mut g = FastGraph::new();
+        assert!(p.parse_str(nested_4).in_graph(&mut g).is_err());
+        assert!(TurtleParser::default()
+            .parse_str(nested_4)
+            .in_graph(&mut g)
+            .is_ok());
+        Ok(())
+    }
 }
@@ -0,0 +1,315 @@
+//! Event-based Turtle parsing,
+//! reporting directives and comments along with triples.
+
+use std::collections::VecDeque;
+
+use rio_api::parser::TriplesParser;
+use rio_turtle::{TurtleError, TurtleParser as RioTurtleParser};
+use sophia_term::BoxTerm;
+
+use crate::parser::rio_common::{rio2boxterm, DepthLimitedRead};
+
+use super::{ConfiguredTurtleParser, TurtleParser};
+use crate::parser::DEFAULT_MAX_DEPTH;
+
+/// A structural event of a Turtle document.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Event {
+    /// A prefix declaration (`@prefix` or `PREFIX`),
+    /// with the prefix (without the colon) and the IRI as written in the document.
+    Prefix(String, String),
+    /// A base declaration (`@base` or `BASE`), with the IRI as written in the document.
+    Base(String),
+    /// A triple, with IRIs resolved and blank nodes labelled as by [`TurtleParser`].
+    ///
+    /// [`TurtleParser`]: struct.TurtleParser.html
+    Triple([BoxTerm; 3]),
+    /// A comment, without the leading `#` nor the line terminator.
+    Comment(String),
+}
+
+/// An iterator over the [`Event`]s of a Turtle document, in document order.
+///
+/// Directives are always reported before the triples that depend on them.
+/// Only comments occurring *between* statements are reported;
+/// comments inside a statement are skipped, as they can not be ordered w.r.t. its triples.
+///
+/// After an error is yielded, the iterator is exhausted.
+///
+/// [`Event`]: enum.Event.html
+pub struct TurtleEvents<'a> {
+    txt: &'a str,
+    pos: usize,
+    parser: Option<RioTurtleParser<DepthLimitedRead<&'a [u8]>>>,
+    error: Option<TurtleError>,
+    pending: VecDeque<Event>,
+}
+
+impl TurtleParser {
+    /// Parse `txt` into a sequence of [`Event`]s.
+    ///
+    /// [`Event`]: enum.Event.html
+    pub fn parse_events<'a>(&self, txt: &'a str) -> TurtleEvents<'a> {
+        TurtleEvents::new(&self.base, DEFAULT_MAX_DEPTH, txt)
+    }
+}
+
+impl ConfiguredTurtleParser {
+    /// Parse `txt` into a sequence of [`Event`]s.
+    ///
+    /// [`Event`]: enum.Event.html
+    pub fn parse_events<'a>(&self, txt: &'a str) -> TurtleEvents<'a> {
+        TurtleEvents::new(&self.base, self.max_depth, txt)
+    }
+}
+
+impl<'a> TurtleEvents<'a> {
+    fn new(base: &Option<String>, max_depth: usize, txt: &'a str) -> Self {
+        let base: &str = match base {
+            Some(base) => base,
+            None => "x-no-base:///",
+        };
+        let data = DepthLimitedRead::new(txt.as_bytes(), max_depth);
+        let (parser, error) = match RioTurtleParser::new(data, base) {
+            Ok(parser) => (Some(parser), None),
+            Err(error) => (None, Some(error)),
+        };
+        TurtleEvents {
+            txt,
+            pos: 0,
+            parser,
+            error,
+            pending: VecDeque::new(),
+        }
+    }
+}
+
+impl<'a> Iterator for TurtleEvents<'a> {
+    type Item = Result<Event, TurtleError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            if let Some(event) = self.pending.pop_front() {
+                return Some(Ok(event));
+            }
+            if let Some(error) = self.error.take() {
+                return Some(Err(error));
+            }
+            let parser = self.parser.as_mut()?;
+            let bytes = self.txt.as_bytes();
+            self.pos = skip_whitespace(bytes, self.pos);
+            if self.pos == bytes.len() {
+                self.parser = None;
+                return None;
+            }
+            if bytes[self.pos] == b'#' {
+                let end = line_end(bytes, self.pos);
+                let comment = self.txt[self.pos + 1..end].to_string();
+                self.pos = end;
+                return Some(Ok(Event::Comment(comment)));
+            }
+
+            // let RIO parse exactly the statement delimited by our scanner
+            let end = statement_end(bytes, self.pos);
+            let directive = directive(&self.txt[self.pos..end]);
+            let pending = &mut self.pending;
+            let res = parser.parse_step(&mut |t| -> Result<(), TurtleError> {
+                pending.push_back(Event::Triple([
+                    rio2boxterm(t.subject.into()),
+                    rio2boxterm(t.predicate.into()),
+                    rio2boxterm(t.object.into()),
+                ]));
+                Ok(())
+            });
+            if let Err(error) = res {
+                self.parser = None;
+                self.pending.clear();
+                return Some(Err(error));
+            }
+            // directives produce no triple, so this preserves document order
+            self.pending.extend(directive);
+            self.pos = end;
+        }
+    }
+}
+
+/// Whether `txt` starts with `keyword`, ignoring ASCII case (as RIO does).
+fn starts_with_keyword(txt: &[u8], keyword: &[u8]) -> bool {
+    txt.len() >= keyword.len() && txt[..keyword.len()].eq_ignore_ascii_case(keyword)
+}
+
+/// Whether `b` may occur around a `.` inside a prefixed name, a blank node label or a number.
+fn is_name_byte(b: u8) -> bool {
+    b.is_ascii_alphanumeric() || b"_-:%\\".contains(&b) || b >= 0x80
+}
+
+fn skip_whitespace(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && b" \t\r\n".contains(&bytes[i]) {
+        i += 1;
+    }
+    i
+}
+
+fn line_end(bytes: &[u8], mut i: usize) -> usize {
+    while i < bytes.len() && bytes[i] != b'\r' && bytes[i] != b'\n' {
+        i += 1;
+    }
+    i
+}
+
+/// Return the index just after the string literal starting at `i`.
+fn string_end(bytes: &[u8], i: usize) -> usize {
+    let quote = bytes[i];
+    let triple_quote = [quote; 3];
+    let (mut j, long) = if bytes[i..].starts_with(&triple_quote) {
+        (i + 3, true)
+    } else {
+        (i + 1, false)
+    };
+    while j < bytes.len() {
+        match bytes[j] {
+            b'\\' => j += 2,
+            b'\n' | b'\r' if !long => return j,
+            b if b == quote && (!long || bytes[j..].starts_with(&triple_quote)) => {
+                return j + if long { 3 } else { 1 };
+            }
+            _ => j += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Return the index just after the statement starting at `start`.
+///
+/// If the statement is not terminated, this is the end of `bytes`.
+fn statement_end(bytes: &[u8], start: usize) -> usize {
+    let stmt = &bytes[start..];
+    let sparql_directive =
+        starts_with_keyword(stmt, b"BASE") || starts_with_keyword(stmt, b"PREFIX");
+    let mut i = start;
+    while i < bytes.len() {
+        match bytes[i] {
+            b'<' => {
+                while i < bytes.len() && bytes[i] != b'>' {
+                    i += 1;
+                }
+                i += 1;
+                if sparql_directive {
+                    return i.min(bytes.len());
+                }
+            }
+            b'"' | b'\'' => i = string_end(bytes, i),
+            b'#' => i = line_end(bytes, i),
+            b'\\' => i += 2,
+            b'.' => {
+                let prev = if i > start { Some(bytes[i - 1]) } else { None };
+                let next = bytes.get(i + 1).cloned();
+                let in_token = match next {
+                    Some(n) if n.is_ascii_digit() => true,
+                    Some(n) if is_name_byte(n) => prev.is_some_and(is_name_byte),
+                    _ => false,
+                };
+                i += 1;
+                if !in_token {
+                    return i;
+                }
+            }
+            _ => i += 1,
+        }
+    }
+    bytes.len()
+}
+
+/// Extract the [`Event`] corresponding to `stmt`, if it is a directive.
+///
+/// `stmt` is assumed to have been accepted by RIO.
+fn directive(stmt: &str) -> Option<Event> {
+    let keyword = stmt.trim_start_matches('@');
+    if starts_with_keyword(keyword.as_bytes(), b"BASE") {
+        Some(Event::Base(iri_in(&keyword[4..])?))
+    } else if starts_with_keyword(keyword.as_bytes(), b"PREFIX") {
+        let rest = &keyword[6..];
+        let colon = rest.find(':')?;
+        let prefix = rest[..colon].trim().to_string();
+        Some(Event::Prefix(prefix, iri_in(&rest[colon + 1..])?))
+    } else {
+        None
+    }
+}
+
+/// Extract the first IRI reference (between `<` and `>`) in `txt`.
+fn iri_in(txt: &str) -> Option<String> {
+    let start = txt.find('<')? + 1;
+    let end = start + txt[start..].find('>')?;
+    Some(txt[start..end].to_string())
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::xsd;
+    use sophia_term::StaticTerm;
+
+    #[test]
+    fn test_events_in_document_order() -> std::result::Result<(), Box<dyn std::error::Error>> {
+        let turtle = r#"# leading comment
+            @prefix : <http://example.org/ns/> .
+            BASE <http://example.org/>
+            # about alice
+            <alice> :name "Alice. # not a comment" ; :age 4.2 .
+            PREFIX foaf: <http://xmlns.com/foaf/0.1/>
+            <alice> foaf:knows <bob>, :bob.
+        "#;
+
+        let events: Vec<Event> = TurtleParser::default()
+            .parse_events(turtle)
+            .collect::<Result<_, _>>()?;
+
+        let iri = |txt: &'static str| StaticTerm::new_iri(txt).map(|t| BoxTerm::from(&t));
+        let alice = iri("http://example.org/alice")?;
+        let expected = vec![
+            Event::Comment(" leading comment".to_string()),
+            Event::Prefix("".to_string(), "http://example.org/ns/".to_string()),
+            Event::Base("http://example.org/".to_string()),
+            Event::Comment(" about alice".to_string()),
+            Event::Triple([
+                alice.clone(),
+                iri("http://example.org/ns/name")?,
+                BoxTerm::from(&StaticTerm::new_literal_dt(
+                    "Alice. # not a comment",
+                    xsd::string,
+                )?),
+            ]),
+            Event::Triple([
+                alice.clone(),
+                iri("http://example.org/ns/age")?,
+                BoxTerm::from(&StaticTerm::new_literal_dt("4.2", xsd::decimal)?),
+            ]),
+            Event::Prefix("foaf".to_string(), "http://xmlns.com/foaf/0.1/".to_string()),
+            Event::Triple([
+                alice.clone(),
+                iri("http://xmlns.com/foaf/0.1/knows")?,
+                iri("http://example.org/bob")?,
+            ]),
+            Event::Triple([
+                alice,
+                iri("http://xmlns.com/foaf/0.1/knows")?,
+                iri("http://example.org/ns/bob")?,
+            ]),
+        ];
+        assert_eq!(events, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn test_events_stop_after_error() {
+        let mut events = TurtleParser::default().parse_events("@prefix : <tag:> .\n:a :b .\n");
+        assert!(matches!(events.next(), Some(Ok(Event::Prefix(..)))));
+        assert!(matches!(events.next(), Some(Err(_))));
+        assert!(events.next().is_none());
+    }
+}
@@ -21,7 +21,7 @@ use url::Url;
 use crate::ns::rdf;
 use crate::ns::xsd;
 use crate::ns::Namespace;
-use crate::parser::{LocatableError, TripleParser};
+use crate::parser::{LocatableError, TripleParser, DEFAULT_MAX_DEPTH};
 use sophia_term::factory::RcTermFactory;
 use sophia_term::factory::TermFactory;
 use sophia_term::iri::is_absolute_iri_ref;
@@ -88,27 +88,51 @@ mod xmlname {
 }
 
 /// RDF/XML parser.
-#[derive(Clone, Debug, Default)]
+#[derive(Clone, Debug)]
 pub struct RdfXmlParser {
     base: Option<Url>,
+    max_depth: usize,
+}
+
+impl Default for RdfXmlParser {
+    fn default() -> Self {
+        Self {
+            base: None,
+            max_depth: DEFAULT_MAX_DEPTH,
+        }
+    }
 }
 
 impl RdfXmlParser {
     pub fn with_base(base: &str) -> Result<Self> {
         match Url::parse(base) {
-            Ok(url) => Ok(Self { base: Some(url) }),
+            Ok(url) => Ok(Self {
+                base: Some(url),
+                ..Self::default()
+            }),
             Err(_) => Err(RdfError::InvalidBaseIri(base.to_owned()).into()),
         }
     }
+
+    /// Set the maximum nesting depth of XML elements;
+    /// parsing fails on documents exceeding it.
+    ///
+    /// Defaults to [`DEFAULT_MAX_DEPTH`](../constant.DEFAULT_MAX_DEPTH.html).
+    pub fn set_max_depth(&mut self, max_depth: usize) -> &mut Self {
+        self.max_depth = max_depth;
+        self
+    }
 }
 
 impl<B: BufRead> TripleParser<B> for RdfXmlParser {
     type Source = RdfXmlSource<B, RcTermFactory>;
     fn parse(&self, data: B) -> Self::Source {
-        match &self.base {
+        let mut source = match &self.base {
             Some(base) => RdfXmlSource::with_base(Reader::from_reader(data), base.clone()),
             None => RdfXmlSource::new(Reader::from_reader(data)),
-        }
+        };
+        source.handler.max_depth = self.max_depth;
+        source
     }
 }
 
@@ -195,6 +219,8 @@ impl<B: BufRead> Deref for XmlReader<B> {
 pub struct RdfXmlSource<B: BufRead, F: TermFactory> {
     handler: XmlHandler<B, F>,
     buffer: Vec<u8>,
+    /// Set when the maximum depth has been exceeded, as parsing can not resume after that.
+    aborted: bool,
 }
 
 impl<B, F> RdfXmlSource<B, F>
@@ -207,6 +233,7 @@ where
         Self {
             handler: XmlHandler::new(reader),
             buffer: Vec::with_capacity(DEFAULT_BUFFER_SIZE),
+            aborted: false,
         }
     }
 
@@ -215,6 +242,7 @@ where
         Self {
             handler: XmlHandler::with_base(reader, base),
             buffer: Vec::with_capacity(DEFAULT_BUFFER_SIZE),
+            aborted: false,
         }
     }
 }
@@ -231,6 +259,9 @@ where
             if let Some(res) = self.handler.triples.pop_front() {
                 return Some(res);
             }
+            if self.aborted {
+                return None;
+            }
 
             //
             self.buffer.clear();
@@ -238,6 +269,13 @@ where
             // Then process the next event to maybe produce triples
             match self.handler.reader.read_event(&mut self.buffer) {
                 Ok(Event::Eof) => return None,
+                Ok(Event::Start(_)) | Ok(Event::Empty(_))
+                    if self.handler.scopes.len() > self.handler.max_depth =>
+                {
+                    self.aborted = true;
+                    let e = RdfError::MaxDepthExceeded(self.handler.max_depth);
+                    return Some(Err(e.locate_with(&self.handler.reader)));
+                }
                 Ok(Event::Start(s)) => self.handler.element_start(&s),
                 Ok(Event::Empty(e)) => self.handler.element_empty(&e),
                 Ok(Event::End(e)) => self.handler.element_end(&e),
@@ -1069,4 +1107,29 @@ mod test {
         r#"<http://www.w3.org/TR/rdf-syntax-grammar> <http://example.org/stuff/1.0/editor> <http://example.org/user/dave-beckett> .
         "#
     }
+
+    #[test]
+    fn max_depth() {
+        let xml = r#"<?xml version="1.0"?>
+            <rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"
+                     xmlns:ex="http://example.org/">
+              <rdf:Description rdf:about="http://example.org/a">
+                <ex:p>
+                  <rdf:Description>
+                    <ex:p rdf:resource="http://example.org/b"/>
+                  </rdf:Description>
+                </ex:p>
+              </rdf:Description>
+            </rdf:RDF>
+        "#;
+
+        let mut p = crate::parser::xml::RdfXmlParser::default();
+        p.set_max_depth(5);
+        let mut g = TestGraph::new();
+        assert_eq!(p.parse_str(xml).in_graph(&mut g).unwrap(), 2);
+
+        p.set_max_depth(4);
+        let mut g = TestGraph::new();
+        assert!(p.parse_str(xml).in_graph(&mut g).is_err());
+    }
 }
@@ -84,6 +84,8 @@ pub enum RdfError {
     InvalidUrl(#[from] url::ParseError),
     #[error("The given base IRI `{0}` is not a valid IRI")]
     InvalidBaseIri(String),
+    #[error("Elements are nested deeper than the maximum depth ({0})")]
+    MaxDepthExceeded(usize),
 }
 
 impl<'a, BR> LocatableError<&'a Reader<BR>> for RdfError
@@ -39,6 +39,8 @@ pub(super) struct XmlHandler<B: BufRead, F: TermFactory> {
     pub(super) bnodes: AtomicUsize,
     //
     pub(super) ids: HashSet<Term<F::TermData>>,
+    /// The maximum nesting depth of XML elements.
+    pub(super) max_depth: usize,
     /// The current state of the parser.
     state: Vec<ParsingState>,
 }
@@ -142,7 +144,7 @@ where
     fn new_bnode(&self) -> Term<F::TermData> {
         self.factory
             .borrow_mut()
-            .bnode(&format!("n{}", self.bnodes.fetch_add(1, Ordering::Relaxed)))
+            .bnode(format!("n{}", self.bnodes.fetch_add(1, Ordering::Relaxed)))
             .expect("always produces a correct BNode")
     }
 
@@ -151,7 +153,7 @@ where
         if xmlname::is_valid_xmlname(id) {
             self.factory
                 .borrow_mut()
-                .bnode(&format!("o{}", id))
+                .bnode(format!("o{}", id))
                 .map_err(|e| RdfError::from(e).locate_with(&self.reader))
         } else {
             Err(RdfError::InvalidXmlName(id.to_owned())).locate_err_with(&self.reader)
@@ -210,6 +212,7 @@ where
             bnodes: AtomicUsize::new(0),
             state: vec![ParsingState::Node],
             ids: HashSet::new(),
+            max_depth: DEFAULT_MAX_DEPTH,
         }
     }
 
@@ -4,6 +4,7 @@
 //!
 //! They are the individual statements of an RDF `dataset`(../dataset/index.html).
 
+use crate::statement::QuadAsStatement;
 use crate::triple::*;
 use sophia_term::*;
 
@@ -23,6 +24,34 @@ pub trait Quad {
     /// The (optional) graph name
     fn g(&self) -> Option<&Term<<Self as Quad>::TermData>>;
 
+    /// Whether this quad is only valid in the *generalized* RDF model,
+    /// i.e. if its subject is neither an IRI nor a blank node,
+    /// if its predicate is not an IRI,
+    /// if its object is a variable,
+    /// or if its graph name is neither an IRI nor a blank node.
+    fn is_generalized(&self) -> bool {
+        !matches!(self.s(), Term::Iri(_) | Term::BNode(_))
+            || !matches!(self.p(), Term::Iri(_))
+            || matches!(self.o(), Term::Variable(_))
+            || !matches!(self.g(), None | Some(Term::Iri(_)) | Some(Term::BNode(_)))
+    }
+
+    /// Return an owned copy of this quad,
+    /// or `None` if this quad is [generalized](#method.is_generalized).
+    ///
+    /// This is useful to sanitize the output of a lenient parser
+    /// before inserting it into a dataset supporting only strict RDF.
+    fn to_standard(&self) -> Option<([BoxTerm; 3], Option<BoxTerm>)> {
+        if self.is_generalized() {
+            None
+        } else {
+            Some((
+                [self.s().into(), self.p().into(), self.o().into()],
+                self.g().map(BoxTerm::from),
+            ))
+        }
+    }
+
     /// [`Triple`](../triple/trait.Triple.html) adapter owning this quad.
     fn as_triple(self) -> QuadAsTriple<Self>
     where
@@ -30,6 +59,14 @@ pub trait Quad {
     {
         QuadAsTriple(self)
     }
+
+    /// [`Statement`](../statement/trait.Statement.html) adapter owning this quad.
+    fn into_statement(self) -> QuadAsStatement<Self>
+    where
+        Self: Sized,
+    {
+        QuadAsStatement(self)
+    }
 }
 
 impl<T> Quad for [Term<T>; 4]
@@ -178,5 +215,19 @@ impl<Q: Quad> Triple for QuadAsTriple<Q> {
 
 #[cfg(test)]
 mod test {
-    // Nothing really worth testing here
+    use super::*;
+
+    #[test]
+    fn test_to_standard() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let lit = BoxTerm::from("lit".to_string());
+        let q = ([s.clone(), s.clone(), lit.clone()], Some(s.clone()));
+        assert_eq!(q.to_standard(), Some(q.clone()));
+        let q = ([s.clone(), s.clone(), lit.clone()], None::<BoxTerm>);
+        assert_eq!(q.to_standard(), Some(q.clone()));
+        let q = [&s, &s, &s, &lit];
+        assert_eq!(q.to_standard(), None);
+        let q = ([&lit, &s, &s], None::<&BoxTerm>);
+        assert_eq!(q.to_standard(), None);
+    }
 }
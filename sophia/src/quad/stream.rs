@@ -14,12 +14,14 @@
 //! [`try_for_each_quad`]: ./trait.QuadSource.html#method.try_for_each_quad
 //! [`triple::stream`]: ../../triple/stream/index.html
 
+use std::collections::{HashMap, VecDeque};
 use std::error::Error;
 
 use crate::dataset::*;
 use crate::quad::streaming_mode::*;
 use crate::quad::*;
 use crate::triple::stream::{SinkError, SourceError, StreamError, StreamResult};
+use sophia_term::BoxTerm;
 
 mod _filter;
 pub use _filter::*;
@@ -105,6 +107,52 @@ pub trait QuadSource {
     {
         dataset.insert_all(self)
     }
+    /// Insert all quads from this source into the given [dataset](../../dataset/trait.MutableDataset.html),
+    /// and return the number of quads actually inserted in each graph.
+    ///
+    /// The default graph is keyed by `None`.
+    /// Graphs where no quad was actually inserted do not appear in the result.
+    ///
+    /// Stop on the first error (in the source or in the dataset).
+    fn in_dataset_counted<D: MutableDataset>(
+        &mut self,
+        dataset: &mut D,
+    ) -> StreamResult<
+        HashMap<Option<BoxTerm>, usize>,
+        Self::Error,
+        <D as MutableDataset>::MutationError,
+    >
+    where
+        Self: Sized,
+    {
+        let mut counts = HashMap::new();
+        self.try_for_each_quad(|q| -> MDResult<D, ()> {
+            if dataset.insert(q.s(), q.p(), q.o(), q.g())? {
+                *counts.entry(q.g().map(BoxTerm::from)).or_insert(0) += 1;
+            }
+            Ok(())
+        })
+        .and(Ok(counts))
+    }
+    /// Collect all quads from this source into a vector of owned quads.
+    ///
+    /// The default graph is represented by `None`.
+    /// Stop on the first error.
+    #[inline]
+    #[allow(clippy::type_complexity)]
+    fn collect_quads(mut self) -> Result<Vec<([BoxTerm; 3], Option<BoxTerm>)>, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut v = Vec::new();
+        self.for_each_quad(|q| {
+            v.push((
+                [q.s().into(), q.p().into(), q.o().into()],
+                q.g().map(BoxTerm::from),
+            ))
+        })?;
+        Ok(v)
+    }
     /// Creates a quad source which uses a closure to determine if a quad should be yielded.
     #[inline]
     fn filter_quads<F>(self, filter: F) -> FilterSource<Self, F>
@@ -140,5 +188,55 @@ pub trait QuadSource {
     }
 }
 
+/// Whether the quad sources `a` and `b` yield the same quads, in the same order.
+///
+/// Quads are compared position by position, as they are produced,
+/// so neither source is ever loaded in memory.
+/// This makes this function suitable for comparing very large sources,
+/// such as two exports of the same dataset.
+///
+/// **Precondition**: both sources must be *sorted* in the same order
+/// (and contain no duplicate, or duplicates in the same positions).
+/// Otherwise, sources yielding the same quads in a different order
+/// are reported as different.
+///
+/// Stop on the first difference, or on the first error in either source.
+pub fn streams_equal<A, B>(a: &mut A, b: &mut B) -> Result<bool, A::Error>
+where
+    A: QuadSource,
+    B: QuadSource<Error = A::Error>,
+{
+    let mut buffer_a = VecDeque::new();
+    let mut buffer_b = VecDeque::new();
+    loop {
+        refill(a, &mut buffer_a)?;
+        refill(b, &mut buffer_b)?;
+        match (buffer_a.pop_front(), buffer_b.pop_front()) {
+            (None, None) => return Ok(true),
+            (Some(qa), Some(qb)) if qa == qb => {}
+            _ => return Ok(false),
+        }
+    }
+}
+
+/// If `buffer` is empty, fill it with the next quad(s) of `source`, if any.
+fn refill<S>(
+    source: &mut S,
+    buffer: &mut VecDeque<([BoxTerm; 3], Option<BoxTerm>)>,
+) -> Result<(), S::Error>
+where
+    S: QuadSource,
+{
+    while buffer.is_empty()
+        && source.for_some_quad(&mut |q| {
+            buffer.push_back((
+                [q.s().into(), q.p().into(), q.o().into()],
+                q.g().map(BoxTerm::from),
+            ))
+        })?
+    {}
+    Ok(())
+}
+
 #[cfg(test)]
 mod test;
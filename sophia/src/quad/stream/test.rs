@@ -108,6 +108,42 @@ fn in_dataset() {
     }
 }
 
+#[test]
+fn collect_quads() {
+    use crate::parser::trig;
+
+    let trig = r#"
+        @prefix ex: <http://example.org/>.
+        ex:alice a ex:Person.
+        GRAPH ex:g {
+            ex:bob a ex:Person; ex:name "Bob".
+        }
+    "#;
+    let v = trig::parse_str(trig).collect_quads().unwrap();
+    assert_eq!(v.len(), 3);
+    assert!(v[0].1.is_none());
+    let g = StaticTerm::new_iri("http://example.org/g").unwrap();
+    assert_eq!(v[2].1, Some(BoxTerm::from(&g)));
+
+    assert!(trig::parse_str("GRAPH <tag:g> {").collect_quads().is_err());
+}
+
+#[test]
+fn streams_equal() {
+    let mut d1 = make_dataset();
+    d1.sort();
+    let d2 = d1.clone();
+    assert!(super::streams_equal(&mut d1.quads(), &mut d2.quads()).unwrap());
+
+    let d3 = make_mapped_dataset();
+    assert!(!super::streams_equal(&mut d1.quads(), &mut d3.quads()).unwrap());
+
+    // one stream is a prefix of the other
+    let d4 = d1[..d1.len() - 1].to_vec();
+    assert!(!super::streams_equal(&mut d1.quads(), &mut d4.quads()).unwrap());
+    assert!(!super::streams_equal(&mut d4.quads(), &mut d1.quads()).unwrap());
+}
+
 #[test]
 fn filter_quads() {
     let d = make_dataset();
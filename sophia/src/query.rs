@@ -13,6 +13,10 @@ use sophia_term::*;
 use crate::graph::*;
 use crate::triple::*;
 
+pub mod aggregate;
+pub mod join;
+pub mod text;
+
 /// A map associating variable names to [`term`](../term/enum.Term.html)s.
 pub type BindingMap = HashMap<String, RcTerm>;
 
@@ -0,0 +1,68 @@
+//! Aggregate functions over the solutions of a query.
+
+use std::collections::HashSet;
+
+use sophia_term::RcTerm;
+
+use crate::graph::*;
+use crate::query::Query;
+
+/// Count the distinct terms bound to variable `var`
+/// in the solutions of the basic graph pattern `patterns` against graph `g`
+/// (similar to SPARQL's `COUNT(DISTINCT ?var)`).
+///
+/// Terms are considered distinct according to the `Eq` implementation of
+/// [`Term`](../../term/enum.Term.html).
+/// Solutions where `var` is not bound are ignored.
+pub fn count_distinct<G>(g: &G, patterns: &[[RcTerm; 3]], var: &str) -> GResult<G, usize>
+where
+    G: Graph,
+{
+    let mut query = Query::Triples(patterns.to_vec());
+    let mut distinct = HashSet::new();
+    for bindings in query.process(g) {
+        if let Some(t) = bindings?.remove(var) {
+            distinct.insert(t);
+        }
+    }
+    Ok(distinct.len())
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::inmem::FastGraph;
+    use crate::ns::{rdf, Namespace};
+
+    #[test]
+    fn test_count_distinct() {
+        let schema = Namespace::new("http://schema.org/").unwrap();
+        let s_person = RcTerm::from(&schema.get("Person").unwrap());
+        let s_event = RcTerm::from(&schema.get("Event").unwrap());
+        let s_name = RcTerm::from(&schema.get("name").unwrap());
+        let foo = RcTerm::from("foo".to_string());
+
+        let mut g = FastGraph::new();
+        for (i, typ) in [&s_person, &s_person, &s_event, &s_person]
+            .iter()
+            .enumerate()
+        {
+            let x = RcTerm::new_iri(format!("http://example.org/x{}", i)).unwrap();
+            g.insert(&x, &rdf::type_, *typ).unwrap();
+            g.insert(&x, &s_name, &foo).unwrap();
+        }
+        let extra = RcTerm::new_iri("http://example.org/x0").unwrap();
+        g.insert(&extra, &rdf::type_, &s_event).unwrap();
+
+        let x = RcTerm::new_variable("x").unwrap();
+        let typ = RcTerm::new_variable("type").unwrap();
+        let patterns = [
+            [x.clone(), RcTerm::from(&rdf::type_), typ.clone()],
+            [x.clone(), s_name.clone(), foo],
+        ];
+        assert_eq!(count_distinct(&g, &patterns, "type").unwrap(), 2);
+        assert_eq!(count_distinct(&g, &patterns, "x").unwrap(), 4);
+        assert_eq!(count_distinct(&g, &patterns, "y").unwrap(), 0);
+        assert_eq!(count_distinct(&g, &patterns[1..], "x").unwrap(), 4);
+    }
+}
@@ -0,0 +1,212 @@
+//! Joins between the solutions of triple patterns.
+
+use std::cmp::Ordering;
+
+use sophia_term::*;
+
+use crate::graph::inmem::SortedVecGraph;
+use crate::query::BindingMap;
+use crate::triple::nt_key;
+
+/// Evaluate the join of pattern `lp` over graph `left` with pattern `rp` over graph `right`.
+///
+/// If the first variable shared by both patterns
+/// is in the leading position of the order of its graph
+/// (e.g. in subject position for a graph sorted in
+/// [`SPO`](../../triple/enum.TripleOrder.html#variant.SPO) order),
+/// the join is computed by a linear merge of the solutions of both patterns.
+/// Otherwise, this function falls back to a nested-loop join,
+/// which gives the same results, but in quadratic time.
+///
+/// The solutions are returned in no particular order.
+pub fn merge_join(
+    left: &SortedVecGraph,
+    lp: &[RcTerm; 3],
+    right: &SortedVecGraph,
+    rp: &[RcTerm; 3],
+) -> Vec<BindingMap> {
+    let lsols = solutions(left, lp);
+    let rsols = solutions(right, rp);
+    let shared = lp.iter().position(|t| match t {
+        Term::Variable(_) => var_position(rp, t).is_some(),
+        _ => false,
+    });
+    match shared {
+        Some(lpos)
+            if lpos == left.order().positions()[0]
+                && var_position(rp, &lp[lpos]) == Some(right.order().positions()[0]) =>
+        {
+            let vname = lp[lpos].value();
+            sorted_merge_join(&lsols, &rsols, &vname)
+        }
+        _ => nested_loop_join(&lsols, &rsols),
+    }
+}
+
+/// The solutions of `pattern` in `g`, in the order of the triples of `g`.
+fn solutions(g: &SortedVecGraph, pattern: &[RcTerm; 3]) -> Vec<BindingMap> {
+    g.as_slice()
+        .iter()
+        .filter_map(|t| {
+            let mut b = BindingMap::new();
+            for (pt, tt) in pattern.iter().zip(t.iter()) {
+                if let Term::Variable(var) = pt {
+                    let vname: &str = var.as_ref();
+                    match b.get(vname) {
+                        Some(bound) if bound != tt => return None,
+                        Some(_) => {}
+                        None => {
+                            b.insert(vname.to_string(), tt.into());
+                        }
+                    }
+                } else if pt != tt {
+                    return None;
+                }
+            }
+            Some(b)
+        })
+        .collect()
+}
+
+/// The position of variable `var` in `pattern`, if any.
+fn var_position(pattern: &[RcTerm; 3], var: &RcTerm) -> Option<usize> {
+    pattern.iter().position(|t| t == var)
+}
+
+/// Merge two lists of solutions, both sorted by the value of variable `vname`.
+fn sorted_merge_join(lsols: &[BindingMap], rsols: &[BindingMap], vname: &str) -> Vec<BindingMap> {
+    let lkeys: Vec<String> = lsols.iter().map(|b| nt_key(&b[vname])).collect();
+    let rkeys: Vec<String> = rsols.iter().map(|b| nt_key(&b[vname])).collect();
+    let mut res = Vec::new();
+    let (mut i, mut j) = (0, 0);
+    while i < lsols.len() && j < rsols.len() {
+        match lkeys[i].cmp(&rkeys[j]) {
+            Ordering::Less => i += 1,
+            Ordering::Greater => j += 1,
+            Ordering::Equal => {
+                let iend = (i..lsols.len())
+                    .find(|k| lkeys[*k] != lkeys[i])
+                    .unwrap_or(lsols.len());
+                let jend = (j..rsols.len())
+                    .find(|k| rkeys[*k] != rkeys[j])
+                    .unwrap_or(rsols.len());
+                res.extend(nested_loop_join(&lsols[i..iend], &rsols[j..jend]));
+                i = iend;
+                j = jend;
+            }
+        }
+    }
+    res
+}
+
+/// Join two lists of solutions by comparing every pair.
+fn nested_loop_join(lsols: &[BindingMap], rsols: &[BindingMap]) -> Vec<BindingMap> {
+    let mut res = Vec::new();
+    for lb in lsols {
+        for rb in rsols {
+            let compatible = lb
+                .iter()
+                .all(|(k, v)| rb.get(k).into_iter().all(|rv| rv == v));
+            if compatible {
+                let mut b = lb.clone();
+                b.extend(rb.iter().map(|(k, v)| (k.clone(), v.clone())));
+                res.push(b);
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::rdf;
+    use crate::triple::TripleOrder;
+
+    fn sorted_keys(solutions: &[BindingMap]) -> Vec<String> {
+        let mut keys: Vec<String> = solutions
+            .iter()
+            .map(|b| {
+                let mut vars: Vec<_> = b.iter().map(|(k, v)| format!("{}={}", k, v)).collect();
+                vars.sort();
+                vars.join(" ")
+            })
+            .collect();
+        keys.sort();
+        keys
+    }
+
+    fn data(order: TripleOrder) -> (SortedVecGraph, SortedVecGraph) {
+        let ex = |s: &str| BoxTerm::new_iri(format!("http://example.org/{}", s)).unwrap();
+        let typ = BoxTerm::from(&rdf::type_);
+        let name = ex("name");
+        let mut types = vec![];
+        let mut names = vec![];
+        for i in 0..10 {
+            let x = ex(&format!("x{}", i));
+            let t = if i % 3 == 0 { "Person" } else { "Robot" };
+            types.push([x.clone(), typ.clone(), ex(t)]);
+            if i % 2 == 0 {
+                let n = BoxTerm::from(format!("n{}", i));
+                names.push([x.clone(), name.clone(), n]);
+                let n = BoxTerm::from(format!("m{}", i));
+                names.push([x, name.clone(), n]);
+            }
+        }
+        (
+            SortedVecGraph::new(types, TripleOrder::SPO),
+            SortedVecGraph::new(names, order),
+        )
+    }
+
+    fn patterns() -> ([RcTerm; 3], [RcTerm; 3]) {
+        let x = RcTerm::new_variable("x").unwrap();
+        (
+            [
+                x.clone(),
+                RcTerm::from(&rdf::type_),
+                RcTerm::new_iri("http://example.org/Person").unwrap(),
+            ],
+            [
+                x,
+                RcTerm::new_iri("http://example.org/name").unwrap(),
+                RcTerm::new_variable("n").unwrap(),
+            ],
+        )
+    }
+
+    #[test]
+    fn merge_equals_nested_loop() {
+        let (left, right) = data(TripleOrder::SPO);
+        let (lp, rp) = patterns();
+        let got = merge_join(&left, &lp, &right, &rp);
+        let expected = nested_loop_join(&solutions(&left, &lp), &solutions(&right, &rp));
+        // x0, x6 are named persons, with 2 names each
+        assert_eq!(got.len(), 4);
+        assert_eq!(sorted_keys(&got), sorted_keys(&expected));
+    }
+
+    #[test]
+    fn fallback_when_not_sorted_on_shared_variable() {
+        let (left, right) = data(TripleOrder::POS);
+        let (lp, rp) = patterns();
+        let got = merge_join(&left, &lp, &right, &rp);
+        let (left2, right2) = data(TripleOrder::SPO);
+        let expected = merge_join(&left2, &lp, &right2, &rp);
+        assert_eq!(got.len(), 4);
+        assert_eq!(sorted_keys(&got), sorted_keys(&expected));
+    }
+
+    #[test]
+    fn no_shared_variable() {
+        let (left, right) = data(TripleOrder::SPO);
+        let (lp, _) = patterns();
+        let rp = [
+            RcTerm::new_variable("y").unwrap(),
+            RcTerm::new_iri("http://example.org/name").unwrap(),
+            RcTerm::new_variable("n").unwrap(),
+        ];
+        // 4 persons (x0, x3, x6, x9) times 10 names
+        assert_eq!(merge_join(&left, &lp, &right, &rp).len(), 40);
+    }
+}
@@ -0,0 +1,71 @@
+//! A simple full-text index over the literals of a graph.
+//!
+//! See [`Graph::build_text_index`](../../graph/trait.Graph.html#method.build_text_index).
+
+use std::collections::{BTreeSet, HashMap};
+
+use sophia_term::*;
+
+/// An inverted index, associating the words of some literals
+/// to the subjects of these literals.
+#[derive(Clone, Debug, Default)]
+pub struct TextIndex {
+    words: HashMap<String, BTreeSet<BoxTerm>>,
+}
+
+impl TextIndex {
+    /// Build an empty index.
+    pub fn new() -> TextIndex {
+        TextIndex::default()
+    }
+
+    /// Associate `subject` to every word of `text`.
+    pub fn insert<T>(&mut self, subject: &Term<T>, text: &str)
+    where
+        T: TermData,
+    {
+        for word in tokenize(text) {
+            self.words.entry(word).or_default().insert(subject.into());
+        }
+    }
+
+    /// Return the subjects associated to *all* the words of `query`,
+    /// in increasing order.
+    ///
+    /// Words are compared case-insensitively,
+    /// and a query without any word matches nothing.
+    pub fn search(&self, query: &str) -> Vec<BoxTerm> {
+        let mut result: Option<BTreeSet<BoxTerm>> = None;
+        for word in tokenize(query) {
+            let subjects = match self.words.get(&word) {
+                None => return vec![],
+                Some(subjects) => subjects,
+            };
+            result = Some(match result {
+                None => subjects.clone(),
+                Some(result) => result.intersection(subjects).cloned().collect(),
+            });
+        }
+        result.unwrap_or_default().into_iter().collect()
+    }
+}
+
+/// Split `text` into words (maximal sequences of alphanumeric characters),
+/// folded to lower case.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|word| !word.is_empty())
+        .map(str::to_lowercase)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        let words: Vec<_> = tokenize("  Hello, World! Ça va?").collect();
+        assert_eq!(words, vec!["hello", "world", "ça", "va"]);
+        assert_eq!(tokenize(" -- ").count(), 0);
+    }
+}
@@ -16,8 +16,12 @@ use crate::graph::*;
 use crate::quad::stream::*;
 use crate::triple::{stream::*, *};
 
+pub mod dot;
 pub mod nq;
 pub mod nt;
+pub mod sparql_update;
+pub mod trig;
+pub mod turtle;
 
 /// A triple serializer writes triples according to a given format.
 pub trait TripleSerializer {
@@ -0,0 +1,277 @@
+//! Serializer for the [DOT] language of [Graphviz],
+//! in order to visualize (small) graphs.
+//!
+//! Subjects and objects are rendered as nodes, and predicates as edge labels.
+//! IRIs are rendered as ellipses, blank nodes as unlabelled circles,
+//! and literals as boxes.
+//! Every occurrence of a literal gets its own node,
+//! so that literals do not connect otherwise unrelated parts of the graph.
+//!
+//! Nodes are given synthetic identifiers (`n0`, `n1`, ...), in the order they are met.
+//!
+//! **Important**:
+//! the methods in this module accepting a [`Write`]
+//! make no effort to minimize the number of write operations.
+//! Hence, in most cased, they should be passed a [`BufWriter`].
+//!
+//! [DOT]: https://graphviz.org/doc/info/lang.html
+//! [Graphviz]: https://graphviz.org/
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+
+use std::collections::HashMap;
+use std::io;
+
+use crate::ns::{xsd, PrefixMap};
+use crate::triple::stream::*;
+use sophia_term::{BoxTerm, Term, TermData};
+
+use super::*;
+
+/// DOT serializer configuration.
+#[derive(Clone, Debug, Default)]
+pub struct DotConfig {
+    prefixes: PrefixMap,
+}
+
+impl DotConfig {
+    /// Abbreviate IRIs in labels as CURIEs, using the given prefix map
+    /// (see [`PrefixMap::compact`](../../ns/struct.PrefixMap.html#method.compact)).
+    ///
+    /// IRIs that can not be abbreviated are displayed in full, between `<` and `>`.
+    pub fn set_prefixes(&mut self, prefixes: PrefixMap) -> &mut Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    fn iri_label(&self, iri: &str) -> String {
+        self.prefixes
+            .compact(iri)
+            .unwrap_or_else(|| format!("<{}>", iri))
+    }
+
+    fn label<T: TermData>(&self, t: &Term<T>) -> String {
+        match t {
+            Term::Iri(iri) => self.iri_label(&iri.value()),
+            Term::Literal(lit) => match lit.lang() {
+                Some(tag) => format!("\"{}\"@{}", lit.txt().as_ref(), tag.as_ref()),
+                None if lit.dt() == xsd::iri::string => format!("\"{}\"", lit.txt().as_ref()),
+                None => format!(
+                    "\"{}\"^^{}",
+                    lit.txt().as_ref(),
+                    self.iri_label(&lit.dt().value())
+                ),
+            },
+            _ => t.to_string(),
+        }
+    }
+}
+
+/// DOT serializer.
+pub struct DotSerializer<W> {
+    config: DotConfig,
+    write: W,
+}
+
+impl<W> DotSerializer<W>
+where
+    W: io::Write,
+{
+    /// Build a new DOT serializer writing to `write`, with the default config.
+    #[inline]
+    pub fn new(write: W) -> DotSerializer<W> {
+        Self::new_with_config(write, DotConfig::default())
+    }
+
+    /// Build a new DOT serializer writing to `write`, with the given config.
+    pub fn new_with_config(write: W, config: DotConfig) -> DotSerializer<W> {
+        DotSerializer { write, config }
+    }
+
+    /// Borrow this serializer's configuration.
+    pub fn config(&self) -> &DotConfig {
+        &self.config
+    }
+}
+
+impl<W> TripleSerializer for DotSerializer<W>
+where
+    W: io::Write,
+{
+    type Error = io::Error;
+
+    /// Serialize all triples from the given source as a single DOT `digraph`.
+    fn serialize_triples<TS>(
+        &mut self,
+        source: &mut TS,
+    ) -> StreamResult<&mut Self, TS::Error, Self::Error>
+    where
+        TS: TripleSource,
+    {
+        let config = &self.config;
+        let w = &mut self.write;
+        writeln!(w, "digraph {{").map_err(SinkError)?;
+        let mut nodes = Nodes::default();
+        source.try_for_each_triple(|t| -> io::Result<()> {
+            let s = nodes.write(w, config, t.s())?;
+            let o = nodes.write(w, config, t.o())?;
+            writeln!(
+                w,
+                "    n{} -> n{} [label={}];",
+                s,
+                o,
+                quoted(&config.label(t.p()))
+            )
+        })?;
+        writeln!(w, "}}").map_err(SinkError)?;
+        Ok(self)
+    }
+}
+
+impl DotSerializer<Vec<u8>> {
+    /// Create a new serializer which targets a `String`.
+    #[inline]
+    pub fn new_stringifier() -> Self {
+        DotSerializer::new(Vec::new())
+    }
+    /// Create a new serializer which targets a `String` with a custom config.
+    #[inline]
+    pub fn new_stringifier_with_config(config: DotConfig) -> Self {
+        DotSerializer::new_with_config(Vec::new(), config)
+    }
+}
+
+impl Stringifier for DotSerializer<Vec<u8>> {
+    fn as_utf8(&self) -> &[u8] {
+        &self.write[..]
+    }
+}
+
+/// The nodes declared so far in a `digraph`.
+#[derive(Default)]
+struct Nodes {
+    ids: HashMap<BoxTerm, usize>,
+    count: usize,
+}
+
+impl Nodes {
+    /// Return the identifier of the node representing `t`,
+    /// declaring that node first if required.
+    fn write<W, T>(&mut self, w: &mut W, config: &DotConfig, t: &Term<T>) -> io::Result<usize>
+    where
+        W: io::Write,
+        T: TermData,
+    {
+        // every occurrence of a literal is a distinct node
+        if !matches!(t, Term::Literal(_)) {
+            let key = BoxTerm::from(t);
+            if let Some(id) = self.ids.get(&key) {
+                return Ok(*id);
+            }
+            self.ids.insert(key, self.count);
+        }
+        let id = self.count;
+        self.count += 1;
+        match t {
+            Term::BNode(_) => writeln!(w, "    n{} [label=\"\", shape=circle];", id)?,
+            Term::Literal(_) => writeln!(
+                w,
+                "    n{} [label={}, shape=box];",
+                id,
+                quoted(&config.label(t))
+            )?,
+            _ => writeln!(w, "    n{} [label={}];", id, quoted(&config.label(t)))?,
+        }
+        Ok(id)
+    }
+}
+
+/// Quote `txt` as a DOT string.
+fn quoted(txt: &str) -> String {
+    let mut res = String::with_capacity(txt.len() + 2);
+    res.push('"');
+    for c in txt.chars() {
+        match c {
+            '"' => res.push_str("\\\""),
+            '\\' => res.push_str("\\\\"),
+            '\n' => res.push_str("\\n"),
+            _ => res.push(c),
+        }
+    }
+    res.push('"');
+    res
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use sophia_term::StaticTerm;
+
+    #[test]
+    fn graph() {
+        let alice = StaticTerm::new_iri("http://example.org/alice").unwrap();
+        let knows = StaticTerm::new_iri("http://example.org/knows").unwrap();
+        let name = StaticTerm::new_iri("http://example.org/name").unwrap();
+        let bob = StaticTerm::new_bnode("b").unwrap();
+        let g = vec![
+            [alice, knows, bob],
+            [bob, name, "Bob".into()],
+            [
+                alice,
+                name,
+                StaticTerm::new_literal_lang("Alice", "en").unwrap(),
+            ],
+        ];
+
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/").unwrap();
+        let mut config = DotConfig::default();
+        config.set_prefixes(prefixes);
+        let s = DotSerializer::new_stringifier_with_config(config)
+            .serialize_graph(&g)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            &s,
+            r#"digraph {
+    n0 [label="ex:alice"];
+    n1 [label="", shape=circle];
+    n0 -> n1 [label="ex:knows"];
+    n2 [label="\"Bob\"", shape=box];
+    n1 -> n2 [label="ex:name"];
+    n3 [label="\"Alice\"@en", shape=box];
+    n0 -> n3 [label="ex:name"];
+}
+"#
+        );
+
+        let s = DotSerializer::new_stringifier()
+            .serialize_graph(&g)
+            .unwrap()
+            .to_string();
+        assert!(s.contains(r#"n0 [label="<http://example.org/alice>"];"#));
+        assert!(s.contains(r#"n0 -> n1 [label="<http://example.org/knows>"];"#));
+    }
+
+    #[test]
+    fn typed_literal() {
+        let g = vec![[
+            StaticTerm::new_iri("http://example.org/x").unwrap(),
+            StaticTerm::new_iri("http://example.org/p").unwrap(),
+            StaticTerm::new_literal_dt("42", xsd::integer).unwrap(),
+        ]];
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("xsd", xsd::PREFIX).unwrap();
+        let mut config = DotConfig::default();
+        config.set_prefixes(prefixes);
+        let s = DotSerializer::new_stringifier_with_config(config)
+            .serialize_graph(&g)
+            .unwrap()
+            .to_string();
+        assert!(s.contains(r#"n1 [label="\"42\"^^xsd:integer", shape=box];"#));
+    }
+}
@@ -9,16 +9,23 @@
 //! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
 //! [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
 
+use std::collections::hash_map::Entry;
+use std::collections::{BTreeMap, HashMap};
 use std::io;
 
 use crate::quad::{stream::*, Quad};
+use sophia_term::BoxTerm;
 
+use super::nt::write_term;
 use super::*;
 
 /// N-Quads serializer configuration.
 #[derive(Clone, Debug, Default)]
 pub struct NqConfig {
     ascii: bool,
+    flush_every: usize,
+    group_by_graph: bool,
+    strict_iris: bool,
 }
 
 impl NqConfig {
@@ -26,6 +33,36 @@ impl NqConfig {
         self.ascii = ascii;
         self
     }
+
+    /// Make the serializer flush its target every `n` quads
+    /// (0, the default, means never).
+    ///
+    /// This is useful when writing to a slow sink, such as a network socket,
+    /// to avoid unbounded buffering.
+    pub fn set_flush_every(&mut self, n: usize) -> &mut Self {
+        self.flush_every = n;
+        self
+    }
+
+    /// Make the serializer group quads by graph name,
+    /// so that quads from the same graph are written contiguously,
+    /// the default graph coming first, then named graphs in increasing order.
+    ///
+    /// **Important**: when this option is set, the serializer is no longer streaming:
+    /// all quads are buffered in memory before anything is written.
+    pub fn set_group_by_graph(&mut self, group_by_graph: bool) -> &mut Self {
+        self.group_by_graph = group_by_graph;
+        self
+    }
+
+    /// If true, serialization fails on IRIs containing characters
+    /// that are not allowed in N-Quads (such as spaces, `<` or `>`).
+    /// If false (the default), those characters are percent-encoded,
+    /// as with [`NtConfig::set_strict_iris`](../nt/struct.NtConfig.html#method.set_strict_iris).
+    pub fn set_strict_iris(&mut self, strict: bool) -> &mut Self {
+        self.strict_iris = strict;
+        self
+    }
 }
 
 // N-Quads serializer.
@@ -53,6 +90,17 @@ where
     pub fn config(&self) -> &NqConfig {
         &self.config
     }
+
+    /// Write `q`, and flush if required by the `flush_every` option.
+    fn write_quad<Q: Quad>(&mut self, q: &Q, count: &mut usize) -> io::Result<()> {
+        write_quad_line(&mut self.write, q, self.config.strict_iris)?;
+        *count += 1;
+        let flush_every = self.config.flush_every;
+        if flush_every > 0 && *count % flush_every == 0 {
+            self.write.flush()?;
+        }
+        Ok(())
+    }
 }
 
 impl<W> QuadSerializer for NqSerializer<W>
@@ -71,22 +119,118 @@ where
         if self.config.ascii {
             todo!("Pure-ASCII N-Quads is not implemented yet")
         }
-        source
-            .try_for_each_quad(|q| {
-                {
-                    let w = &mut self.write;
-                    write!(w, "{} {} {} ", q.s(), q.p(), q.o())?;
-                    if let Some(g) = q.g() {
-                        write!(w, "{} ", g)?;
-                    }
-                    w.write_all(b".\n")
+        if self.config.group_by_graph {
+            let mut graphs = BTreeMap::<Option<BoxTerm>, Vec<[BoxTerm; 3]>>::new();
+            source
+                .for_each_quad(|q| {
+                    graphs.entry(q.g().map(BoxTerm::from)).or_default().push([
+                        q.s().into(),
+                        q.p().into(),
+                        q.o().into(),
+                    ]);
+                })
+                .map_err(SourceError)?;
+            let mut count = 0;
+            for (g, triples) in graphs.iter() {
+                for t in triples.iter() {
+                    self.write_quad(&(t, g.as_ref()), &mut count)
+                        .map_err(SinkError)?;
                 }
-                .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
-            })
+            }
+            return Ok(self);
+        }
+        let mut count = 0;
+        source
+            .try_for_each_quad(|q| self.write_quad(&q, &mut count).map_err(io::Error::other))
             .map(|_| self)
     }
 }
 
+/// An N-Quads serializer writing the quads of each graph to a distinct target.
+///
+/// The target of each graph is created by the `make_writer` function
+/// passed to [`new`](#method.new),
+/// the first time a quad from that graph is encountered
+/// (with `None` for the default graph).
+/// This makes it possible to split a stream of quads into several files in a single pass.
+pub struct GraphSplittingSink<F, W> {
+    make_writer: F,
+    writers: HashMap<Option<BoxTerm>, W>,
+}
+
+impl<F, W> GraphSplittingSink<F, W>
+where
+    F: FnMut(Option<&BoxTerm>) -> io::Result<W>,
+    W: io::Write,
+{
+    /// Build a new splitting serializer, creating its targets with `make_writer`.
+    pub fn new(make_writer: F) -> Self {
+        GraphSplittingSink {
+            make_writer,
+            writers: HashMap::new(),
+        }
+    }
+
+    /// Borrow the targets created so far, indexed by graph name.
+    pub fn writers(&self) -> &HashMap<Option<BoxTerm>, W> {
+        &self.writers
+    }
+
+    /// Consume this serializer to get the targets created so far, indexed by graph name.
+    pub fn into_writers(self) -> HashMap<Option<BoxTerm>, W> {
+        self.writers
+    }
+
+    /// Write `q` to the target of its graph, creating it if required.
+    fn write_quad<Q: Quad>(&mut self, q: &Q) -> io::Result<()> {
+        let w = match self.writers.entry(q.g().map(BoxTerm::from)) {
+            Entry::Occupied(e) => e.into_mut(),
+            Entry::Vacant(e) => {
+                let w = (self.make_writer)(e.key().as_ref())?;
+                e.insert(w)
+            }
+        };
+        write_quad_line(w, q, false)
+    }
+}
+
+impl<F, W> QuadSerializer for GraphSplittingSink<F, W>
+where
+    F: FnMut(Option<&BoxTerm>) -> io::Result<W>,
+    W: io::Write,
+{
+    type Error = io::Error;
+
+    fn serialize_quads<QS>(
+        &mut self,
+        source: &mut QS,
+    ) -> StreamResult<&mut Self, QS::Error, Self::Error>
+    where
+        QS: QuadSource,
+    {
+        source.try_for_each_quad(|q| self.write_quad(&q))?;
+        for w in self.writers.values_mut() {
+            w.flush().map_err(SinkError)?;
+        }
+        Ok(self)
+    }
+}
+
+/// Write `q` into `w` as a line of N-Quads,
+/// failing on IRIs with characters not allowed in N-Quads if `strict_iris` is true
+/// (see [`NqConfig::set_strict_iris`](struct.NqConfig.html#method.set_strict_iris)).
+fn write_quad_line<W, Q>(w: &mut W, q: &Q, strict_iris: bool) -> io::Result<()>
+where
+    W: io::Write,
+    Q: Quad,
+{
+    for t in [q.s(), q.p(), q.o()].iter().copied().chain(q.g()) {
+        write_term(w, t, strict_iris, false)?;
+        w.write_all(b" ")?;
+    }
+    w.write_all(b".\n")
+}
+
 impl NqSerializer<Vec<u8>> {
     /// Create a new serializer which targets a `String`.
     #[inline]
@@ -145,6 +289,92 @@ pub(crate) mod test {
             &s,
             r#"<http://champin.net/#pa> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://schema.org/Person> .
 <http://champin.net/#pa> <http://schema.org/name> "Pierre-Antoine" <http://champin.net/> .
+"#
+        );
+    }
+
+    #[test]
+    fn strict_iris() {
+        let me = StaticTerm::new_iri("http://champin.net/#pa").unwrap();
+        let d = vec![(
+            [
+                me,
+                rdf::type_,
+                StaticTerm::new_literal_dt("42", xsd::integer).unwrap(),
+            ],
+            Some(me),
+        )];
+        let mut config = NqConfig::default();
+        config.set_strict_iris(true);
+        let s = NqSerializer::new_stringifier_with_config(config)
+            .serialize_dataset(&d)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            &s,
+            "<http://champin.net/#pa> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> <http://champin.net/#pa> .\n"
+        );
+    }
+
+    #[test]
+    fn grouped_by_graph() {
+        let ex = |suffix: &'static str| {
+            StaticTerm::new_iri_suffixed("http://example.org/", suffix).unwrap()
+        };
+        let d = vec![
+            ([ex("a"), ex("p"), ex("b")], Some(ex("g2"))),
+            ([ex("a"), ex("p"), ex("c")], None),
+            ([ex("b"), ex("p"), ex("c")], Some(ex("g1"))),
+            ([ex("c"), ex("p"), ex("a")], Some(ex("g2"))),
+            ([ex("b"), ex("p"), ex("a")], None),
+        ];
+        let mut config = NqConfig::default();
+        config.set_group_by_graph(true);
+        let s = NqSerializer::new_stringifier_with_config(config)
+            .serialize_dataset(&d)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            &s,
+            r#"<http://example.org/a> <http://example.org/p> <http://example.org/c> .
+<http://example.org/b> <http://example.org/p> <http://example.org/a> .
+<http://example.org/b> <http://example.org/p> <http://example.org/c> <http://example.org/g1> .
+<http://example.org/a> <http://example.org/p> <http://example.org/b> <http://example.org/g2> .
+<http://example.org/c> <http://example.org/p> <http://example.org/a> <http://example.org/g2> .
+"#
+        );
+    }
+
+    #[test]
+    fn split_by_graph() {
+        let ex = |suffix: &'static str| {
+            StaticTerm::new_iri_suffixed("http://example.org/", suffix).unwrap()
+        };
+        let d = vec![
+            ([ex("a"), ex("p"), ex("b")], Some(ex("g1"))),
+            ([ex("a"), ex("p"), ex("c")], None),
+            ([ex("b"), ex("p"), ex("c")], Some(ex("g1"))),
+            ([ex("b"), ex("p"), ex("a")], None),
+        ];
+        let mut created = vec![];
+        let mut sink = GraphSplittingSink::new(|g| {
+            created.push(g.cloned());
+            Ok(Vec::<u8>::new())
+        });
+        sink.serialize_dataset(&d).unwrap();
+        let writers = sink.into_writers();
+        assert_eq!(created, vec![Some(BoxTerm::from(&ex("g1"))), None]);
+        assert_eq!(writers.len(), 2);
+        assert_eq!(
+            std::str::from_utf8(&writers[&None]).unwrap(),
+            r#"<http://example.org/a> <http://example.org/p> <http://example.org/c> .
+<http://example.org/b> <http://example.org/p> <http://example.org/a> .
+"#
+        );
+        assert_eq!(
+            std::str::from_utf8(&writers[&Some(BoxTerm::from(&ex("g1")))]).unwrap(),
+            r#"<http://example.org/a> <http://example.org/p> <http://example.org/b> <http://example.org/g1> .
+<http://example.org/b> <http://example.org/p> <http://example.org/c> <http://example.org/g1> .
 "#
         );
     }
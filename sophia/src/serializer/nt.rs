@@ -9,9 +9,13 @@
 //! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
 //! [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
 
+use std::borrow::Cow;
 use std::io;
 
+use crate::ns::xsd;
 use crate::triple::stream::*;
+use sophia_term::literal::Literal;
+use sophia_term::{Term, TermData};
 
 use super::*;
 
@@ -19,6 +23,9 @@ use super::*;
 #[derive(Clone, Debug, Default)]
 pub struct NtConfig {
     ascii: bool,
+    flush_every: usize,
+    strict_iris: bool,
+    explicit_string_datatype: bool,
 }
 
 impl NtConfig {
@@ -26,6 +33,36 @@ impl NtConfig {
         self.ascii = ascii;
         self
     }
+
+    /// Make the serializer flush its target every `n` triples
+    /// (0, the default, means never).
+    ///
+    /// This is useful when writing to a slow sink, such as a network socket,
+    /// to avoid unbounded buffering.
+    pub fn set_flush_every(&mut self, n: usize) -> &mut Self {
+        self.flush_every = n;
+        self
+    }
+
+    /// If true, serialization fails on IRIs containing characters
+    /// that are not allowed in N-Triples (such as spaces, `<` or `>`).
+    /// If false (the default), those characters are percent-encoded.
+    ///
+    /// NB: such IRIs can only be built with the `unchecked` constructors of terms,
+    /// which do not validate their input in release builds.
+    pub fn set_strict_iris(&mut self, strict: bool) -> &mut Self {
+        self.strict_iris = strict;
+        self
+    }
+
+    /// If true, literals of type `xsd:string` are written with their datatype,
+    /// e.g. `"foo"^^<http://www.w3.org/2001/XMLSchema#string>`.
+    /// If false (the default), the shorthand `"foo"` is used,
+    /// as recommended by [RDF 1.1](https://www.w3.org/TR/n-triples/#canonical-ntriples).
+    pub fn set_explicit_string_datatype(&mut self, explicit: bool) -> &mut Self {
+        self.explicit_string_datatype = explicit;
+        self
+    }
 }
 
 // N-Triples serializer.
@@ -71,16 +108,98 @@ where
         if self.config.ascii {
             todo!("Pure-ASCII N-Triples is not implemented yet")
         }
+        let flush_every = self.config.flush_every;
+        let strict = self.config.strict_iris;
+        let explicit = self.config.explicit_string_datatype;
+        let w = &mut self.write;
+        let mut count = 0;
         source
             .try_for_each_triple(|t| {
-                let w = &mut self.write;
-                writeln!(w, "{} {} {} .", t.s(), t.p(), t.o())
-                    .map_err(|e| io::Error::new(io::ErrorKind::Other, e))
+                {
+                    write_term(w, t.s(), strict, explicit)?;
+                    w.write_all(b" ")?;
+                    write_term(w, t.p(), strict, explicit)?;
+                    w.write_all(b" ")?;
+                    write_term(w, t.o(), strict, explicit)?;
+                    w.write_all(b" .\n")?;
+                    count += 1;
+                    if flush_every > 0 && count % flush_every == 0 {
+                        w.flush()?;
+                    }
+                    Ok(())
+                }
+                .map_err(|e: io::Error| io::Error::other(e))
             })
             .map(|_| self)
     }
 }
 
+/// Write `t` into `w` using the N-Triples syntax (shared by N-Quads),
+/// taking care of IRIs containing characters that are not allowed in N-Triples
+/// (see [`NtConfig::set_strict_iris`](struct.NtConfig.html#method.set_strict_iris))
+/// and, if `explicit_string_datatype` is true, writing the datatype of `xsd:string` literals
+/// (see [`NtConfig::set_explicit_string_datatype`](struct.NtConfig.html#method.set_explicit_string_datatype)).
+pub(super) fn write_term<W, T>(
+    w: &mut W,
+    t: &Term<T>,
+    strict_iris: bool,
+    explicit_string_datatype: bool,
+) -> io::Result<()>
+where
+    W: io::Write,
+    T: TermData,
+{
+    match t {
+        Term::Iri(iri) => {
+            let iri = iri.value();
+            write!(w, "<{}>", escape_iri(&iri, strict_iris)?)
+        }
+        Term::Literal(lit) if lit.lang().is_none() => {
+            let dt = lit.dt();
+            let explicit = explicit_string_datatype && dt == xsd::iri::string;
+            let dt = dt.value();
+            match escape_iri(&dt, strict_iris)? {
+                Cow::Borrowed(_) if !explicit => write!(w, "{}", t),
+                dt => {
+                    Literal::<&str>::new_dt(lit.txt().as_ref(), &xsd::iri::string).write_io(w)?;
+                    write!(w, "^^<{}>", dt)
+                }
+            }
+        }
+        _ => write!(w, "{}", t),
+    }
+}
+
+/// Percent-encode the characters of `iri` which are not allowed in N-Triples,
+/// or fail if there are some and `strict` is true.
+pub(super) fn escape_iri(iri: &str, strict: bool) -> io::Result<Cow<'_, str>> {
+    if !iri.chars().any(is_illegal_in_iri) {
+        return Ok(Cow::Borrowed(iri));
+    }
+    if strict {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("IRI {:?} contains characters not allowed in N-Triples", iri),
+        ));
+    }
+    let mut escaped = String::with_capacity(iri.len() + 8);
+    for c in iri.chars() {
+        if is_illegal_in_iri(c) {
+            // illegal characters are all ASCII, hence encoded in one byte
+            escaped.push_str(&format!("%{:02X}", c as u32));
+        } else {
+            escaped.push(c);
+        }
+    }
+    Ok(Cow::Owned(escaped))
+}
+
+/// Whether `c` is not allowed in IRIs in N-Triples
+/// (see [IRIREF](https://www.w3.org/TR/n-triples/#grammar-production-IRIREF)).
+fn is_illegal_in_iri(c: char) -> bool {
+    c <= ' ' || matches!(c, '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\')
+}
+
 impl NtSerializer<Vec<u8>> {
     /// Create a new serializer which targets a `String`.
     #[inline]
@@ -136,4 +255,100 @@ pub(crate) mod test {
 "#
         );
     }
+
+    /// A writer counting how many times it has been flushed.
+    #[derive(Default)]
+    struct FlushCounter {
+        data: Vec<u8>,
+        flushes: usize,
+    }
+
+    impl io::Write for FlushCounter {
+        fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+            self.data.extend_from_slice(buf);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> io::Result<()> {
+            self.flushes += 1;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn flush_every() {
+        let me = StaticTerm::new_iri("http://champin.net/#pa").unwrap();
+        let g: Vec<[StaticTerm; 3]> = (0..10).map(|_| [me, rdf::type_, rdf::Property]).collect();
+
+        let mut config = NtConfig::default();
+        config.set_flush_every(3);
+        let mut ser = NtSerializer::new_with_config(FlushCounter::default(), config);
+        ser.serialize_graph(&g).unwrap();
+        assert_eq!(ser.write.flushes, 3);
+        assert_eq!(ser.write.data.iter().filter(|b| **b == b'\n').count(), 10);
+
+        let mut ser = NtSerializer::new(FlushCounter::default());
+        ser.serialize_graph(&g).unwrap();
+        assert_eq!(ser.write.flushes, 0);
+    }
+
+    #[test]
+    fn escape_iris() {
+        let valid = "http://example.org/ok";
+        assert!(matches!(escape_iri(valid, false), Ok(Cow::Borrowed(i)) if i == valid));
+        assert!(matches!(escape_iri(valid, true), Ok(Cow::Borrowed(i)) if i == valid));
+
+        let sloppy = "http://example.org/a b<c>\"d\"";
+        assert_eq!(
+            escape_iri(sloppy, false).unwrap(),
+            "http://example.org/a%20b%3Cc%3E%22d%22"
+        );
+        let err = escape_iri(sloppy, true).unwrap_err();
+        assert_eq!(err.kind(), io::ErrorKind::InvalidData);
+    }
+
+    #[test]
+    fn strict_iris() {
+        let me = StaticTerm::new_iri("http://champin.net/#pa").unwrap();
+        let g = vec![[
+            me,
+            rdf::type_,
+            StaticTerm::new_literal_dt("42", xsd::integer).unwrap(),
+        ]];
+        let mut config = NtConfig::default();
+        config.set_strict_iris(true);
+        let s = NtSerializer::new_stringifier_with_config(config)
+            .serialize_graph(&g)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            &s,
+            "<http://champin.net/#pa> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> \"42\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n"
+        );
+    }
+
+    #[test]
+    fn explicit_string_datatype() {
+        let me = StaticTerm::new_iri("http://champin.net/#pa").unwrap();
+        let foo = StaticTerm::new_literal_dt("foo", xsd::string).unwrap();
+        let g = vec![[me, rdf::value, foo]];
+        let s = NtSerializer::new_stringifier()
+            .serialize_graph(&g)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            &s,
+            "<http://champin.net/#pa> <http://www.w3.org/1999/02/22-rdf-syntax-ns#value> \"foo\" .\n"
+        );
+
+        let mut config = NtConfig::default();
+        config.set_explicit_string_datatype(true);
+        let s = NtSerializer::new_stringifier_with_config(config)
+            .serialize_graph(&g)
+            .unwrap()
+            .to_string();
+        assert_eq!(
+            &s,
+            "<http://champin.net/#pa> <http://www.w3.org/1999/02/22-rdf-syntax-ns#value> \"foo\"^^<http://www.w3.org/2001/XMLSchema#string> .\n"
+        );
+    }
 }
@@ -0,0 +1,436 @@
+//! Serializers for [SPARQL Update] requests,
+//! inserting (and deleting) the serialized quads with `INSERT DATA` (and `DELETE DATA`) operations.
+//!
+//! Quads are grouped by graph name, the default graph coming first,
+//! and the quads of each named graph are written in a `GRAPH <name> { ... }` block.
+//! Triples are grouped by subject and predicate (using `;` and `,`),
+//! and IRIs are abbreviated according to the configured [`PrefixMap`],
+//! declared with `PREFIX` at the start of the request.
+//!
+//! Variables are not allowed in `INSERT DATA` nor `DELETE DATA`,
+//! so serializing a quad containing a variable fails,
+//! as does serializing an IRI containing characters that are not allowed in SPARQL
+//! (such as spaces, `<` or `>`).
+//! Blank nodes are written with their label in `INSERT DATA`,
+//! but keep in mind that the endpoint will replace them with fresh blank nodes;
+//! they are not allowed at all in `DELETE DATA`.
+//!
+//! Since quads have to be grouped, all the quads are buffered (and sorted)
+//! before anything is written.
+//!
+//! **Important**:
+//! the methods in this module accepting a [`Write`]
+//! make no effort to minimize the number of write operations.
+//! Hence, in most cased, they should be passed a [`BufWriter`].
+//!
+//! [SPARQL Update]: https://www.w3.org/TR/sparql11-update/
+//! [`PrefixMap`]: ../../ns/struct.PrefixMap.html
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::ns::PrefixMap;
+use crate::quad::{stream::*, Quad};
+use sophia_term::{BoxTerm, Term, TermData};
+
+use super::nt::escape_iri;
+use super::trig::GroupedGraph;
+use super::turtle::TurtleConfig;
+pub use super::turtle::TurtleStyle;
+use super::*;
+
+/// The quads of one operation, grouped by graph name, subject and predicate.
+type GroupedDataset = BTreeMap<Option<BoxTerm>, GroupedGraph>;
+
+/// SPARQL Update serializer configuration,
+/// shared by [`InsertDataSerializer`] and [`DeleteInsertDataSerializer`].
+///
+/// [`InsertDataSerializer`]: struct.InsertDataSerializer.html
+/// [`DeleteInsertDataSerializer`]: struct.DeleteInsertDataSerializer.html
+#[derive(Clone, Debug, Default)]
+pub struct SparqlUpdateConfig {
+    turtle: TurtleConfig,
+}
+
+impl SparqlUpdateConfig {
+    /// Declare the prefixes of the given prefix map,
+    /// and use them to abbreviate IRIs.
+    pub fn set_prefixes(&mut self, prefixes: PrefixMap) -> &mut Self {
+        self.turtle.set_prefixes(prefixes);
+        self
+    }
+
+    /// Set the whitespace style of the output.
+    pub fn set_style(&mut self, style: TurtleStyle) -> &mut Self {
+        self.turtle.set_style(style);
+        self
+    }
+
+    /// Write a request made of the given operations, separated by `;`.
+    fn write_request<W: io::Write>(
+        &self,
+        w: &mut W,
+        operations: &[(Operation, &GroupedDataset)],
+    ) -> io::Result<()> {
+        let config = &self.turtle;
+        for (prefix, ns) in config.prefixes.iter() {
+            writeln!(w, "PREFIX {}: <{}>", prefix, ns.as_ref())?;
+        }
+        let indent = config.style.indent();
+        for (i, (operation, dataset)) in operations.iter().enumerate() {
+            if i > 0 {
+                w.write_all(b" ;\n")?;
+            }
+            writeln!(w, "{} DATA {{", operation.keyword())?;
+            for (name, graph) in dataset.iter() {
+                match name {
+                    None => {
+                        for (s, predicates) in graph.iter() {
+                            config.write_subject(w, indent, s, predicates)?;
+                        }
+                    }
+                    Some(name) => {
+                        write!(w, "{}GRAPH ", indent)?;
+                        config.write_term(w, name)?;
+                        w.write_all(b" {\n")?;
+                        let inner = indent.repeat(2);
+                        for (s, predicates) in graph.iter() {
+                            config.write_subject(w, &inner, s, predicates)?;
+                        }
+                        writeln!(w, "{}}}", indent)?;
+                    }
+                }
+            }
+            w.write_all(b"}")?;
+        }
+        if !operations.is_empty() {
+            w.write_all(b"\n")?;
+        }
+        Ok(())
+    }
+}
+
+/// `INSERT DATA` serializer.
+pub struct InsertDataSerializer<W> {
+    config: SparqlUpdateConfig,
+    write: W,
+}
+
+impl<W> InsertDataSerializer<W>
+where
+    W: io::Write,
+{
+    /// Build a new `INSERT DATA` serializer writing to `write`, with the default config.
+    #[inline]
+    pub fn new(write: W) -> InsertDataSerializer<W> {
+        Self::new_with_config(write, SparqlUpdateConfig::default())
+    }
+
+    /// Build a new `INSERT DATA` serializer writing to `write`, with the given config.
+    pub fn new_with_config(write: W, config: SparqlUpdateConfig) -> InsertDataSerializer<W> {
+        InsertDataSerializer { write, config }
+    }
+
+    /// Borrow this serializer's configuration.
+    pub fn config(&self) -> &SparqlUpdateConfig {
+        &self.config
+    }
+}
+
+impl<W> QuadSerializer for InsertDataSerializer<W>
+where
+    W: io::Write,
+{
+    type Error = io::Error;
+
+    fn serialize_quads<QS>(
+        &mut self,
+        source: &mut QS,
+    ) -> StreamResult<&mut Self, QS::Error, Self::Error>
+    where
+        QS: QuadSource,
+    {
+        let inserted = group_quads(source, Operation::Insert)?;
+        self.config
+            .write_request(&mut self.write, &[(Operation::Insert, &inserted)])
+            .map_err(SinkError)?;
+        Ok(self)
+    }
+}
+
+impl InsertDataSerializer<Vec<u8>> {
+    /// Create a new serializer which targets a `String`.
+    #[inline]
+    pub fn new_stringifier() -> Self {
+        InsertDataSerializer::new(Vec::new())
+    }
+    /// Create a new serializer which targets a `String` with a custom config.
+    #[inline]
+    pub fn new_stringifier_with_config(config: SparqlUpdateConfig) -> Self {
+        InsertDataSerializer::new_with_config(Vec::new(), config)
+    }
+}
+
+impl Stringifier for InsertDataSerializer<Vec<u8>> {
+    fn as_utf8(&self) -> &[u8] {
+        &self.write[..]
+    }
+}
+
+/// `DELETE DATA` + `INSERT DATA` serializer,
+/// writing the difference between two states of a dataset as a single request.
+pub struct DeleteInsertDataSerializer<W> {
+    config: SparqlUpdateConfig,
+    write: W,
+}
+
+impl<W> DeleteInsertDataSerializer<W>
+where
+    W: io::Write,
+{
+    /// Build a new `DELETE DATA` + `INSERT DATA` serializer writing to `write`,
+    /// with the default config.
+    #[inline]
+    pub fn new(write: W) -> DeleteInsertDataSerializer<W> {
+        Self::new_with_config(write, SparqlUpdateConfig::default())
+    }
+
+    /// Build a new `DELETE DATA` + `INSERT DATA` serializer writing to `write`,
+    /// with the given config.
+    pub fn new_with_config(write: W, config: SparqlUpdateConfig) -> DeleteInsertDataSerializer<W> {
+        DeleteInsertDataSerializer { write, config }
+    }
+
+    /// Borrow this serializer's configuration.
+    pub fn config(&self) -> &SparqlUpdateConfig {
+        &self.config
+    }
+
+    /// Serialize all quads from `removed` in a `DELETE DATA` operation,
+    /// followed by all quads from `added` in an `INSERT DATA` operation.
+    ///
+    /// An operation without any quad is omitted.
+    pub fn serialize_changes<R, A>(
+        &mut self,
+        removed: &mut R,
+        added: &mut A,
+    ) -> StreamResult<&mut Self, R::Error, io::Error>
+    where
+        R: QuadSource,
+        A: QuadSource<Error = R::Error>,
+    {
+        let deleted = group_quads(removed, Operation::Delete)?;
+        let inserted = group_quads(added, Operation::Insert)?;
+        let operations: Vec<_> = vec![
+            (Operation::Delete, &deleted),
+            (Operation::Insert, &inserted),
+        ]
+        .into_iter()
+        .filter(|(_, dataset)| !dataset.is_empty())
+        .collect();
+        self.config
+            .write_request(&mut self.write, &operations)
+            .map_err(SinkError)?;
+        Ok(self)
+    }
+}
+
+impl DeleteInsertDataSerializer<Vec<u8>> {
+    /// Create a new serializer which targets a `String`.
+    #[inline]
+    pub fn new_stringifier() -> Self {
+        DeleteInsertDataSerializer::new(Vec::new())
+    }
+    /// Create a new serializer which targets a `String` with a custom config.
+    #[inline]
+    pub fn new_stringifier_with_config(config: SparqlUpdateConfig) -> Self {
+        DeleteInsertDataSerializer::new_with_config(Vec::new(), config)
+    }
+}
+
+impl Stringifier for DeleteInsertDataSerializer<Vec<u8>> {
+    fn as_utf8(&self) -> &[u8] {
+        &self.write[..]
+    }
+}
+
+/// The kinds of operation written by the serializers of this module.
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Operation {
+    Insert,
+    Delete,
+}
+
+impl Operation {
+    fn keyword(self) -> &'static str {
+        match self {
+            Operation::Insert => "INSERT",
+            Operation::Delete => "DELETE",
+        }
+    }
+}
+
+/// Buffer all quads from `source`, grouped by graph name, subject and predicate,
+/// checking that they can be written in `operation`.
+fn group_quads<QS>(
+    source: &mut QS,
+    operation: Operation,
+) -> StreamResult<GroupedDataset, QS::Error, io::Error>
+where
+    QS: QuadSource,
+{
+    let mut dataset = GroupedDataset::new();
+    source.try_for_each_quad(|q| -> io::Result<()> {
+        check_term(q.s(), operation)?;
+        check_term(q.p(), operation)?;
+        check_term(q.o(), operation)?;
+        if let Some(g) = q.g() {
+            check_term(g, operation)?;
+        }
+        dataset
+            .entry(q.g().map(BoxTerm::from))
+            .or_default()
+            .entry(q.s().into())
+            .or_default()
+            .entry(q.p().into())
+            .or_default()
+            .insert(q.o().into());
+        Ok(())
+    })?;
+    Ok(dataset)
+}
+
+/// Fail if `t` can not be written in `operation`.
+fn check_term<T>(t: &Term<T>, operation: Operation) -> io::Result<()>
+where
+    T: TermData,
+{
+    match t {
+        Term::Iri(iri) => escape_iri(&iri.value(), true).map(|_| ()),
+        Term::Literal(lit) => escape_iri(&lit.dt().value(), true).map(|_| ()),
+        Term::BNode(_) if operation == Operation::Insert => Ok(()),
+        _ => Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{} is not allowed in {} DATA", t, operation.keyword()),
+        )),
+    }
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dataset::Dataset;
+
+    type TestDataset = Vec<([BoxTerm; 3], Option<BoxTerm>)>;
+
+    #[test]
+    fn insert_data() -> Result<(), Box<dyn std::error::Error>> {
+        let ex = |suffix| BoxTerm::new_iri(format!("http://example.org/{}", suffix)).unwrap();
+        let d: TestDataset = vec![
+            ([ex("alice"), ex("knows"), ex("bob")], None),
+            (
+                [
+                    ex("alice"),
+                    ex("name"),
+                    BoxTerm::from("A \"quoted\"\nname".to_string()),
+                ],
+                Some(ex("g")),
+            ),
+        ];
+
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/")?;
+        let mut config = SparqlUpdateConfig::default();
+        config.set_prefixes(prefixes);
+        let mut ser = InsertDataSerializer::new_stringifier_with_config(config);
+        let output = ser.serialize_dataset(&d)?.to_string();
+        assert_eq!(
+            output,
+            r#"PREFIX ex: <http://example.org/>
+INSERT DATA {
+    ex:alice ex:knows ex:bob .
+    GRAPH ex:g {
+        ex:alice ex:name "A \"quoted\"\nname" .
+    }
+}
+"#
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn variables_are_rejected() -> Result<(), Box<dyn std::error::Error>> {
+        let s = BoxTerm::new_iri("http://example.org/s")?;
+        let d: TestDataset = vec![([s.clone(), s, BoxTerm::new_variable("x")?], None)];
+        let err = InsertDataSerializer::new_stringifier()
+            .serialize_dataset(&d)
+            .err()
+            .unwrap();
+        assert!(matches!(err, SinkError(e) if e.kind() == io::ErrorKind::InvalidData));
+        Ok(())
+    }
+
+    #[test]
+    fn delete_insert_data() -> Result<(), Box<dyn std::error::Error>> {
+        let ex = |suffix| BoxTerm::new_iri(format!("http://example.org/{}", suffix)).unwrap();
+        let removed: TestDataset = vec![([ex("alice"), ex("knows"), ex("carol")], Some(ex("g")))];
+        let added: TestDataset = vec![([ex("alice"), ex("knows"), ex("bob")], None)];
+
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/")?;
+        let mut config = SparqlUpdateConfig::default();
+        config.set_prefixes(prefixes);
+        let mut ser = DeleteInsertDataSerializer::new_stringifier_with_config(config.clone());
+        let output = ser
+            .serialize_changes(&mut removed.quads(), &mut added.quads())?
+            .to_string();
+        assert_eq!(
+            output,
+            r#"PREFIX ex: <http://example.org/>
+DELETE DATA {
+    GRAPH ex:g {
+        ex:alice ex:knows ex:carol .
+    }
+} ;
+INSERT DATA {
+    ex:alice ex:knows ex:bob .
+}
+"#
+        );
+
+        // empty operations are omitted
+        let none = TestDataset::new();
+        let mut ser = DeleteInsertDataSerializer::new_stringifier_with_config(config);
+        let output = ser
+            .serialize_changes(&mut none.quads(), &mut added.quads())?
+            .to_string();
+        assert!(!output.contains("DELETE"));
+        assert!(output.contains("INSERT DATA {\n"));
+        Ok(())
+    }
+
+    #[test]
+    fn bnodes_are_rejected_in_delete() -> Result<(), Box<dyn std::error::Error>> {
+        let s = BoxTerm::new_iri("http://example.org/s")?;
+        let d: TestDataset = vec![([s.clone(), s, BoxTerm::new_bnode("b")?], None)];
+        let err = DeleteInsertDataSerializer::new_stringifier()
+            .serialize_changes(&mut d.quads(), &mut TestDataset::new().quads())
+            .err()
+            .unwrap();
+        assert!(matches!(err, SinkError(e) if e.kind() == io::ErrorKind::InvalidData));
+
+        // but they are allowed in INSERT DATA
+        let mut ser = DeleteInsertDataSerializer::new_stringifier();
+        let output = ser
+            .serialize_changes(&mut TestDataset::new().quads(), &mut d.quads())?
+            .to_string();
+        assert!(output.contains(" _:b .\n"));
+        Ok(())
+    }
+}
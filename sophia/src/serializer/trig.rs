@@ -0,0 +1,282 @@
+//! Serializer for the [TriG] concrete syntax of RDF.
+//!
+//! Quads are grouped by graph name, the default graph coming first,
+//! and each named graph being written in a `GRAPH <name> { ... }` block.
+//! Triples are grouped by subject and predicate (using `;` and `,`),
+//! and IRIs are abbreviated according to the configured [`PrefixMap`].
+//! Blank nodes are always written with their label,
+//! so that blank nodes shared across graphs are preserved.
+//!
+//! Since quads have to be grouped, all the quads are buffered (and sorted)
+//! before anything is written.
+//!
+//! **Important**:
+//! the methods in this module accepting a [`Write`]
+//! make no effort to minimize the number of write operations.
+//! Hence, in most cased, they should be passed a [`BufWriter`].
+//!
+//! [TriG]: https://www.w3.org/TR/trig/
+//! [`PrefixMap`]: ../../ns/struct.PrefixMap.html
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+
+use std::collections::BTreeMap;
+use std::io;
+
+use crate::ns::PrefixMap;
+use crate::quad::{stream::*, Quad};
+use sophia_term::BoxTerm;
+
+pub use super::turtle::TurtleStyle;
+use super::turtle::{PredicateMap, TurtleConfig};
+use super::*;
+
+/// The triples of one graph, grouped by subject and predicate.
+pub(super) type GroupedGraph = BTreeMap<BoxTerm, PredicateMap>;
+
+/// TriG serializer configuration.
+#[derive(Clone, Debug, Default)]
+pub struct TriGConfig {
+    turtle: TurtleConfig,
+}
+
+impl TriGConfig {
+    /// Declare the prefixes of the given prefix map,
+    /// and use them to abbreviate IRIs.
+    pub fn set_prefixes(&mut self, prefixes: PrefixMap) -> &mut Self {
+        self.turtle.set_prefixes(prefixes);
+        self
+    }
+
+    /// Set the whitespace style of the output (see [`TurtleStyle`]).
+    ///
+    /// The style applies to the triples of every graph,
+    /// those of named graphs being indented one more level inside their `GRAPH` block.
+    ///
+    /// [`TurtleStyle`]: struct.TurtleStyle.html
+    pub fn set_style(&mut self, style: TurtleStyle) -> &mut Self {
+        self.turtle.set_style(style);
+        self
+    }
+}
+
+/// TriG serializer.
+pub struct TriGSerializer<W> {
+    config: TriGConfig,
+    write: W,
+}
+
+impl<W> TriGSerializer<W>
+where
+    W: io::Write,
+{
+    /// Build a new TriG serializer writing to `write`, with the default config.
+    #[inline]
+    pub fn new(write: W) -> TriGSerializer<W> {
+        Self::new_with_config(write, TriGConfig::default())
+    }
+
+    /// Build a new TriG serializer writing to `write`, with the given config.
+    pub fn new_with_config(write: W, config: TriGConfig) -> TriGSerializer<W> {
+        TriGSerializer { write, config }
+    }
+
+    /// Borrow this serializer's configuration.
+    pub fn config(&self) -> &TriGConfig {
+        &self.config
+    }
+
+    fn write_dataset(
+        &mut self,
+        dataset: &BTreeMap<Option<BoxTerm>, GroupedGraph>,
+    ) -> io::Result<()> {
+        let config = &self.config.turtle;
+        let w = &mut self.write;
+        config.write_prefixes(w)?;
+        let mut first = config.prefixes.iter().next().is_none();
+        for (name, graph) in dataset.iter() {
+            if !first {
+                writeln!(w)?;
+            }
+            first = false;
+            match name {
+                None => {
+                    for (s, predicates) in graph.iter() {
+                        config.write_subject(w, "", s, predicates)?;
+                    }
+                }
+                Some(name) => {
+                    w.write_all(b"GRAPH ")?;
+                    config.write_term(w, name)?;
+                    w.write_all(b" {\n")?;
+                    for (s, predicates) in graph.iter() {
+                        config.write_subject(w, config.style.indent(), s, predicates)?;
+                    }
+                    w.write_all(b"}\n")?;
+                }
+            }
+        }
+        Ok(())
+    }
+}
+
+impl<W> QuadSerializer for TriGSerializer<W>
+where
+    W: io::Write,
+{
+    type Error = io::Error;
+
+    fn serialize_quads<QS>(
+        &mut self,
+        source: &mut QS,
+    ) -> StreamResult<&mut Self, QS::Error, Self::Error>
+    where
+        QS: QuadSource,
+    {
+        let mut dataset = BTreeMap::<Option<BoxTerm>, GroupedGraph>::new();
+        source
+            .for_each_quad(|q| {
+                dataset
+                    .entry(q.g().map(BoxTerm::from))
+                    .or_default()
+                    .entry(q.s().into())
+                    .or_default()
+                    .entry(q.p().into())
+                    .or_default()
+                    .insert(q.o().into());
+            })
+            .map_err(SourceError)?;
+        self.write_dataset(&dataset).map_err(SinkError)?;
+        Ok(self)
+    }
+}
+
+impl TriGSerializer<Vec<u8>> {
+    /// Create a new serializer which targets a `String`.
+    #[inline]
+    pub fn new_stringifier() -> Self {
+        TriGSerializer::new(Vec::new())
+    }
+    /// Create a new serializer which targets a `String` with a custom config.
+    #[inline]
+    pub fn new_stringifier_with_config(config: TriGConfig) -> Self {
+        TriGSerializer::new_with_config(Vec::new(), config)
+    }
+}
+
+impl Stringifier for TriGSerializer<Vec<u8>> {
+    fn as_utf8(&self) -> &[u8] {
+        &self.write[..]
+    }
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::dataset::Dataset;
+    use crate::ns::rdf;
+    use crate::parser::trig;
+    use sophia_term::{RefTerm, Term};
+
+    type TestDataset = Vec<([BoxTerm; 3], Option<BoxTerm>)>;
+
+    #[test]
+    fn roundtrip() -> Result<(), Box<dyn std::error::Error>> {
+        let input = r#"
+            @prefix ex: <http://example.org/>.
+            ex:alice ex:knows _:b.
+            _:b ex:name "Bob".
+            GRAPH ex:g {
+                _:b a ex:Person; ex:age 42.
+                ex:alice ex:name "Alice"@en, "Alicia"@es.
+            }
+        "#;
+        let mut d1 = TestDataset::new();
+        trig::parse_str(input).in_dataset(&mut d1)?;
+
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/")?;
+        let mut config = TriGConfig::default();
+        config.set_prefixes(prefixes);
+        let mut ser = TriGSerializer::new_stringifier_with_config(config);
+        let output = ser.serialize_dataset(&d1)?.to_string();
+        assert!(output.starts_with("@prefix ex: <http://example.org/> .\n"));
+        assert!(output.contains("\nGRAPH ex:g {\n"));
+        assert!(output.contains(" a ex:Person .\n"));
+        assert!(output.contains(r#"    ex:alice ex:name "Alice"@en, "Alicia"@es ."#));
+        assert!(output.contains(r#""42"^^<http://www.w3.org/2001/XMLSchema#integer>"#));
+        assert!(!output.contains('['));
+
+        let mut d2 = TestDataset::new();
+        trig::parse_str(&output).in_dataset(&mut d2)?;
+        assert_eq!(d2.quads().count(), 6);
+        let ex = |suffix| BoxTerm::new_iri(format!("http://example.org/{}", suffix)).unwrap();
+        let g = ex("g");
+        let bnode = d2
+            .quads_with_sp(&ex("alice"), &ex("knows"))
+            .next()
+            .unwrap()?
+            .o()
+            .clone();
+        assert!(matches!(bnode, Term::BNode(_)));
+        // the blank node is shared between the default graph and ex:g
+        let bob = RefTerm::from("Bob");
+        assert!(Dataset::contains(
+            &d2,
+            &bnode,
+            &ex("name"),
+            &bob,
+            None::<&BoxTerm>
+        )?);
+        assert!(Dataset::contains(
+            &d2,
+            &bnode,
+            &rdf::type_,
+            &ex("Person"),
+            Some(&g)
+        )?);
+        Ok(())
+    }
+
+    #[test]
+    fn style() -> Result<(), Box<dyn std::error::Error>> {
+        let input = r#"
+            @prefix ex: <http://example.org/>.
+            GRAPH ex:g {
+                ex:alice a ex:Person; ex:name "Alice"; ex:knows ex:bob.
+            }
+        "#;
+        let mut d1 = TestDataset::new();
+        trig::parse_str(input).in_dataset(&mut d1)?;
+
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/")?;
+        let mut style = TurtleStyle::default();
+        style.set_indent_spaces(2);
+        let mut config = TriGConfig::default();
+        config.set_prefixes(prefixes).set_style(style.clone());
+        let mut ser = TriGSerializer::new_stringifier_with_config(config.clone());
+        let output = ser.serialize_dataset(&d1)?.to_string();
+        assert!(output.contains(
+            "\n  ex:alice ex:knows ex:bob ;\n    ex:name \"Alice\" ;\n    a ex:Person .\n"
+        ));
+
+        style.set_align_predicates(true);
+        config.set_style(style);
+        let mut ser = TriGSerializer::new_stringifier_with_config(config);
+        let output = ser.serialize_dataset(&d1)?.to_string();
+        assert!(output.contains("\n  ex:alice ex:knows ex:bob ;\n           ex:name \"Alice\" ;\n           a ex:Person .\n"));
+
+        let mut d2 = TestDataset::new();
+        trig::parse_str(&output).in_dataset(&mut d2)?;
+        assert_eq!(d2.quads().count(), 3);
+        for q in d1.iter() {
+            assert!(Dataset::contains(&d2, q.s(), q.p(), q.o(), q.g())?);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,416 @@
+//! Serializer for the [Turtle] concrete syntax of RDF.
+//!
+//! Triples are grouped by subject and predicate (using `;` and `,`),
+//! and IRIs are abbreviated according to the configured [`PrefixMap`].
+//! Blank nodes are always written with their label.
+//!
+//! This serializer is streaming: it only buffers the triples of the current subject,
+//! and writes them as soon as a triple with another subject is met.
+//! Hence, **triples must be grouped by subject**
+//! (e.g. sorted with [`sort_triples`]).
+//! If a subject appears again after another subject,
+//! serialization fails with [`TurtleSerializerError::UngroupedSubject`].
+//! To detect this, the serializer remembers every subject already written,
+//! so its memory usage grows with the number of distinct subjects
+//! (though not with the number of triples).
+//!
+//! **Important**:
+//! the methods in this module accepting a [`Write`]
+//! make no effort to minimize the number of write operations.
+//! Hence, in most cased, they should be passed a [`BufWriter`].
+//!
+//! [Turtle]: https://www.w3.org/TR/turtle/
+//! [`PrefixMap`]: ../../ns/struct.PrefixMap.html
+//! [`sort_triples`]: ../../triple/fn.sort_triples.html
+//! [`TurtleSerializerError::UngroupedSubject`]: enum.TurtleSerializerError.html#variant.UngroupedSubject
+//! [`Write`]: https://doc.rust-lang.org/std/io/trait.Write.html
+//! [`BufWriter`]: https://doc.rust-lang.org/std/io/struct.BufWriter.html
+
+use std::collections::{BTreeMap, BTreeSet, HashSet};
+use std::io;
+
+use crate::ns::{rdf, xsd, PrefixMap};
+use crate::triple::stream::*;
+use sophia_term::iri::Iri;
+use sophia_term::literal::Literal;
+use sophia_term::{BoxTerm, Term, TermData};
+
+use super::*;
+
+/// The triples of one subject, grouped by predicate.
+pub(crate) type PredicateMap = BTreeMap<BoxTerm, BTreeSet<BoxTerm>>;
+
+/// Turtle serializer configuration.
+#[derive(Clone, Debug, Default)]
+pub struct TurtleConfig {
+    pub(super) prefixes: PrefixMap,
+    pub(super) style: TurtleStyle,
+    pub(super) explicit_string_datatype: bool,
+}
+
+impl TurtleConfig {
+    /// Declare the prefixes of the given prefix map,
+    /// and use them to abbreviate IRIs.
+    pub fn set_prefixes(&mut self, prefixes: PrefixMap) -> &mut Self {
+        self.prefixes = prefixes;
+        self
+    }
+
+    /// Set the whitespace style of the output.
+    pub fn set_style(&mut self, style: TurtleStyle) -> &mut Self {
+        self.style = style;
+        self
+    }
+
+    /// If true, literals of type `xsd:string` are written with their datatype,
+    /// e.g. `"foo"^^xsd:string`.
+    /// If false (the default), the shorthand `"foo"` is used.
+    pub fn set_explicit_string_datatype(&mut self, explicit: bool) -> &mut Self {
+        self.explicit_string_datatype = explicit;
+        self
+    }
+
+    /// Write the `@prefix` declarations.
+    pub(super) fn write_prefixes<W: io::Write>(&self, w: &mut W) -> io::Result<()> {
+        for (prefix, ns) in self.prefixes.iter() {
+            writeln!(w, "@prefix {}: <{}> .", prefix, ns.as_ref())?;
+        }
+        Ok(())
+    }
+
+    /// Write all the triples of subject `s`, each line starting with `indent`.
+    pub(super) fn write_subject<W: io::Write>(
+        &self,
+        w: &mut W,
+        indent: &str,
+        s: &BoxTerm,
+        predicates: &PredicateMap,
+    ) -> io::Result<()> {
+        let mut subject = Vec::new();
+        self.write_term(&mut subject, s)?;
+        let continuation = if self.style.align_predicates {
+            // subject is valid UTF-8, as written by write_term
+            let width = std::str::from_utf8(&subject).unwrap().chars().count();
+            format!("{}{}", indent, " ".repeat(width + 1))
+        } else {
+            format!("{}{}", indent, self.style.indent)
+        };
+        w.write_all(indent.as_bytes())?;
+        w.write_all(&subject)?;
+        for (i, (p, objects)) in predicates.iter().enumerate() {
+            if i > 0 {
+                write!(w, " ;\n{}", continuation)?;
+            } else {
+                w.write_all(b" ")?;
+            }
+            if p == &rdf::type_ {
+                w.write_all(b"a")?;
+            } else {
+                self.write_term(w, p)?;
+            }
+            for (j, o) in objects.iter().enumerate() {
+                w.write_all(if j > 0 { ", " } else { " " }.as_bytes())?;
+                self.write_term(w, o)?;
+            }
+        }
+        w.write_all(b" .\n")
+    }
+
+    pub(super) fn write_term<W: io::Write>(&self, w: &mut W, t: &BoxTerm) -> io::Result<()> {
+        match t {
+            Term::Iri(iri) => self.write_iri(w, iri),
+            Term::Literal(lit)
+                if lit.lang().is_none()
+                    && (self.explicit_string_datatype || lit.dt() != xsd::iri::string) =>
+            {
+                Literal::<&str>::new_dt(lit.txt().as_ref(), &xsd::iri::string).write_io(w)?;
+                w.write_all(b"^^")?;
+                self.write_iri(w, &lit.dt())
+            }
+            _ => write!(w, "{}", t),
+        }
+    }
+
+    fn write_iri<W: io::Write, T: TermData>(&self, w: &mut W, iri: &Iri<T>) -> io::Result<()> {
+        match self.prefixes.compact(&iri.value()) {
+            Some(curie) if is_simple_curie(&curie) => w.write_all(curie.as_bytes()),
+            _ => iri.write_io(w),
+        }
+    }
+}
+
+/// Whitespace style of the Turtle syntax
+/// written by [`TurtleSerializer`] and [`TriGSerializer`].
+///
+/// Whatever the style, the output parses to the same triples (or quads).
+///
+/// [`TurtleSerializer`]: struct.TurtleSerializer.html
+/// [`TriGSerializer`]: ../trig/struct.TriGSerializer.html
+#[derive(Clone, Debug)]
+pub struct TurtleStyle {
+    indent: String,
+    align_predicates: bool,
+}
+
+impl Default for TurtleStyle {
+    fn default() -> Self {
+        TurtleStyle {
+            indent: "    ".to_string(),
+            align_predicates: false,
+        }
+    }
+}
+
+impl TurtleStyle {
+    /// Indent with `width` spaces (4 by default).
+    pub fn set_indent_spaces(&mut self, width: usize) -> &mut Self {
+        self.indent = " ".repeat(width);
+        self
+    }
+
+    /// Indent with one tab.
+    pub fn set_indent_tab(&mut self) -> &mut Self {
+        self.indent = "\t".to_string();
+        self
+    }
+
+    /// If true, predicates following a `;` are aligned with the first predicate of their subject
+    /// (false by default, in which case they are indented one level deeper than the subject).
+    pub fn set_align_predicates(&mut self, align_predicates: bool) -> &mut Self {
+        self.align_predicates = align_predicates;
+        self
+    }
+
+    /// One level of indentation.
+    pub(super) fn indent(&self) -> &str {
+        &self.indent
+    }
+}
+
+/// The error type of [`TurtleSerializer`](struct.TurtleSerializer.html).
+#[derive(Debug, thiserror::Error)]
+pub enum TurtleSerializerError {
+    /// An error occurred while writing.
+    #[error("{0}")]
+    Io(#[from] io::Error),
+    /// The triples were not grouped by subject:
+    /// this subject appeared again after other subjects.
+    #[error("triples are not grouped by subject: {0} appears again after other subjects")]
+    UngroupedSubject(BoxTerm),
+}
+
+/// Turtle serializer.
+pub struct TurtleSerializer<W> {
+    config: TurtleConfig,
+    write: W,
+}
+
+impl<W> TurtleSerializer<W>
+where
+    W: io::Write,
+{
+    /// Build a new Turtle serializer writing to `write`, with the default config.
+    #[inline]
+    pub fn new(write: W) -> TurtleSerializer<W> {
+        Self::new_with_config(write, TurtleConfig::default())
+    }
+
+    /// Build a new Turtle serializer writing to `write`, with the given config.
+    pub fn new_with_config(write: W, config: TurtleConfig) -> TurtleSerializer<W> {
+        TurtleSerializer { write, config }
+    }
+
+    /// Borrow this serializer's configuration.
+    pub fn config(&self) -> &TurtleConfig {
+        &self.config
+    }
+}
+
+impl<W> TripleSerializer for TurtleSerializer<W>
+where
+    W: io::Write,
+{
+    type Error = TurtleSerializerError;
+
+    /// Serialize all triples from the given source,
+    /// which must be grouped by subject (see [module documentation](index.html)).
+    fn serialize_triples<TS>(
+        &mut self,
+        source: &mut TS,
+    ) -> StreamResult<&mut Self, TS::Error, Self::Error>
+    where
+        TS: TripleSource,
+    {
+        let config = &self.config;
+        let w = &mut self.write;
+        config
+            .write_prefixes(w)
+            .map_err(|err| SinkError(err.into()))?;
+        if config.prefixes.iter().next().is_some() {
+            writeln!(w).map_err(|err| SinkError(err.into()))?;
+        }
+        let mut current: Option<(BoxTerm, PredicateMap)> = None;
+        let mut done = HashSet::new();
+        source.try_for_each_triple(|t| -> Result<(), TurtleSerializerError> {
+            if let Some((s, predicates)) = &mut current {
+                if *s == *t.s() {
+                    predicates
+                        .entry(t.p().into())
+                        .or_default()
+                        .insert(t.o().into());
+                    return Ok(());
+                }
+            }
+            if let Some((s, predicates)) = current.take() {
+                config.write_subject(w, "", &s, &predicates)?;
+                done.insert(s);
+            }
+            let s = BoxTerm::from(t.s());
+            if done.contains(&s) {
+                return Err(TurtleSerializerError::UngroupedSubject(s));
+            }
+            let mut predicates = PredicateMap::new();
+            predicates
+                .entry(t.p().into())
+                .or_default()
+                .insert(t.o().into());
+            current = Some((s, predicates));
+            Ok(())
+        })?;
+        if let Some((s, predicates)) = current {
+            config
+                .write_subject(w, "", &s, &predicates)
+                .map_err(|err| SinkError(err.into()))?;
+        }
+        Ok(self)
+    }
+}
+
+impl TurtleSerializer<Vec<u8>> {
+    /// Create a new serializer which targets a `String`.
+    #[inline]
+    pub fn new_stringifier() -> Self {
+        TurtleSerializer::new(Vec::new())
+    }
+    /// Create a new serializer which targets a `String` with a custom config.
+    #[inline]
+    pub fn new_stringifier_with_config(config: TurtleConfig) -> Self {
+        TurtleSerializer::new_with_config(Vec::new(), config)
+    }
+}
+
+impl Stringifier for TurtleSerializer<Vec<u8>> {
+    fn as_utf8(&self) -> &[u8] {
+        &self.write[..]
+    }
+}
+
+/// Whether the local part of `curie` can be written in Turtle without escaping.
+///
+/// This is more conservative than the Turtle grammar,
+/// IRIs failing this test are simply written in full.
+fn is_simple_curie(curie: &str) -> bool {
+    let local = &curie[curie.find(':').unwrap() + 1..];
+    !local.starts_with('-')
+        && local
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+// ---------------------------------------------------------------------------------
+//                                      tests
+// ---------------------------------------------------------------------------------
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::graph::Graph;
+    use crate::parser::turtle;
+    use crate::triple::{sort_triples, TripleOrder};
+
+    fn config() -> TurtleConfig {
+        let mut prefixes = PrefixMap::new();
+        prefixes.insert("ex", "http://example.org/").unwrap();
+        let mut config = TurtleConfig::default();
+        config.set_prefixes(prefixes);
+        config
+    }
+
+    #[test]
+    fn grouped_by_subject() -> Result<(), Box<dyn std::error::Error>> {
+        let input = r#"
+            @prefix ex: <http://example.org/>.
+            ex:bob ex:name "Bob".
+            ex:alice a ex:Person; ex:name "Alice"@en, "Alicia"@es.
+            ex:bob a ex:Person; ex:knows ex:alice.
+        "#;
+        let mut g: Vec<[BoxTerm; 3]> = vec![];
+        turtle::parse_str(input).in_graph(&mut g)?;
+        sort_triples(&mut g, TripleOrder::SPO);
+
+        let mut ser = TurtleSerializer::new_stringifier_with_config(config());
+        let output = ser.serialize_graph(&g)?.to_string();
+        assert_eq!(
+            output,
+            r#"@prefix ex: <http://example.org/> .
+
+ex:alice ex:name "Alice"@en, "Alicia"@es ;
+    a ex:Person .
+ex:bob ex:knows ex:alice ;
+    ex:name "Bob" ;
+    a ex:Person .
+"#
+        );
+
+        let mut g2: Vec<[BoxTerm; 3]> = vec![];
+        turtle::parse_str(&output).in_graph(&mut g2)?;
+        assert_eq!(g2.len(), g.len());
+        for [s, p, o] in g.iter() {
+            assert!(Graph::contains(&g2, s, p, o)?);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn ungrouped_subject() -> Result<(), Box<dyn std::error::Error>> {
+        let input = r#"
+            @prefix ex: <http://example.org/>.
+            ex:bob ex:name "Bob".
+            ex:alice a ex:Person.
+            ex:bob a ex:Person.
+        "#;
+        let mut g: Vec<[BoxTerm; 3]> = vec![];
+        turtle::parse_str(input).in_graph(&mut g)?;
+
+        let mut ser = TurtleSerializer::new_stringifier_with_config(config());
+        let err = ser.serialize_graph(&g).err().unwrap();
+        match err {
+            SinkError(TurtleSerializerError::UngroupedSubject(s)) => {
+                assert_eq!(s, BoxTerm::new_iri("http://example.org/bob")?)
+            }
+            _ => panic!("unexpected error {}", err),
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn explicit_string_datatype() -> Result<(), Box<dyn std::error::Error>> {
+        let mut g: Vec<[BoxTerm; 3]> = vec![];
+        turtle::parse_str(r#"<http://example.org/bob> <http://example.org/name> "Bob"."#)
+            .in_graph(&mut g)?;
+
+        let mut ser = TurtleSerializer::new_stringifier_with_config(config());
+        let output = ser.serialize_graph(&g)?.to_string();
+        assert!(output.ends_with("ex:bob ex:name \"Bob\" .\n"));
+
+        let mut explicit = config();
+        explicit
+            .prefixes
+            .insert("xsd", "http://www.w3.org/2001/XMLSchema#")?;
+        explicit.set_explicit_string_datatype(true);
+        let mut ser = TurtleSerializer::new_stringifier_with_config(explicit);
+        let output = ser.serialize_graph(&g)?.to_string();
+        assert!(output.ends_with("ex:bob ex:name \"Bob\"^^xsd:string .\n"));
+        Ok(())
+    }
+}
@@ -0,0 +1,123 @@
+//! A statement is either a [triple](../triple/index.html) or a [quad](../quad/index.html).
+//!
+//! The [`Statement`](./trait.Statement.html) trait abstracts over the arity of both,
+//! so that generic algorithms (hashing, display...)
+//! can be written once and applied uniformly to triples and quads.
+
+use std::ops::Deref;
+
+use crate::quad::Quad;
+use crate::triple::Triple;
+use sophia_term::*;
+
+/// This trait represents an abstract RDF statement,
+/// i.e. a triple or a quad, regardless of its arity.
+///
+/// Implementations are provided by the adapters returned by
+/// [`Triple::into_statement`](../triple/trait.Triple.html#method.into_statement) and
+/// [`Quad::into_statement`](../quad/trait.Quad.html#method.into_statement).
+pub trait Statement {
+    type TermData: TermData;
+    /// The terms of this statement,
+    /// in the order subject, predicate, object, graph name.
+    ///
+    /// This contains 4 terms for a quad in a named graph,
+    /// and 3 terms for a triple or a quad in the default graph.
+    fn terms(&self) -> StatementTerms<'_, Self::TermData>;
+}
+
+/// The terms of a [`Statement`](./trait.Statement.html),
+/// usable as a slice `&[&Term]` (without any allocation).
+#[derive(Clone, Copy, Debug)]
+pub struct StatementTerms<'a, TD: TermData> {
+    terms: [&'a Term<TD>; 4],
+    len: usize,
+}
+
+impl<'a, TD: TermData> StatementTerms<'a, TD> {
+    fn new(s: &'a Term<TD>, p: &'a Term<TD>, o: &'a Term<TD>, g: Option<&'a Term<TD>>) -> Self {
+        match g {
+            Some(g) => StatementTerms {
+                terms: [s, p, o, g],
+                len: 4,
+            },
+            None => StatementTerms {
+                terms: [s, p, o, o],
+                len: 3,
+            },
+        }
+    }
+}
+
+impl<'a, TD: TermData> Deref for StatementTerms<'a, TD> {
+    type Target = [&'a Term<TD>];
+    fn deref(&self) -> &Self::Target {
+        &self.terms[..self.len]
+    }
+}
+
+/// The adapter returned by [`Triple::into_statement`](../triple/trait.Triple.html#method.into_statement).
+pub struct TripleAsStatement<T>(pub(crate) T);
+
+impl<T> TripleAsStatement<T> {
+    /// Unwrap this adapter to get the original triple back.
+    pub fn unwrap(self) -> T {
+        self.0
+    }
+}
+
+impl<T: Triple> Statement for TripleAsStatement<T> {
+    type TermData = T::TermData;
+    fn terms(&self) -> StatementTerms<'_, T::TermData> {
+        StatementTerms::new(self.0.s(), self.0.p(), self.0.o(), None)
+    }
+}
+
+/// The adapter returned by [`Quad::into_statement`](../quad/trait.Quad.html#method.into_statement).
+pub struct QuadAsStatement<Q>(pub(crate) Q);
+
+impl<Q> QuadAsStatement<Q> {
+    /// Unwrap this adapter to get the original quad back.
+    pub fn unwrap(self) -> Q {
+        self.0
+    }
+}
+
+impl<Q: Quad> Statement for QuadAsStatement<Q> {
+    type TermData = Q::TermData;
+    fn terms(&self) -> StatementTerms<'_, Q::TermData> {
+        StatementTerms::new(self.0.s(), self.0.p(), self.0.o(), self.0.g())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn n3_like<S: Statement>(st: &S) -> String {
+        st.terms()
+            .iter()
+            .map(|t| t.to_string())
+            .collect::<Vec<_>>()
+            .join(" ")
+    }
+
+    #[test]
+    fn terms() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let g = BoxTerm::new_iri("http://example.org/g").unwrap();
+        let lit = BoxTerm::from("lit".to_string());
+
+        let t = [&s, &s, &lit].into_statement();
+        assert_eq!(*t.terms(), [&s, &s, &lit]);
+        assert_eq!(n3_like(&t), format!("{} {} {}", s, s, lit));
+
+        let q = ([&s, &s, &lit], Some(&g)).into_statement();
+        assert_eq!(*q.terms(), [&s, &s, &lit, &g]);
+        assert_eq!(n3_like(&q), format!("{} {} {} {}", s, s, lit, g));
+
+        let q = ([&s, &s, &lit], None::<&BoxTerm>).into_statement();
+        assert_eq!(q.terms().len(), 3);
+        assert_eq!(n3_like(&q), n3_like(&t));
+    }
+}
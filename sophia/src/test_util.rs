@@ -0,0 +1,72 @@
+//! Utilities for testing parsers and serializers,
+//! including those implemented in other crates.
+//!
+//! This module is only available with the `test-util` feature,
+//! which is meant to be enabled in the `[dev-dependencies]` section of downstream crates:
+//!
+//! ```toml
+//! [dev-dependencies]
+//! sophia = { version = "0.4", features = ["test-util"] }
+//! ```
+
+use std::collections::HashSet;
+
+use crate::parser::TripleParser;
+use crate::serializer::{Stringifier, TripleSerializer};
+use crate::triple::stream::TripleSource;
+use sophia_term::BoxTerm;
+
+pub use crate::graph::isomorphism::isomorphic_graphs;
+
+type TestGraph = HashSet<[BoxTerm; 3]>;
+
+/// Parse `input` with `parser`, serialize the result with `serializer`,
+/// parse the serialized data again with `parser`,
+/// and assert that both parsed graphs are [isomorphic](../graph/isomorphism/fn.isomorphic_graphs.html).
+///
+/// Since the serialized data is re-parsed with `parser`,
+/// the syntax produced by `serializer` must be supported by `parser`.
+///
+/// # Panics
+///
+/// This function panics if any step fails, or if the graphs are not isomorphic.
+///
+/// # Example
+///
+/// ```
+/// use sophia::parser::turtle::TurtleParser;
+/// use sophia::serializer::nt::NtSerializer;
+/// use sophia::test_util::assert_roundtrip;
+///
+/// let input = r#"
+///     @prefix : <http://example.org/>.
+///     :alice :knows [ :name "Bob" ], _:c.
+///     _:c :name "Charlie"@en.
+/// "#;
+/// assert_roundtrip(input, &TurtleParser::default(), NtSerializer::new_stringifier());
+/// ```
+pub fn assert_roundtrip<P, S>(input: &str, parser: &P, mut serializer: S)
+where
+    P: for<'x> TripleParser<&'x [u8]>,
+    S: TripleSerializer + Stringifier,
+{
+    let mut g1 = TestGraph::new();
+    parser
+        .parse(input.as_bytes())
+        .in_graph(&mut g1)
+        .expect("failed to parse input");
+    serializer
+        .serialize_graph(&g1)
+        .expect("failed to serialize graph");
+    let output = serializer.as_str();
+    let mut g2 = TestGraph::new();
+    parser
+        .parse(output.as_bytes())
+        .in_graph(&mut g2)
+        .expect("failed to parse serialized data");
+    assert!(
+        isomorphic_graphs(&g1, &g2),
+        "round-trip produced a different graph; serialized data:\n{}",
+        output
+    );
+}
@@ -13,6 +13,7 @@
 //!
 
 use crate::quad::Quad;
+use crate::statement::TripleAsStatement;
 use sophia_term::*;
 
 pub mod stream;
@@ -29,6 +30,29 @@ pub trait Triple {
     /// The object of this triple.
     fn o(&self) -> &Term<Self::TermData>;
 
+    /// Whether this triple is only valid in the *generalized* RDF model,
+    /// i.e. if its subject is neither an IRI nor a blank node,
+    /// if its predicate is not an IRI,
+    /// or if its object is a variable.
+    fn is_generalized(&self) -> bool {
+        !matches!(self.s(), Term::Iri(_) | Term::BNode(_))
+            || !matches!(self.p(), Term::Iri(_))
+            || matches!(self.o(), Term::Variable(_))
+    }
+
+    /// Return an owned copy of this triple,
+    /// or `None` if this triple is [generalized](#method.is_generalized).
+    ///
+    /// This is useful to sanitize the output of a lenient parser
+    /// before inserting it into a graph supporting only strict RDF.
+    fn to_standard(&self) -> Option<[BoxTerm; 3]> {
+        if self.is_generalized() {
+            None
+        } else {
+            Some([self.s().into(), self.p().into(), self.o().into()])
+        }
+    }
+
     /// [`Quad`](../quad/trait.Quad.html) adapter owning this triple,
     /// pretending to belong to the default graph.
     fn as_quad(self) -> TripleAsQuad<Self>
@@ -45,6 +69,14 @@ pub trait Triple {
     {
         TripleAsQuadFrom(self, name)
     }
+
+    /// [`Statement`](../statement/trait.Statement.html) adapter owning this triple.
+    fn into_statement(self) -> TripleAsStatement<Self>
+    where
+        Self: Sized,
+    {
+        TripleAsStatement(self)
+    }
 }
 
 impl<T> Triple for [Term<T>; 3]
@@ -161,7 +193,163 @@ impl<T: Triple> Quad for TripleAsQuadFrom<T> {
     }
 }
 
+/// The orderings supported by [`sort_triples`](./fn.sort_triples.html).
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum TripleOrder {
+    /// Sort by subject, then predicate, then object.
+    SPO,
+    /// Sort by predicate, then object, then subject.
+    POS,
+    /// Sort by object, then subject, then predicate.
+    OSP,
+}
+
+impl TripleOrder {
+    /// The positions (0 for subject, 1 for predicate, 2 for object) in the order they are compared.
+    pub(crate) fn positions(self) -> [usize; 3] {
+        match self {
+            TripleOrder::SPO => [0, 1, 2],
+            TripleOrder::POS => [1, 2, 0],
+            TripleOrder::OSP => [2, 0, 1],
+        }
+    }
+}
+
+/// The positions of a triple considered by
+/// [`Graph::triples_in_namespace`](../graph/trait.Graph.html#method.triples_in_namespace).
+///
+/// By default, the subject, predicate and object are considered,
+/// but not the datatypes of literals.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct NamespacePositions {
+    /// Whether to consider the subject.
+    pub subject: bool,
+    /// Whether to consider the predicate.
+    pub predicate: bool,
+    /// Whether to consider the object.
+    pub object: bool,
+    /// Whether to consider the datatype of the object, if it is a literal.
+    pub datatype: bool,
+}
+
+impl Default for NamespacePositions {
+    fn default() -> Self {
+        NamespacePositions {
+            subject: true,
+            predicate: true,
+            object: true,
+            datatype: false,
+        }
+    }
+}
+
+/// Sort `triples` according to `order`.
+///
+/// Each term is compared according to its N-Triples serialization
+/// (with language tags lowercased, so that equal terms always end up adjacent),
+/// which provides a total order over all kinds of terms.
+/// The sort is stable.
+pub fn sort_triples(triples: &mut [[BoxTerm; 3]], order: TripleOrder) {
+    let positions = order.positions();
+    triples.sort_by_cached_key(|t| {
+        [
+            nt_key(&t[positions[0]]),
+            nt_key(&t[positions[1]]),
+            nt_key(&t[positions[2]]),
+        ]
+    });
+}
+
+/// The N-Triples serialization of `t`, with its language tag (if any) lowercased.
+pub(crate) fn nt_key<T: TermData>(t: &Term<T>) -> String {
+    let mut key = t.to_string();
+    if let Term::Literal(lit) = t {
+        if let Some(tag) = lit.lang() {
+            let start = key.len() - tag.as_ref().len();
+            key[start..].make_ascii_lowercase();
+        }
+    }
+    key
+}
+
 #[cfg(test)]
 mod test {
-    // Nothing really worth testing here
+    use super::*;
+    use crate::ns::{rdf, rdfs};
+
+    #[test]
+    fn test_sort_triples_pos() {
+        let a = BoxTerm::new_iri("http://example.org/a").unwrap();
+        let b = BoxTerm::new_iri("http://example.org/b").unwrap();
+        let bn = BoxTerm::new_bnode("x").unwrap();
+        let lit = BoxTerm::from("lit".to_string());
+        let typ = BoxTerm::from(&rdf::type_);
+        let label = BoxTerm::from(&rdfs::label);
+        let mut triples = vec![
+            [b.clone(), typ.clone(), a.clone()],
+            [a.clone(), label.clone(), lit.clone()],
+            [bn.clone(), typ.clone(), a.clone()],
+            [a.clone(), typ.clone(), b.clone()],
+            [a.clone(), typ.clone(), a.clone()],
+        ];
+        sort_triples(&mut triples, TripleOrder::POS);
+        assert_eq!(
+            triples,
+            vec![
+                [a.clone(), typ.clone(), a.clone()],
+                [b.clone(), typ.clone(), a.clone()],
+                [bn.clone(), typ.clone(), a.clone()],
+                [a.clone(), typ.clone(), b.clone()],
+                [a.clone(), label.clone(), lit.clone()],
+            ]
+        );
+
+        sort_triples(&mut triples, TripleOrder::OSP);
+        // literals come first, as '"' < '<'
+        assert_eq!(triples[0], [a.clone(), label, lit]);
+        assert_eq!(triples[4], [a.clone(), typ.clone(), b.clone()]);
+        sort_triples(&mut triples, TripleOrder::SPO);
+        assert_eq!(triples[0], [a.clone(), typ.clone(), a.clone()]);
+        assert_eq!(triples[4], [bn, typ, a]);
+    }
+
+    #[test]
+    fn test_sort_triples_lang_case() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let p = BoxTerm::from(&rdfs::label);
+        let mut triples = vec![
+            [
+                s.clone(),
+                p.clone(),
+                BoxTerm::new_literal_lang("x", "EN").unwrap(),
+            ],
+            [
+                s.clone(),
+                p.clone(),
+                BoxTerm::new_literal_lang("x", "de").unwrap(),
+            ],
+            [
+                s.clone(),
+                p.clone(),
+                BoxTerm::new_literal_lang("x", "en").unwrap(),
+            ],
+        ];
+        sort_triples(&mut triples, TripleOrder::SPO);
+        assert_eq!(triples[0][2].to_string(), "\"x\"@de");
+        assert_eq!(triples[1][2].to_string(), "\"x\"@EN");
+        assert_eq!(triples[2][2].to_string(), "\"x\"@en");
+    }
+
+    #[test]
+    fn test_to_standard() {
+        let s = BoxTerm::new_iri("http://example.org/s").unwrap();
+        let p = BoxTerm::from(&rdfs::label);
+        let lit = BoxTerm::from("lit".to_string());
+        let t = [s.clone(), p.clone(), lit.clone()];
+        assert_eq!(t.to_standard(), Some(t.clone()));
+        let t = [&lit, &p, &s];
+        assert_eq!(t.to_standard(), None);
+        let t = [&s, &lit, &s];
+        assert_eq!(t.to_standard(), None);
+    }
 }
@@ -46,6 +46,7 @@ use std::error::Error;
 use crate::graph::*;
 use crate::triple::streaming_mode::*;
 use crate::triple::*;
+use sophia_term::BoxTerm;
 
 mod _error;
 pub use self::_error::*;
@@ -57,6 +58,8 @@ mod _iterator;
 pub use self::_iterator::*;
 mod _map;
 pub use self::_map::*;
+mod _pipeline;
+pub use self::_pipeline::*;
 
 /// Type alias for referencing the `TermData` used in a `TripleSource`.
 pub type TSData<S> =
@@ -133,6 +136,18 @@ pub trait TripleSource {
     {
         graph.insert_all(self)
     }
+    /// Collect all triples from this source into a vector of owned triples.
+    ///
+    /// Stop on the first error.
+    #[inline]
+    fn collect_triples(mut self) -> Result<Vec<[BoxTerm; 3]>, Self::Error>
+    where
+        Self: Sized,
+    {
+        let mut v = Vec::new();
+        self.for_each_triple(|t| v.push([t.s().into(), t.p().into(), t.o().into()]))?;
+        Ok(v)
+    }
     /// Creates a triple source which uses a closure to determine if a triple should be yielded.
     #[inline]
     fn filter_triples<F>(self, filter: F) -> FilterSource<Self, F>
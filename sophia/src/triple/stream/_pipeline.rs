@@ -0,0 +1,124 @@
+// this module is transparently re-exported by its parent `stream`
+
+use super::*;
+
+use crate::serializer::TripleSerializer;
+
+/// A builder connecting a [`TripleSource`](./trait.TripleSource.html),
+/// through a series of transformation stages,
+/// directly into a [`TripleSerializer`](../../serializer/trait.TripleSerializer.html).
+///
+/// Triples are streamed one by one from the source to the serializer,
+/// so no intermediate collection is ever built.
+///
+/// # Example
+///
+/// ```
+/// use sophia::parser::turtle;
+/// use sophia::serializer::Stringifier;
+/// use sophia::serializer::nt::NtSerializer;
+/// use sophia::triple::stream::Pipeline;
+/// use sophia::triple::Triple;
+///
+/// let turtle = r#"
+///     @prefix : <http://example.org/>.
+///     :alice :name "Alice"; :age 42.
+/// "#;
+/// let mut ser = NtSerializer::new_stringifier();
+/// let count = Pipeline::new(turtle::parse_str(turtle))
+///     .filter(|t| t.p().value() != "http://example.org/age")
+///     .serialize_into(&mut ser)
+///     .unwrap();
+/// assert_eq!(count, 1);
+/// assert_eq!(
+///     ser.as_str(),
+///     "<http://example.org/alice> <http://example.org/name> \"Alice\" .\n",
+/// );
+/// ```
+pub struct Pipeline<S> {
+    source: S,
+}
+
+impl<S> Pipeline<S>
+where
+    S: TripleSource,
+{
+    /// Start a new pipeline from the given source.
+    pub fn new(source: S) -> Self {
+        Pipeline { source }
+    }
+
+    /// Add a stage keeping only the triples for which `filter` returns `true`
+    /// (see [`TripleSource::filter_triples`](./trait.TripleSource.html#method.filter_triples)).
+    pub fn filter<F>(self, filter: F) -> Pipeline<FilterSource<S, F>>
+    where
+        F: FnMut(&StreamedTriple<S::Triple>) -> bool,
+    {
+        Pipeline::new(self.source.filter_triples(filter))
+    }
+
+    /// Add a stage replacing each triple by the result of `map`
+    /// (see [`TripleSource::map_triples`](./trait.TripleSource.html#method.map_triples)).
+    pub fn map<F, T>(self, map: F) -> Pipeline<MapSource<S, F>>
+    where
+        F: FnMut(StreamedTriple<S::Triple>) -> T,
+        T: Triple,
+    {
+        Pipeline::new(self.source.map_triples(map))
+    }
+
+    /// Add a stage that both filters and maps triples
+    /// (see [`TripleSource::filter_map_triples`](./trait.TripleSource.html#method.filter_map_triples)).
+    pub fn filter_map<F, T>(self, filter_map: F) -> Pipeline<FilterMapSource<S, F>>
+    where
+        F: FnMut(StreamedTriple<S::Triple>) -> Option<T>,
+        T: Triple,
+    {
+        Pipeline::new(self.source.filter_map_triples(filter_map))
+    }
+
+    /// Run this pipeline, feeding all the triples that went through every stage to `serializer`.
+    ///
+    /// Return the number of triples passed to the serializer.
+    ///
+    /// # Error
+    ///
+    /// Stop on the first error, raised either by the source or by the serializer.
+    /// The returned [`PipelineError`](./struct.PipelineError.html) tells which one failed,
+    /// and how many triples had reached the serializer at that point.
+    pub fn serialize_into<Ser>(
+        self,
+        serializer: &mut Ser,
+    ) -> Result<usize, PipelineError<S::Error, Ser::Error>>
+    where
+        Ser: TripleSerializer,
+    {
+        let mut triples = 0;
+        let result = serializer
+            .serialize_triples(&mut self.source.filter_triples(|_| {
+                triples += 1;
+                true
+            }))
+            .map(|_| ());
+        match result {
+            Ok(()) => Ok(triples),
+            Err(error) => Err(PipelineError { triples, error }),
+        }
+    }
+}
+
+/// The error raised by [`Pipeline::serialize_into`](./struct.Pipeline.html#method.serialize_into).
+#[derive(Debug, thiserror::Error)]
+#[error("Pipeline failed after {triples} triple(s): {error}")]
+pub struct PipelineError<SourceErr, SinkErr>
+where
+    SourceErr: 'static + Error,
+    SinkErr: 'static + Error,
+{
+    /// The number of triples that had reached the serializer when the error occurred
+    /// (including the one that the serializer failed to process, if it is the culprit).
+    pub triples: usize,
+    /// The underlying error, telling whether the source or the serializer failed.
+    #[source]
+    pub error: StreamError<SourceErr, SinkErr>,
+}
@@ -109,6 +109,26 @@ fn in_graph() {
     }
 }
 
+#[test]
+fn collect_triples() {
+    use crate::parser::nt;
+
+    let nt = r#"
+        <http://example.org/alice> <http://example.org/name> "Alice" .
+        <http://example.org/bob> <http://example.org/name> "Bob" .
+        <http://example.org/bob> <http://example.org/knows> <http://example.org/alice> .
+    "#;
+    let v = nt::parse_str(nt).collect_triples().unwrap();
+    assert_eq!(v.len(), 3);
+    assert_eq!(&v[2][2], &ALICE as &StaticTerm);
+
+    let nt = r#"
+        <http://example.org/alice> <http://example.org/name> "Alice" .
+        <http://example.org/bob> <http://example.org/name> .
+    "#;
+    assert!(nt::parse_str(nt).collect_triples().is_err());
+}
+
 #[test]
 fn filter_triples() {
     let g = make_graph();
@@ -246,6 +266,59 @@ fn map_triples_iter() {
     );
 }
 
+#[test]
+fn pipeline() {
+    use crate::parser::turtle;
+    use crate::serializer::{nt::NtSerializer, Stringifier};
+    use sophia_term::{Term, TermData};
+
+    fn rename<T: TermData>(t: &Term<T>) -> BoxTerm {
+        if t == &ALICE as &StaticTerm {
+            (&CHARLIE as &StaticTerm).into()
+        } else {
+            t.into()
+        }
+    }
+
+    let ttl = r#"
+        @prefix : <http://example.org/>.
+        :alice a :Person; :name "Alice".
+        :bob a :Person; :name "Bob"; :knows :alice.
+    "#;
+    let mut ser = NtSerializer::new_stringifier();
+    let count = Pipeline::new(turtle::parse_str(ttl))
+        .filter(|t| t.p() != &NAME as &StaticTerm)
+        .map(|t| [rename(t.s()), rename(t.p()), rename(t.o())])
+        .serialize_into(&mut ser)
+        .unwrap();
+    assert_eq!(count, 3);
+    assert_eq!(
+        ser.as_str(),
+        "<http://example.org/charlie> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .\n\
+         <http://example.org/bob> <http://www.w3.org/1999/02/22-rdf-syntax-ns#type> <http://example.org/Person> .\n\
+         <http://example.org/bob> <http://example.org/knows> <http://example.org/charlie> .\n"
+    );
+}
+
+#[test]
+fn pipeline_error() {
+    use crate::parser::turtle;
+    use crate::serializer::nt::NtSerializer;
+
+    let ttl = r#"
+        @prefix : <http://example.org/>.
+        :alice a :Person.
+        :bob a :Person.
+        :charlie a
+    "#;
+    let mut ser = NtSerializer::new_stringifier();
+    let err = Pipeline::new(turtle::parse_str(ttl))
+        .serialize_into(&mut ser)
+        .unwrap_err();
+    assert_eq!(err.triples, 2);
+    assert!(matches!(err.error, SourceError(_)));
+}
+
 // TODO: Requires implementation of `resolve_triples()`.
 // fn make_ref_graph() -> Vec<[Term<String>; 3]> {
 //     vec![
@@ -0,0 +1,196 @@
+// this module is transparently re-exported by its parent `term`
+//
+// Conversions between date/time literals and the types of the `chrono` crate.
+
+use chrono::{DateTime, FixedOffset, NaiveDate, NaiveDateTime, TimeZone};
+
+use crate::literal::{Literal, ToLiteral};
+use crate::ns::xsd;
+use crate::*;
+
+impl<T> Term<T>
+where
+    T: TermData,
+{
+    /// Return a new `xsd:dateTime` literal term representing `dt`.
+    ///
+    /// The timezone of `dt` is preserved in the lexical form
+    /// (UTC being written `Z`).
+    pub fn new_literal_datetime<Tz>(dt: &DateTime<Tz>) -> Self
+    where
+        T: From<String> + From<&'static str>,
+        Tz: TimeZone,
+        Tz::Offset: std::fmt::Display,
+    {
+        dt.to_term()
+    }
+
+    /// Return a new `xsd:date` literal term representing `d`.
+    pub fn new_literal_date(d: NaiveDate) -> Self
+    where
+        T: From<String> + From<&'static str>,
+    {
+        d.to_term()
+    }
+
+    /// If this term is an `xsd:dateTime` literal with a valid lexical form,
+    /// return its value.
+    ///
+    /// An explicit timezone (`Z` or `±hh:mm`) is kept as the offset of the result;
+    /// a lexical form without timezone is interpreted as UTC.
+    ///
+    /// Return `None` if this term is not a literal,
+    /// if its datatype is not `xsd:dateTime`,
+    /// or if its lexical form is malformed.
+    pub fn as_datetime(&self) -> Option<DateTime<FixedOffset>> {
+        match self {
+            Term::Literal(lit) if xsd::dateTime == lit.dt() => parse_datetime(lit.txt().as_ref()),
+            _ => None,
+        }
+    }
+
+    /// If this term is an `xsd:date` literal with a valid lexical form,
+    /// return its value.
+    ///
+    /// The (optional) timezone of the lexical form is checked but ignored.
+    ///
+    /// Return `None` if this term is not a literal,
+    /// if its datatype is not `xsd:date`,
+    /// or if its lexical form is malformed.
+    pub fn as_date(&self) -> Option<NaiveDate> {
+        match self {
+            Term::Literal(lit) if xsd::date == lit.dt() => parse_date(lit.txt().as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl<TD> ToLiteral<TD> for NaiveDate
+where
+    TD: TermData + From<String> + From<&'static str>,
+{
+    fn to_literal(&self) -> Literal<TD> {
+        Literal::new_dt(self.format("%Y-%m-%d").to_string(), &xsd::iri::date)
+    }
+}
+
+/// Split an XSD date/time lexical form into its main part and its (optional) timezone.
+///
+/// Return `None` if the timezone looks like one (`±..:..`) but is malformed.
+fn split_timezone(txt: &str) -> Option<(&str, Option<FixedOffset>)> {
+    if let Some(main) = txt.strip_suffix('Z') {
+        return Some((main, FixedOffset::east_opt(0)));
+    }
+    if txt.len() > 6 && txt.is_char_boundary(txt.len() - 6) {
+        let (main, tz) = txt.split_at(txt.len() - 6);
+        let tzb = tz.as_bytes();
+        if (tzb[0] == b'+' || tzb[0] == b'-') && tzb[3] == b':' {
+            let hh: i32 = tz[1..3].parse().ok()?;
+            let mm: i32 = tz[4..6].parse().ok()?;
+            if hh > 14 || mm > 59 || (hh == 14 && mm > 0) {
+                return None;
+            }
+            let secs = (hh * 60 + mm) * 60;
+            let offset = if tzb[0] == b'+' {
+                FixedOffset::east_opt(secs)
+            } else {
+                FixedOffset::west_opt(secs)
+            };
+            return Some((main, Some(offset?)));
+        }
+    }
+    Some((txt, None))
+}
+
+fn parse_datetime(txt: &str) -> Option<DateTime<FixedOffset>> {
+    let (main, tz) = split_timezone(txt)?;
+    if main.contains(char::is_whitespace) {
+        return None;
+    }
+    let naive = NaiveDateTime::parse_from_str(main, "%Y-%m-%dT%H:%M:%S%.f").ok()?;
+    let tz = tz.or_else(|| FixedOffset::east_opt(0))?;
+    tz.from_local_datetime(&naive).single()
+}
+
+fn parse_date(txt: &str) -> Option<NaiveDate> {
+    let (main, _) = split_timezone(txt)?;
+    if main.contains(char::is_whitespace) {
+        return None;
+    }
+    NaiveDate::parse_from_str(main, "%Y-%m-%d").ok()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use chrono::Utc;
+
+    #[test]
+    fn datetime_round_trip() {
+        let tz = FixedOffset::east_opt(2 * 3600).unwrap();
+        let naive = NaiveDate::from_ymd_opt(2020, 3, 14)
+            .unwrap()
+            .and_hms_milli_opt(15, 9, 26, 535)
+            .unwrap();
+        let dt = tz.from_local_datetime(&naive).unwrap();
+        let t = BoxTerm::new_literal_datetime(&dt);
+        assert_eq!(t.value(), "2020-03-14T15:09:26.535+02:00");
+        let got = t.as_datetime().unwrap();
+        assert_eq!(got, dt);
+        assert_eq!(got.offset(), &tz);
+
+        let utc = Utc.from_utc_datetime(&naive);
+        let t = BoxTerm::new_literal_datetime(&utc);
+        assert_eq!(t.value(), "2020-03-14T15:09:26.535Z");
+        assert_eq!(t.as_datetime().unwrap(), utc);
+    }
+
+    #[test]
+    fn datetime_without_timezone_is_utc() {
+        let t = RefTerm::new_literal_dt("2020-03-14T15:09:26", xsd::dateTime).unwrap();
+        let got = t.as_datetime().unwrap();
+        assert_eq!(got.offset().local_minus_utc(), 0);
+        assert_eq!(got.naive_utc().to_string(), "2020-03-14 15:09:26");
+    }
+
+    #[test]
+    fn date_round_trip() {
+        let d = NaiveDate::from_ymd_opt(2020, 2, 29).unwrap();
+        let t = BoxTerm::new_literal_date(d);
+        assert_eq!(t.value(), "2020-02-29");
+        assert_eq!(t.as_date(), Some(d));
+
+        let t = RefTerm::new_literal_dt("2020-02-29-05:00", xsd::date).unwrap();
+        assert_eq!(t.as_date(), Some(d));
+    }
+
+    #[test]
+    fn malformed_are_rejected() {
+        for txt in &[
+            "2020-13-01T00:00:00Z",
+            "2020-03-14 15:09:26Z",
+            "2020-03-14T15:09:26+25:00",
+            "2020-03-14T15:09:26+0200",
+            "2020-03-14",
+            "not a date",
+        ] {
+            let t = RefTerm::new_literal_dt(*txt, xsd::dateTime).unwrap();
+            assert!(t.as_datetime().is_none(), "{}", txt);
+        }
+        for txt in &["2019-02-29", "2020-03-14T00:00:00", "20200314"] {
+            let t = RefTerm::new_literal_dt(*txt, xsd::date).unwrap();
+            assert!(t.as_date().is_none(), "{}", txt);
+        }
+    }
+
+    #[test]
+    fn wrong_datatype_or_kind() {
+        let t = RefTerm::new_literal_dt("2020-03-14T15:09:26Z", xsd::string).unwrap();
+        assert!(t.as_datetime().is_none());
+        let t = RefTerm::new_literal_dt("2020-03-14", xsd::dateTime).unwrap();
+        assert!(t.as_date().is_none());
+        let t = RefTerm::new_iri("http://example.org/").unwrap();
+        assert!(t.as_datetime().is_none());
+        assert!(t.as_date().is_none());
+    }
+}
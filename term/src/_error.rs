@@ -14,6 +14,14 @@ pub enum TermError {
     /// The IRI of a term must apply to [RFC 3987](https://tools.ietf.org/html/rfc3987).
     #[error("The given IRI '{0}' is not valid according to RFC3987")]
     InvalidIri(String),
+    /// An IRI was rejected for a reason other than not being valid according to RFC3987.
+    #[error("The given IRI '{iri}' was rejected: {reason}")]
+    RejectedIri {
+        /// The faulty IRI.
+        iri: String,
+        /// Why `iri` was rejected.
+        reason: String,
+    },
     /// An IRI must be represented by one `TermData` to be able to parse its components.
     #[error("IRI components could not be parsed in one as it has a suffix")]
     IriParse,
@@ -43,6 +51,12 @@ pub enum TermError {
         "The identifier '{0}' is not valid for a blank node according to the N3 specification"
     )]
     InvalidBlankNodeId(String),
+    /// Raised when expanding a CURIE whose prefix is not known.
+    #[error("The prefix '{0}' is not defined")]
+    UnknownPrefix(String),
+    /// Raised when expanding a CURIE that has no prefix.
+    #[error("The given CURIE '{0}' has no prefix")]
+    InvalidCurie(String),
     /// Raised when failing to downcast a term.
     #[error("The term '{term}' is not the expected {expect}")]
     UnexpectedKindOfTerm {
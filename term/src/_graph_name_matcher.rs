@@ -24,6 +24,13 @@ pub trait GraphNameMatcher {
     fn matches<T>(&self, g: Option<&Term<T>>) -> bool
     where
         T: TermData;
+
+    /// Whether this matcher matches only one graph name,
+    /// possibly the default graph
+    /// (see [`constant`](#tymethod.constant)).
+    fn is_bound(&self) -> bool {
+        self.constant().is_some()
+    }
 }
 
 impl GraphNameMatcher for AnyTerm {
@@ -200,6 +207,15 @@ where
     }
 }
 
+/// Any closure taking an optional `RefTerm` can be used as a graph name matcher,
+/// e.g. to match all graph names starting with a given prefix:
+/// ```
+/// # use sophia_term::{RefTerm, matcher::GraphNameMatcher};
+/// let m = |g: Option<&RefTerm>| g.map_or(false, |t| t.value().starts_with("http://ex/g/"));
+/// assert!(m.matches(Some(&RefTerm::new_iri("http://ex/g/v1").unwrap())));
+/// assert!(!m.matches(None::<&RefTerm>));
+/// ```
+/// Note that the type of the closure parameter must be explicitly given.
 impl<F: Fn(Option<&Term<&str>>) -> bool> GraphNameMatcher for F {
     type TermData = &'static str;
     fn constant(&self) -> Option<Option<&Term<Self::TermData>>> {
@@ -219,6 +235,19 @@ mod test {
     use super::*;
 
     use crate::matcher::{AnyOrExactly, ANY};
+    #[test]
+    fn test_is_bound() {
+        let g = Some(BoxTerm::new_iri("http://champin.net/#pa").unwrap());
+        let dg: Option<BoxTerm> = None;
+        assert!(!GraphNameMatcher::is_bound(&ANY));
+        assert!(GraphNameMatcher::is_bound(&g));
+        assert!(GraphNameMatcher::is_bound(&dg));
+        assert!(GraphNameMatcher::is_bound(&AnyOrExactly::Exactly(g)));
+        assert!(!GraphNameMatcher::is_bound(
+            &AnyOrExactly::<Option<BoxTerm>>::Any
+        ));
+    }
+
     #[test]
     fn test_option_ref_term_as_matcher() {
         let m = Some(BoxTerm::new_iri("http://champin.net/#pa").unwrap());
@@ -407,4 +436,20 @@ mod test {
         assert!(m.matches(n1.as_ref()));
         assert!(!m.matches(n2.as_ref()));
     }
+
+    #[test]
+    fn test_func_prefix_as_matcher() {
+        let m = |g: Option<&RefTerm>| g.is_some_and(|t| t.value().starts_with("http://ex/g/"));
+
+        let n0: Option<RcTerm> = None;
+        let n1 = Some(RcTerm::new_iri("http://ex/g/v1").unwrap());
+        let n2 = Some(RcTerm::new_iri_suffixed("http://ex/g/", "v2").unwrap());
+        let n3 = Some(RcTerm::new_iri("http://ex/h/v1").unwrap());
+
+        assert!(GraphNameMatcher::constant(&m).is_none());
+        assert!(!m.matches(n0.as_ref()));
+        assert!(m.matches(n1.as_ref()));
+        assert!(m.matches(n2.as_ref()));
+        assert!(!m.matches(n3.as_ref()));
+    }
 }
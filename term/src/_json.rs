@@ -0,0 +1,118 @@
+// this module is transparently re-exported by its parent `term`
+//
+// Conversion of terms to JSON values (see `serde_json`).
+
+use serde_json::{json, Number, Value};
+
+use crate::literal::INTEGER_DATATYPES;
+use crate::ns::xsd;
+use crate::*;
+
+impl<T> Term<T>
+where
+    T: TermData,
+{
+    /// Convert this term to a JSON value.
+    ///
+    /// * literals of type `xsd:integer` (or derived from it), `xsd:double` and `xsd:float`
+    ///   become JSON numbers,
+    ///   literals of type `xsd:boolean` become JSON booleans,
+    ///   and other datatyped literals become JSON strings
+    ///   (so do numeric and boolean literals with an invalid or out-of-range lexical form);
+    ///   in particular, `xsd:decimal` literals keep their lexical form,
+    ///   as converting them to JSON numbers could lose precision;
+    /// * language-tagged literals become objects `{"@value": ..., "@language": ...}`;
+    /// * IRIs become objects `{"@id": ...}`,
+    ///   as well as blank nodes (with an `_:` prefix);
+    /// * variables become objects `{"@variable": ...}`.
+    pub fn to_json_value(&self) -> Value {
+        match self {
+            Term::Iri(iri) => json!({ "@id": iri.value().as_ref() }),
+            Term::BNode(bn) => json!({ "@id": format!("_:{}", bn.value()) }),
+            Term::Variable(var) => json!({ "@variable": var.value().as_ref() }),
+            Term::Literal(lit) => {
+                let txt = lit.txt().as_ref();
+                if let Some(tag) = lit.lang() {
+                    return json!({ "@value": txt, "@language": tag.as_ref() });
+                }
+                let dt = lit.dt();
+                let scalar = if INTEGER_DATATYPES.iter().any(|i| **i == dt) {
+                    txt.trim().parse::<i64>().ok().map(Value::from)
+                } else if FLOAT_TYPES.iter().any(|i| **i == dt) {
+                    txt.trim()
+                        .parse::<f64>()
+                        .ok()
+                        .and_then(Number::from_f64)
+                        .map(Value::Number)
+                } else if xsd::iri::boolean == dt {
+                    match txt.trim() {
+                        "true" | "1" => Some(Value::Bool(true)),
+                        "false" | "0" => Some(Value::Bool(false)),
+                        _ => None,
+                    }
+                } else {
+                    None
+                };
+                scalar.unwrap_or_else(|| Value::String(txt.to_string()))
+            }
+        }
+    }
+}
+
+static FLOAT_TYPES: [&iri::Iri<&str>; 2] = [&xsd::iri::double, &xsd::iri::float];
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn scalars() {
+        let t = RefTerm::new_literal_dt("42", xsd::integer).unwrap();
+        assert_eq!(t.to_json_value(), json!(42));
+        let t = RefTerm::new_literal_dt("-7", xsd::short).unwrap();
+        assert_eq!(t.to_json_value(), json!(-7));
+        let t = RefTerm::new_literal_dt("true", xsd::boolean).unwrap();
+        assert_eq!(t.to_json_value(), json!(true));
+        let t = RefTerm::new_literal_dt("foo", xsd::string).unwrap();
+        assert_eq!(t.to_json_value(), json!("foo"));
+        let t = RefTerm::new_literal_dt("1.5", xsd::double).unwrap();
+        assert_eq!(t.to_json_value(), json!(1.5));
+        let t = RefTerm::new_literal_dt("0", xsd::boolean).unwrap();
+        assert_eq!(t.to_json_value(), json!(false));
+    }
+
+    #[test]
+    fn decimals_keep_their_lexical_form() {
+        let t = RefTerm::new_literal_dt("12345678901234567890.123456789", xsd::decimal).unwrap();
+        assert_eq!(t.to_json_value(), json!("12345678901234567890.123456789"));
+        let t = RefTerm::new_literal_dt("1.50", xsd::decimal).unwrap();
+        assert_eq!(t.to_json_value(), json!("1.50"));
+        let t = RefTerm::new_literal_dt("1e3", xsd::decimal).unwrap();
+        assert_eq!(t.to_json_value(), json!("1e3"));
+    }
+
+    #[test]
+    fn non_scalars() {
+        let t = RefTerm::new_iri("http://example.org/").unwrap();
+        assert_eq!(t.to_json_value(), json!({"@id": "http://example.org/"}));
+        let t = RefTerm::new_bnode("b1").unwrap();
+        assert_eq!(t.to_json_value(), json!({"@id": "_:b1"}));
+        let t = RefTerm::new_literal_lang("chat", "fr").unwrap();
+        assert_eq!(
+            t.to_json_value(),
+            json!({"@value": "chat", "@language": "fr"})
+        );
+        let t = RefTerm::new_variable("x").unwrap();
+        assert_eq!(t.to_json_value(), json!({"@variable": "x"}));
+    }
+
+    #[test]
+    fn invalid_or_overflowing_fall_back_to_string() {
+        let t = RefTerm::new_literal_dt("99999999999999999999", xsd::integer).unwrap();
+        assert_eq!(t.to_json_value(), json!("99999999999999999999"));
+        let t = RefTerm::new_literal_dt("INF", xsd::double).unwrap();
+        assert_eq!(t.to_json_value(), json!("INF"));
+        let t = RefTerm::new_literal_dt("yes", xsd::boolean).unwrap();
+        assert_eq!(t.to_json_value(), json!("yes"));
+    }
+}
@@ -1,10 +1,11 @@
 //! Blank node like specified in [RDF](https://www.w3.org/TR/rdf11-primer/#section-blank-node).
 //!
 
-use super::{Result, Term, TermData, TermError};
+use super::{same_txt, Result, Term, TermData, TermError};
 use crate::mown_str::MownStr;
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt;
 use std::io;
@@ -176,7 +177,26 @@ where
     U: TermData,
 {
     fn eq(&self, other: &BlankNode<U>) -> bool {
-        self.as_ref() == other.as_ref()
+        same_txt(self.as_ref(), other.as_ref())
+    }
+}
+
+impl<T, U> PartialOrd<BlankNode<U>> for BlankNode<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn partial_cmp(&self, other: &BlankNode<U>) -> Option<Ordering> {
+        Some(self.as_ref().cmp(other.as_ref()))
+    }
+}
+
+impl<TD> Ord for BlankNode<TD>
+where
+    TD: TermData,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(other.as_ref())
     }
 }
 
@@ -107,6 +107,70 @@ pub trait TermFactory {
     fn shrink_to_fit(&mut self);
 }
 
+/// An object-safe version of [`TermFactory`](trait.TermFactory.html),
+/// allowing to choose a factory at runtime (as a `Box<dyn DynTermFactory>`).
+///
+/// It is implemented by all implementations of `TermFactory`.
+/// Since the `TermData` of the factory can not be exposed,
+/// terms are returned as `BoxTerm`s.
+pub trait DynTermFactory {
+    /// Get a new IRI without suffix.
+    fn make_iri(&mut self, iri: &str) -> Result<BoxTerm>;
+
+    /// Get a new suffixed IRI.
+    fn make_iri2(&mut self, ns: &str, suffix: &str) -> Result<BoxTerm>;
+
+    /// Get a new blank node.
+    fn make_bnode(&mut self, id: &str) -> Result<BoxTerm>;
+
+    /// Get a new language-tagged literal.
+    fn make_literal_lang(&mut self, txt: &str, lang: &str) -> Result<BoxTerm>;
+
+    /// Get a new typed literal.
+    fn make_literal_dt(&mut self, txt: &str, dt: &str) -> Result<BoxTerm>;
+
+    /// Get a new variable.
+    fn make_variable(&mut self, name: &str) -> Result<BoxTerm>;
+
+    /// Release memory that the factory no longer uses
+    /// (see [`TermFactory::shrink_to_fit`](trait.TermFactory.html#tymethod.shrink_to_fit)).
+    fn dyn_shrink_to_fit(&mut self);
+}
+
+impl<F> DynTermFactory for F
+where
+    F: TermFactory,
+{
+    fn make_iri(&mut self, iri: &str) -> Result<BoxTerm> {
+        self.iri(iri).map(|t| BoxTerm::from(&t))
+    }
+
+    fn make_iri2(&mut self, ns: &str, suffix: &str) -> Result<BoxTerm> {
+        self.iri2(ns, suffix).map(|t| BoxTerm::from(&t))
+    }
+
+    fn make_bnode(&mut self, id: &str) -> Result<BoxTerm> {
+        self.bnode(id).map(|t| BoxTerm::from(&t))
+    }
+
+    fn make_literal_lang(&mut self, txt: &str, lang: &str) -> Result<BoxTerm> {
+        self.literal_lang(txt, lang).map(|t| BoxTerm::from(&t))
+    }
+
+    fn make_literal_dt(&mut self, txt: &str, dt: &str) -> Result<BoxTerm> {
+        let dt = RefTerm::new_iri(dt)?;
+        self.literal_dt(txt, dt).map(|t| BoxTerm::from(&t))
+    }
+
+    fn make_variable(&mut self, name: &str) -> Result<BoxTerm> {
+        self.variable(name).map(|t| BoxTerm::from(&t))
+    }
+
+    fn dyn_shrink_to_fit(&mut self) {
+        self.shrink_to_fit()
+    }
+}
+
 /// A `TermFactory` ref-counting the data given out.
 pub type RcTermFactory = WeakHashSet<rc::Weak<str>>;
 
@@ -151,5 +215,32 @@ impl TermFactory for ArcTermFactory {
 
 #[cfg(test)]
 mod test {
-    // Nothing really worth testing here
+    use super::*;
+    use crate::ns::xsd;
+
+    #[test]
+    fn dyn_term_factory() {
+        let factories: Vec<Box<dyn DynTermFactory>> = vec![
+            Box::new(RcTermFactory::default()),
+            Box::new(ArcTermFactory::default()),
+        ];
+        for mut f in factories {
+            let iri = f.make_iri("http://example.org/foo").unwrap();
+            assert_eq!(iri, BoxTerm::new_iri("http://example.org/foo").unwrap());
+            let iri2 = f.make_iri2("http://example.org/", "foo").unwrap();
+            assert_eq!(iri2, iri);
+            let bnode = f.make_bnode("b").unwrap();
+            assert_eq!(bnode, BoxTerm::new_bnode("b").unwrap());
+            let lit = f.make_literal_lang("chat", "fr").unwrap();
+            assert_eq!(lit, BoxTerm::new_literal_lang("chat", "fr").unwrap());
+            let lit = f
+                .make_literal_dt("42", "http://www.w3.org/2001/XMLSchema#integer")
+                .unwrap();
+            assert_eq!(lit, BoxTerm::new_literal_dt("42", &xsd::integer).unwrap());
+            let var = f.make_variable("x").unwrap();
+            assert_eq!(var, BoxTerm::new_variable("x").unwrap());
+            assert!(f.make_iri("not an IRI").is_err());
+            f.dyn_shrink_to_fit();
+        }
+    }
 }
@@ -0,0 +1,121 @@
+//! An interning table for the text of terms.
+//!
+//! Like [`RcTermFactory`](../factory/type.RcTermFactory.html)
+//! (used by [`FastGraph`] and [`LightGraph`]),
+//! an [`Interner`](struct.Interner.html) ensures that equal strings
+//! share the same `Rc<str>`.
+//! In addition, it assigns to each string a numeric identifier,
+//! which makes it suitable for building external indexes.
+//!
+//! [`FastGraph`]: https://docs.rs/sophia/latest/sophia/graph/inmem/type.FastGraph.html
+//! [`LightGraph`]: https://docs.rs/sophia/latest/sophia/graph/inmem/type.LightGraph.html
+
+use std::collections::HashMap;
+use std::rc::Rc;
+
+use crate::factory::TermFactory;
+
+/// An interning table, associating each distinct string to an `Rc<str>` and an identifier.
+///
+/// Identifiers are allocated sequentially, starting from 0.
+/// Contrarily to [`RcTermFactory`](../factory/type.RcTermFactory.html),
+/// an `Interner` never forgets a string,
+/// so that identifiers remain valid for the whole life of the table.
+///
+/// `Interner` also implements [`TermFactory`](../factory/trait.TermFactory.html),
+/// so it can be used to create terms.
+#[derive(Clone, Debug, Default)]
+pub struct Interner {
+    strings: Vec<Rc<str>>,
+    ids: HashMap<Rc<str>, usize>,
+}
+
+impl Interner {
+    /// Build an empty table.
+    pub fn new() -> Interner {
+        Interner::default()
+    }
+
+    /// Return the shared `Rc<str>` equal to `txt`, adding it to the table if required.
+    pub fn intern(&mut self, txt: &str) -> Rc<str> {
+        let id = self.intern_id(txt);
+        self.strings[id].clone()
+    }
+
+    /// Return the identifier of `txt`, adding it to the table if required.
+    pub fn intern_id(&mut self, txt: &str) -> usize {
+        if let Some(id) = self.ids.get(txt) {
+            return *id;
+        }
+        let rc: Rc<str> = Rc::from(txt);
+        let id = self.strings.len();
+        self.strings.push(rc.clone());
+        self.ids.insert(rc, id);
+        id
+    }
+
+    /// Return the identifier of `txt`, if it is in the table.
+    pub fn get_id(&self, txt: &str) -> Option<usize> {
+        self.ids.get(txt).cloned()
+    }
+
+    /// Return the string identified by `id`, if any.
+    pub fn resolve(&self, id: usize) -> Option<&str> {
+        self.strings.get(id).map(|rc| rc.as_ref())
+    }
+
+    /// The number of strings in this table.
+    pub fn len(&self) -> usize {
+        self.strings.len()
+    }
+
+    /// Whether this table is empty.
+    pub fn is_empty(&self) -> bool {
+        self.strings.is_empty()
+    }
+}
+
+impl TermFactory for Interner {
+    type TermData = Rc<str>;
+
+    fn get_term_data(&mut self, txt: &str) -> Rc<str> {
+        self.intern(txt)
+    }
+
+    fn shrink_to_fit(&mut self) {
+        self.strings.shrink_to_fit();
+        self.ids.shrink_to_fit();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn intern_and_resolve() {
+        let mut i = Interner::new();
+        let a1 = i.intern("a");
+        let b = i.intern("b");
+        let a2 = i.intern(&String::from("a"));
+        assert!(Rc::ptr_eq(&a1, &a2));
+        assert!(!Rc::ptr_eq(&a1, &b));
+        assert_eq!(i.len(), 2);
+
+        let id = i.get_id("b").unwrap();
+        assert_eq!(i.resolve(id), Some("b"));
+        assert_eq!(i.intern_id("a"), i.get_id("a").unwrap());
+        assert_eq!(i.get_id("c"), None);
+        assert_eq!(i.resolve(42), None);
+    }
+
+    #[test]
+    fn as_term_factory() {
+        let mut i = Interner::new();
+        let t1 = i.iri("http://example.org/").unwrap();
+        let t2 = i.iri("http://example.org/").unwrap();
+        assert_eq!(t1, t2);
+        assert_eq!(i.len(), 1);
+        assert_eq!(i.resolve(0), Some("http://example.org/"));
+    }
+}
@@ -22,6 +22,8 @@ pub use self::_join::*;
 
 use super::{Result, Term, TermData, TermError};
 use crate::mown_str::MownStr;
+use std::borrow::Cow;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt;
 use std::hash::{Hash, Hasher};
@@ -370,6 +372,20 @@ where
         }
     }
 
+    /// Checks if the IRI is equal to `other`, ignoring their fragment identifiers,
+    /// i.e. if both IRIs identify resources described by the same document.
+    ///
+    /// The fragment identifier is everything after the first `#` (if any).
+    /// No normalization is performed on either IRI.
+    pub fn eq_ignoring_fragment<U>(&self, other: &Iri<U>) -> bool
+    where
+        U: TermData,
+    {
+        self.bytes()
+            .take_while(|b| *b != b'#')
+            .eq(other.bytes().take_while(|b| *b != b'#'))
+    }
+
     /// Parses the components of the IRI.
     ///
     /// This is necessary to use the IRI as a base to resolve other IRIs.
@@ -431,9 +447,23 @@ where
 
     /// Return this IRI as text.
     pub fn value(&self) -> MownStr {
+        self.to_string_value().into()
+    }
+
+    /// Return this IRI as text.
+    ///
+    /// The text is borrowed if this IRI is not suffixed;
+    /// otherwise, it is allocated once, with the exact required capacity.
+    pub fn to_string_value(&self) -> Cow<'_, str> {
         match &self.suffix {
-            None => self.ns.as_ref().into(),
-            Some(s) => format!("{}{}", self.ns.as_ref(), s.as_ref()).into(),
+            None => Cow::Borrowed(self.ns.as_ref()),
+            Some(s) => {
+                let (ns, s) = (self.ns.as_ref(), s.as_ref());
+                let mut txt = String::with_capacity(ns.len() + s.len());
+                txt.push_str(ns);
+                txt.push_str(s);
+                Cow::Owned(txt)
+            }
         }
     }
 
@@ -483,7 +513,56 @@ where
     U: TermData,
 {
     fn eq(&self, other: &Iri<U>) -> bool {
-        self.len() == other.len() && !self.bytes().zip(other.bytes()).any(|(bs, bo)| bs != bo)
+        // fast path for IRIs sharing their data (e.g. produced by the same factory)
+        let shared = std::ptr::eq(self.ns.as_ref(), other.ns.as_ref())
+            && match (&self.suffix, &other.suffix) {
+                (None, None) => true,
+                (Some(s1), Some(s2)) => std::ptr::eq(s1.as_ref(), s2.as_ref()),
+                _ => false,
+            };
+        shared
+            || (self.len() == other.len()
+                && eq_segments(
+                    self.ns.as_ref().as_bytes(),
+                    self.suffix_as_str().as_bytes(),
+                    other.ns.as_ref().as_bytes(),
+                    other.suffix_as_str().as_bytes(),
+                ))
+    }
+}
+
+/// Whether `a1 + a2` equals `b1 + b2`, comparing slices without concatenating them.
+///
+/// # Pre-condition
+///
+/// Both concatenations must have the same length.
+fn eq_segments(a1: &[u8], a2: &[u8], b1: &[u8], b2: &[u8]) -> bool {
+    if a1.len() > b1.len() {
+        return eq_segments(b1, b2, a1, a2);
+    }
+    // a1 is the shortest first segment, so the start of a2 is aligned with the end of b1
+    let (b1_head, b1_tail) = b1.split_at(a1.len());
+    let (a2_head, a2_tail) = a2.split_at(b1_tail.len());
+    a1 == b1_head && a2_head == b1_tail && a2_tail == b2
+}
+
+/// IRIs are ordered by their textual value (as UTF-8 bytes).
+impl<T, U> PartialOrd<Iri<U>> for Iri<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn partial_cmp(&self, other: &Iri<U>) -> Option<Ordering> {
+        Some(self.bytes().cmp(other.bytes()))
+    }
+}
+
+impl<TD> Ord for Iri<TD>
+where
+    TD: TermData,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.bytes().cmp(other.bytes())
     }
 }
 
@@ -492,15 +571,13 @@ where
     TD: TermData,
 {
     fn eq(&self, other: &str) -> bool {
-        let ns = self.ns.as_ref();
-        let sf = self.suffix_as_str();
-        let ns_len = ns.len();
-        let sf_len = sf.len();
-
-        ns_len + sf_len == other.len()
-            && ns == &other[..ns_len]
-            // prevents panic if not suffixed
-            && (sf.is_empty() || sf == &other[ns_len..])
+        self.len() == other.len()
+            && eq_segments(
+                self.ns.as_ref().as_bytes(),
+                self.suffix_as_str().as_bytes(),
+                other.as_bytes(),
+                &[],
+            )
     }
 }
 
@@ -625,6 +702,59 @@ mod test {
         iri.match_ns(&ns).map(|chars| chars.collect())
     }
 
+    #[test_case("http://example.org/#a", "http://example.org/#b" => true ; "different fragments")]
+    #[test_case("http://example.org/", "http://example.org/#b" => true ; "one fragment")]
+    #[test_case("http://example.org/a#c", "http://example.org/b#c" => false ; "different paths")]
+    #[test_case("http://example.org/?q#a", "http://example.org/#a" => false ; "different queries")]
+    fn eq_ignoring_fragment(iri1: &str, iri2: &str) -> bool {
+        let iri1: Iri<&str> = Iri::new(iri1).unwrap();
+        let iri2: Iri<&str> = Iri::new(iri2).unwrap();
+        iri1.eq_ignoring_fragment(&iri2)
+    }
+
+    #[test]
+    fn eq_ignoring_fragment_suffixed() {
+        let iri1 = Iri::<&str>::new_suffixed("http://example.org/doc#", "a").unwrap();
+        let iri2 = Iri::<Box<str>>::new_suffixed("http://example.org/", "doc#b").unwrap();
+        assert!(iri1.eq_ignoring_fragment(&iri2));
+        assert!(iri1 != iri2);
+    }
+
+    #[test]
+    fn to_string_value() {
+        let iri = Iri::<&str>::new("http://example.org/foo").unwrap();
+        let txt = iri.to_string_value();
+        assert_eq!(txt, "http://example.org/foo");
+        assert!(matches!(txt, Cow::Borrowed(_)));
+
+        let iri = Iri::<Box<str>>::new_suffixed("http://example.org/", "foo").unwrap();
+        let txt = iri.to_string_value();
+        assert_eq!(txt, "http://example.org/foo");
+        assert!(matches!(&txt, Cow::Owned(s) if s.capacity() == s.len()));
+    }
+
+    #[test]
+    fn eq_all_splits() {
+        let txt = "http://example.org/é#ü";
+        let other = "http://example.org/é#ö";
+        let boundaries: Vec<_> = (0..=txt.len())
+            .filter(|i| txt.is_char_boundary(*i))
+            .collect();
+        let split =
+            |txt: &'static str, i: usize| Iri::<&str>::new_suffixed(&txt[..i], &txt[i..]).unwrap();
+        for i in boundaries.iter().cloned() {
+            let iri1 = split(txt, i);
+            assert_eq!(iri1, Iri::<&str>::new(txt).unwrap());
+            assert!(iri1 == *txt);
+            assert!(iri1 != *other);
+            for j in boundaries.iter().cloned() {
+                let iri2 = split(txt, j);
+                assert_eq!(iri1, iri2, "splits {} and {}", i, j);
+                assert_ne!(iri1, split(other, j), "splits {} and {}", i, j);
+            }
+        }
+    }
+
     #[test]
     fn convert_to_mown_does_not_allocate() {
         use crate::mown_str::MownStr;
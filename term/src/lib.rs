@@ -46,6 +46,7 @@
 
 #![deny(missing_docs)]
 
+use std::cmp::Ordering;
 use std::convert::TryInto;
 use std::fmt::Debug;
 use std::hash::Hash;
@@ -54,8 +55,10 @@ use std::sync::Arc;
 
 pub mod factory;
 pub mod index_map;
+pub mod intern;
 pub mod matcher;
 pub mod ns;
+pub mod set;
 
 pub mod variable;
 use self::variable::Variable;
@@ -68,9 +71,13 @@ use self::literal::{AsLiteral, Literal};
 pub mod mown_str;
 use self::mown_str::MownStr;
 
+#[cfg(feature = "chrono")]
+mod _chrono;
 mod _display;
 mod _error;
 mod _graph_name_matcher; // is 'pub use'd by module 'matcher'
+#[cfg(feature = "serde")]
+mod _json;
 pub use self::_error::*;
 
 /// Generic type for RDF terms.
@@ -98,6 +105,15 @@ where
 pub trait TermData: AsRef<str> + Clone + Eq + Hash {}
 impl<T> TermData for T where T: AsRef<str> + Clone + Eq + Hash {}
 
+/// Compare two texts, first checking whether they are the very same data.
+///
+/// This makes comparison cheap for terms sharing their `TermData`
+/// (typically `RcTerm`s or `ArcTerm`s produced by the same factory).
+#[inline]
+pub(crate) fn same_txt(a: &str, b: &str) -> bool {
+    std::ptr::eq(a, b) || a == b
+}
+
 /// Convenient alias for a specialization of `Term<T>`.
 ///
 /// See [module documentation](index.html)
@@ -238,6 +254,27 @@ where
         }
     }
 
+    /// Convert this term into a [`BoxTerm`](type.BoxTerm.html),
+    /// which owns its data.
+    ///
+    /// This is typically used to keep a term that borrows a short-lived buffer
+    /// (e.g. a `RefTerm` produced by a parser).
+    pub fn into_owned(self) -> BoxTerm {
+        self.clone_with(Box::<str>::from)
+    }
+
+    /// Convert this term into an [`RcTerm`](type.RcTerm.html),
+    /// which owns its data.
+    pub fn into_rc(self) -> RcTerm {
+        self.clone_with(Rc::<str>::from)
+    }
+
+    /// Convert this term into an [`ArcTerm`](type.ArcTerm.html),
+    /// which owns its data.
+    pub fn into_arc(self) -> ArcTerm {
+        self.clone_with(Arc::<str>::from)
+    }
+
     /// Create a new IRI-term from a given IRI without checking its validity.
     ///
     /// As it is not checked if absolute or relative this property must be
@@ -357,6 +394,132 @@ where
             _ => true,
         }
     }
+
+    /// Return a copy of this term where the IRI prefix `old` is replaced by `new`.
+    ///
+    /// This applies to IRIs and to the datatype of typed literals.
+    /// Return `None` if this term is neither,
+    /// if its IRI does not start with `old`,
+    /// or if the rewritten IRI is not valid.
+    pub fn replace_iri_prefix(&self, old: &str, new: &str) -> Option<BoxTerm> {
+        let rewrite = |iri: &str| -> Option<Iri<Box<str>>> {
+            let rest = iri.strip_prefix(old)?;
+            Iri::new(format!("{}{}", new, rest)).ok()
+        };
+        match self {
+            Term::Iri(iri) => rewrite(&iri.value()).map(Into::into),
+            Term::Literal(lit) if lit.lang().is_none() => {
+                let dt = rewrite(&lit.dt().value())?;
+                Some(Literal::<Box<str>>::new_dt(lit.txt().as_ref(), dt).into())
+            }
+            _ => None,
+        }
+    }
+
+    /// Return a copy of this term where the lexical form of the literal
+    /// is replaced by its canonical form.
+    ///
+    /// Return `None` if this term is not a literal,
+    /// or in any of the cases described in
+    /// [`Literal::canonicalized`](literal/struct.Literal.html#method.canonicalized).
+    pub fn canonicalized(&self) -> Option<BoxTerm> {
+        match self {
+            Term::Literal(lit) => lit.canonicalized().map(Into::into),
+            _ => None,
+        }
+    }
+
+    /// Return a copy of this term where surrounding whitespace
+    /// is removed from the lexical form of the numeric literal.
+    ///
+    /// Return `None` if this term is not a literal,
+    /// or in any of the cases described in
+    /// [`Literal::trim_numeric`](literal/struct.Literal.html#method.trim_numeric).
+    pub fn trim_numeric(&self) -> Option<BoxTerm> {
+        match self {
+            Term::Literal(lit) => lit.trim_numeric().map(Into::into),
+            _ => None,
+        }
+    }
+
+    /// Return a copy of this term where the language tag of the literal
+    /// is converted to its canonical case.
+    ///
+    /// Return `None` if this term is not a literal,
+    /// or in any of the cases described in
+    /// [`Literal::normalized_lang`](literal/struct.Literal.html#method.normalized_lang).
+    pub fn normalized_lang(&self) -> Option<BoxTerm> {
+        match self {
+            Term::Literal(lit) => lit.normalized_lang().map(Into::into),
+            _ => None,
+        }
+    }
+
+    /// Check whether this term and `other` denote the same value.
+    ///
+    /// For literals, see
+    /// [`Literal::value_eq`](literal/struct.Literal.html#method.value_eq);
+    /// other terms are compared with term equality.
+    pub fn value_eq<U>(&self, other: &Term<U>) -> bool
+    where
+        U: TermData,
+    {
+        match (self, other) {
+            (Term::Literal(lit1), Term::Literal(lit2)) => lit1.value_eq(lit2),
+            _ => self == other,
+        }
+    }
+
+    /// Return a short form of this term, at most `max_len` characters long,
+    /// suitable for logging.
+    ///
+    /// If the N-Triples form of this term is short enough, it is returned unchanged.
+    /// Otherwise, its middle is elided with `...`.
+    /// For IRIs, the scheme and authority are kept, as well as the fragment
+    /// (or the last path segment if there is no fragment),
+    /// e.g. `<http://example.org/...#frag>`,
+    /// provided that this fits in `max_len`.
+    pub fn abbreviate(&self, max_len: usize) -> String {
+        let full = self.to_string();
+        if full.chars().count() <= max_len {
+            return full;
+        }
+        if let Term::Iri(iri) = self {
+            let value = iri.value();
+            let head_end = match value.find("://") {
+                Some(i) => value[i + 3..].find('/').map_or(value.len(), |j| i + 4 + j),
+                None => value.find(':').map_or(0, |i| i + 1),
+            };
+            let tail_start = value
+                .rfind('#')
+                .or_else(|| value.trim_end_matches('/').rfind('/'))
+                .unwrap_or(0);
+            if tail_start >= head_end {
+                let abbr = format!("<{}...{}>", &value[..head_end], &value[tail_start..]);
+                if abbr.chars().count() <= max_len {
+                    return abbr;
+                }
+            }
+        }
+        elide_middle(&full, max_len)
+    }
+
+    /// Estimate the number of bytes occupied by the data backing this term,
+    /// i.e. the total length of the strings it is made of
+    /// (namespace and suffix of IRIs, identifier of blank nodes,
+    /// lexical form and language tag or datatype of literals, name of variables).
+    ///
+    /// This does not include the size of the `Term` itself,
+    /// nor the overhead of the allocator.
+    /// Strings shared with other terms are counted in full.
+    pub fn heap_size(&self) -> usize {
+        let mut size = 0;
+        self.clone_with(|txt| {
+            size += txt.len();
+            txt
+        });
+        size
+    }
 }
 
 impl<T, U> PartialEq<Term<U>> for Term<T>
@@ -377,6 +540,47 @@ where
     }
 }
 
+/// Terms are totally ordered, consistently with `PartialEq`:
+/// IRIs come first, then blank nodes, then literals, then variables.
+/// Terms of the same kind are ordered by their textual value
+/// (see the `PartialOrd` implementation of each kind for details).
+impl<T, U> PartialOrd<Term<U>> for Term<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn partial_cmp(&self, other: &Term<U>) -> Option<Ordering> {
+        use self::Term::*;
+
+        match (self, other) {
+            (Iri(iri1), Iri(iri2)) => iri1.partial_cmp(iri2),
+            (BNode(id1), BNode(id2)) => id1.partial_cmp(id2),
+            (Literal(l1), Literal(l2)) => l1.partial_cmp(l2),
+            (Variable(var1), Variable(var2)) => var1.partial_cmp(var2),
+            _ => Some(kind_rank(self).cmp(&kind_rank(other))),
+        }
+    }
+}
+
+impl<T> Ord for Term<T>
+where
+    T: TermData,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap() // partial_cmp never returns None
+    }
+}
+
+/// The rank of each kind of term in the order defined by `PartialOrd` for `Term`.
+fn kind_rank<T: TermData>(t: &Term<T>) -> u8 {
+    match t {
+        Term::Iri(_) => 0,
+        Term::BNode(_) => 1,
+        Term::Literal(_) => 2,
+        Term::Variable(_) => 3,
+    }
+}
+
 impl<T, U> PartialEq<Iri<U>> for Term<T>
 where
     T: TermData,
@@ -504,5 +708,20 @@ where
     }
 }
 
+/// Keep the first and last characters of `txt`, replacing its middle with `...`,
+/// so that the result is `max_len` characters long.
+fn elide_middle(txt: &str, max_len: usize) -> String {
+    if max_len <= 3 {
+        return txt.chars().take(max_len).collect();
+    }
+    let kept = max_len - 3;
+    let skipped = txt.chars().count() - kept;
+    let head = kept.div_ceil(2);
+    let mut res: String = txt.chars().take(head).collect();
+    res.push_str("...");
+    res.extend(txt.chars().skip(head + skipped));
+    res
+}
+
 #[cfg(test)]
 pub(crate) mod test;
@@ -5,13 +5,18 @@
 use crate::iri::Normalization;
 use crate::mown_str::MownStr;
 use crate::ns::{rdf, xsd};
-use crate::{Iri, Result, Term, TermData, TermError};
+use crate::{same_txt, Iri, Result, Term, TermData, TermError};
 use language_tag::LangTag;
+use std::cmp::Ordering;
 use std::convert::{TryFrom, TryInto};
 use std::fmt;
 use std::hash::{Hash, Hasher};
 use std::io;
 
+mod _builder;
+pub use self::_builder::*;
+mod _canonical;
+pub(crate) use self::_canonical::INTEGER_DATATYPES;
 mod _convert;
 pub use self::_convert::*;
 
@@ -331,7 +336,41 @@ where
     U: TermData,
 {
     fn eq(&self, other: &Literal<U>) -> bool {
-        self.txt.as_ref() == other.txt.as_ref() && self.kind == other.kind
+        same_txt(self.txt.as_ref(), other.txt.as_ref()) && self.kind == other.kind
+    }
+}
+
+/// Literals are ordered by their lexical value,
+/// then typed literals come before language-tagged literals;
+/// typed literals are then ordered by datatype,
+/// and language-tagged literals by tag (ignoring case, consistently with `PartialEq`).
+impl<T, U> PartialOrd<Literal<U>> for Literal<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn partial_cmp(&self, other: &Literal<U>) -> Option<Ordering> {
+        Some(self.txt.as_ref().cmp(other.txt.as_ref()).then_with(|| {
+            match (&self.kind, &other.kind) {
+                (Dt(sdt), Dt(odt)) => sdt.bytes().cmp(odt.bytes()),
+                (Dt(_), Lang(_)) => Ordering::Less,
+                (Lang(_), Dt(_)) => Ordering::Greater,
+                (Lang(stag), Lang(otag)) => {
+                    let stag = stag.as_ref().bytes().map(|b| b.to_ascii_lowercase());
+                    let otag = otag.as_ref().bytes().map(|b| b.to_ascii_lowercase());
+                    stag.cmp(otag)
+                }
+            }
+        }))
+    }
+}
+
+impl<TD> Ord for Literal<TD>
+where
+    TD: TermData,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.partial_cmp(other).unwrap() // partial_cmp never returns None
     }
 }
 
@@ -0,0 +1,116 @@
+//! Public exported by parent-module `literal`.
+//!
+
+use super::*;
+use crate::ns::xsd;
+use crate::{Iri, Result, Term, TermData, TermError};
+use std::convert::TryInto;
+
+/// A builder for typed literals sharing the same datatype,
+/// e.g. all the values of a column when importing tabular data.
+///
+/// By default, the datatype of the built literals is `xsd:string`,
+/// but it can be changed with [`default_datatype`](#method.default_datatype).
+///
+/// # Example
+/// ```
+/// # use sophia_term::literal::LiteralBuilder;
+/// # use sophia_term::ns::xsd;
+/// # use sophia_term::BoxTerm;
+/// # fn main() -> sophia_term::Result<()> {
+/// let builder = LiteralBuilder::new().default_datatype(&xsd::integer)?;
+/// let lits: Vec<BoxTerm> = ["1", "2", "3"].iter().map(|txt| builder.term(*txt)).collect();
+/// assert_eq!(lits[0], BoxTerm::new_literal_dt("1", &xsd::integer)?);
+/// # Ok(()) }
+/// ```
+#[derive(Clone, Debug)]
+pub struct LiteralBuilder {
+    datatype: Iri<Box<str>>,
+}
+
+impl LiteralBuilder {
+    /// Build a new `LiteralBuilder`, producing `xsd:string` literals.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Set the datatype of the literals built by this builder.
+    ///
+    /// # Error
+    ///
+    /// An error is raised if `dt` can not be converted to an IRI.
+    pub fn default_datatype<V>(mut self, dt: V) -> Result<Self>
+    where
+        V: TryInto<Iri<Box<str>>>,
+        TermError: From<<V as TryInto<Iri<Box<str>>>>::Error>,
+    {
+        self.datatype = dt.try_into()?;
+        Ok(self)
+    }
+
+    /// The datatype of the literals built by this builder.
+    pub fn datatype(&self) -> &Iri<Box<str>> {
+        &self.datatype
+    }
+
+    /// Build a literal with the given text and the datatype of this builder.
+    pub fn literal<'a, TD>(&'a self, txt: &'a str) -> Literal<TD>
+    where
+        TD: TermData + From<&'a str>,
+    {
+        Literal::new_dt(txt, self.datatype.clone_with(TD::from))
+    }
+
+    /// Build a literal term with the given text and the datatype of this builder.
+    pub fn term<'a, TD>(&'a self, txt: &'a str) -> Term<TD>
+    where
+        TD: TermData + From<&'a str>,
+    {
+        self.literal(txt).into()
+    }
+}
+
+impl Default for LiteralBuilder {
+    fn default() -> Self {
+        LiteralBuilder {
+            datatype: xsd::iri::string.clone_with(Box::from),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::{BoxTerm, RefTerm, StaticTerm};
+
+    #[test]
+    fn default_is_xsd_string() {
+        let builder = LiteralBuilder::new();
+        let lit: Literal<&str> = builder.literal("foo");
+        assert_eq!(lit.dt(), xsd::iri::string);
+        assert!(lit.lang().is_none());
+    }
+
+    #[test]
+    fn custom_default_datatype() -> Result<()> {
+        let builder = LiteralBuilder::new().default_datatype(&xsd::integer)?;
+        assert_eq!(builder.datatype(), &xsd::iri::integer);
+        for txt in &["1", "42", "not a number"] {
+            let lit: Literal<Box<str>> = builder.literal(txt);
+            assert_eq!(lit.dt(), xsd::iri::integer);
+            assert_eq!(lit.txt().as_ref(), *txt);
+            let term: RefTerm = builder.term(txt);
+            assert_eq!(term, StaticTerm::new_literal_dt(*txt, xsd::integer)?);
+        }
+
+        let dt = Iri::<&str>::new("http://example.org/dt")?;
+        let builder = builder.default_datatype(&dt)?;
+        let term: BoxTerm = builder.term("foo");
+        assert_eq!(term, BoxTerm::new_literal_dt("foo", &dt)?);
+
+        assert!(LiteralBuilder::new()
+            .default_datatype(&StaticTerm::new_bnode("b")?)
+            .is_err());
+        Ok(())
+    }
+}
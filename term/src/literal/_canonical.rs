@@ -0,0 +1,386 @@
+//! Canonical lexical forms and other normalizations of literals,
+//! implemented as inherent methods of `Literal`.
+//!
+
+use super::*;
+
+/// `xsd:integer` and the datatypes derived from it,
+/// all of which share the canonical mapping of `xsd:integer`.
+pub(crate) static INTEGER_DATATYPES: [&Iri<&str>; 13] = [
+    &xsd::iri::integer,
+    &xsd::iri::nonPositiveInteger,
+    &xsd::iri::negativeInteger,
+    &xsd::iri::long,
+    &xsd::iri::int,
+    &xsd::iri::short,
+    &xsd::iri::byte,
+    &xsd::iri::nonNegativeInteger,
+    &xsd::iri::unsignedLong,
+    &xsd::iri::unsignedInt,
+    &xsd::iri::unsignedShort,
+    &xsd::iri::unsignedByte,
+    &xsd::iri::positiveInteger,
+];
+
+/// Numeric datatypes other than those in `INTEGER_DATATYPES`.
+static OTHER_NUMERIC_DATATYPES: [&Iri<&str>; 3] =
+    [&xsd::iri::decimal, &xsd::iri::float, &xsd::iri::double];
+
+impl<TD> Literal<TD>
+where
+    TD: TermData,
+{
+    /// Return a copy of this literal where the lexical form is replaced by
+    /// the canonical lexical form of the same value
+    /// (e.g. `"+05"^^xsd:integer` becomes `"5"^^xsd:integer`).
+    ///
+    /// Only datatypes with a well defined canonical mapping are supported,
+    /// namely `xsd:boolean`, `xsd:decimal`, `xsd:integer`
+    /// and the datatypes derived from `xsd:integer`.
+    /// Canonical forms follow [XSD 1.1](https://www.w3.org/TR/xmlschema11-2/).
+    ///
+    /// Return `None` if the datatype is not supported,
+    /// if this literal is already canonical,
+    /// or if its lexical form is not valid for its datatype
+    /// (ill-typed literals are left untouched).
+    pub fn canonicalized(&self) -> Option<Literal<Box<str>>> {
+        if self.lang().is_some() {
+            return None;
+        }
+        let dt = self.dt();
+        let txt = self.txt().as_ref();
+        let canonical = if INTEGER_DATATYPES.iter().any(|i| dt == **i) {
+            canonical_integer(txt)?
+        } else if dt == xsd::iri::decimal {
+            canonical_decimal(txt)?
+        } else if dt == xsd::iri::boolean {
+            canonical_boolean(txt)?.to_string()
+        } else {
+            return None;
+        };
+        if canonical == txt {
+            None
+        } else {
+            Some(Literal::new_dt(canonical, &dt))
+        }
+    }
+
+    /// Return a copy of this literal where leading and trailing whitespace
+    /// are removed from the lexical form
+    /// (e.g. `" 42 "^^xsd:integer` becomes `"42"^^xsd:integer`).
+    ///
+    /// This only applies to numeric datatypes
+    /// (`xsd:decimal`, `xsd:float`, `xsd:double`, `xsd:integer`
+    /// and the datatypes derived from `xsd:integer`),
+    /// for which surrounding whitespace is insignificant
+    /// (see the `whiteSpace` facet in [XSD 1.1](https://www.w3.org/TR/xmlschema11-2/#rf-whiteSpace)).
+    /// Unlike [`canonicalized`](#method.canonicalized),
+    /// it does not check that the trimmed lexical form is valid.
+    ///
+    /// Return `None` if the datatype is not numeric,
+    /// or if the lexical form has no surrounding whitespace.
+    pub fn trim_numeric(&self) -> Option<Literal<Box<str>>> {
+        if self.lang().is_some() {
+            return None;
+        }
+        let dt = self.dt();
+        if !INTEGER_DATATYPES
+            .iter()
+            .chain(OTHER_NUMERIC_DATATYPES.iter())
+            .any(|i| dt == **i)
+        {
+            return None;
+        }
+        let txt = self.txt().as_ref();
+        let trimmed = txt.trim_matches(|c| matches!(c, ' ' | '\t' | '\r' | '\n'));
+        if trimmed.len() == txt.len() {
+            None
+        } else {
+            Some(Literal::new_dt(trimmed, &dt))
+        }
+    }
+
+    /// Return a copy of this literal where the language tag
+    /// is converted to its canonical case, as recommended by
+    /// [BCP47](https://tools.ietf.org/html/rfc5646#section-2.1.1)
+    /// (e.g. `"..."@ZH-hant-hk` becomes `"..."@zh-Hant-HK`).
+    ///
+    /// Since language tags are compared case-insensitively,
+    /// this does not change the identity of the literal, only its stored form.
+    ///
+    /// Return `None` if this literal has no language tag,
+    /// or if its language tag is already in canonical case.
+    pub fn normalized_lang(&self) -> Option<Literal<Box<str>>> {
+        let tag = self.lang()?.as_ref();
+        let normalized = canonical_lang_case(tag);
+        if normalized == tag {
+            None
+        } else {
+            Some(Literal::new_lang_unchecked(self.txt().as_ref(), normalized))
+        }
+    }
+
+    /// Check whether this literal and `other` denote the same value
+    /// (e.g. `"1.50"^^xsd:decimal` and `"1.5"^^xsd:decimal`).
+    ///
+    /// Decimals are compared through their canonical lexical form,
+    /// so the comparison is exact, whatever their precision.
+    /// As `xsd:integer` and its derived datatypes are derived from `xsd:decimal`,
+    /// they share its value space (e.g. `"2"^^xsd:integer` and `"2.0"^^xsd:decimal` are value-equal).
+    /// Booleans are also supported.
+    ///
+    /// For other datatypes, or if either literal is ill-typed,
+    /// this falls back to literal equality.
+    pub fn value_eq<U>(&self, other: &Literal<U>) -> bool
+    where
+        U: TermData,
+    {
+        match (self.value_key(), other.value_key()) {
+            (Some(k1), Some(k2)) => k1 == k2,
+            _ => self == other,
+        }
+    }
+
+    /// The value space and the canonical lexical form of this literal,
+    /// if its datatype is supported by [`value_eq`](#method.value_eq).
+    fn value_key(&self) -> Option<(ValueSpace, String)> {
+        if self.lang().is_some() {
+            return None;
+        }
+        let dt = self.dt();
+        let txt = self
+            .txt()
+            .as_ref()
+            .trim_matches(|c| matches!(c, ' ' | '\t' | '\r' | '\n'));
+        if INTEGER_DATATYPES.iter().any(|i| dt == **i) {
+            // integers share the value space of decimals,
+            // but not their lexical space (e.g. "2.0" is not a valid integer)
+            canonical_integer(txt)?;
+            Some((ValueSpace::Decimal, canonical_decimal(txt)?))
+        } else if dt == xsd::iri::decimal {
+            Some((ValueSpace::Decimal, canonical_decimal(txt)?))
+        } else if dt == xsd::iri::boolean {
+            Some((ValueSpace::Boolean, canonical_boolean(txt)?.to_string()))
+        } else {
+            None
+        }
+    }
+}
+
+/// The value spaces supported by [`Literal::value_eq`](struct.Literal.html#method.value_eq).
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum ValueSpace {
+    Decimal,
+    Boolean,
+}
+
+/// Split an optional leading sign from `txt`,
+/// returning whether the sign was negative.
+fn split_sign(txt: &str) -> (bool, &str) {
+    match txt.as_bytes().first() {
+        Some(b'-') => (true, &txt[1..]),
+        Some(b'+') => (false, &txt[1..]),
+        _ => (false, txt),
+    }
+}
+
+fn canonical_integer(txt: &str) -> Option<String> {
+    let (negative, digits) = split_sign(txt);
+    if digits.is_empty() || !digits.bytes().all(|b| b.is_ascii_digit()) {
+        return None;
+    }
+    let digits = digits.trim_start_matches('0');
+    Some(if digits.is_empty() {
+        "0".to_string()
+    } else if negative {
+        format!("-{}", digits)
+    } else {
+        digits.to_string()
+    })
+}
+
+fn canonical_decimal(txt: &str) -> Option<String> {
+    let (negative, unsigned) = split_sign(txt);
+    let (int, frac) = match unsigned.find('.') {
+        Some(i) => (&unsigned[..i], &unsigned[i + 1..]),
+        None => (unsigned, ""),
+    };
+    if (int.is_empty() && frac.is_empty())
+        || !int.bytes().chain(frac.bytes()).all(|b| b.is_ascii_digit())
+    {
+        return None;
+    }
+    let int = int.trim_start_matches('0');
+    let frac = frac.trim_end_matches('0');
+    let mut canonical = String::with_capacity(txt.len() + 1);
+    if negative && !(int.is_empty() && frac.is_empty()) {
+        canonical.push('-');
+    }
+    canonical.push_str(if int.is_empty() { "0" } else { int });
+    if !frac.is_empty() {
+        canonical.push('.');
+        canonical.push_str(frac);
+    }
+    Some(canonical)
+}
+
+fn canonical_boolean(txt: &str) -> Option<&'static str> {
+    match txt {
+        "true" | "1" => Some("true"),
+        "false" | "0" => Some("false"),
+        _ => None,
+    }
+}
+
+/// Apply the case conventions of BCP47 to `tag`:
+/// everything is lowercase, except two-letter subtags (regions), which are uppercase,
+/// and four-letter subtags (scripts), which are titlecase,
+/// unless they are the first subtag or occur after a singleton.
+fn canonical_lang_case(tag: &str) -> String {
+    let mut res = String::with_capacity(tag.len());
+    let mut after_singleton = false;
+    for (i, subtag) in tag.split('-').enumerate() {
+        if i > 0 {
+            res.push('-');
+        }
+        let special = i > 0 && !after_singleton;
+        match subtag.len() {
+            2 if special => res.push_str(&subtag.to_ascii_uppercase()),
+            4 if special => {
+                res.push_str(&subtag[..1].to_ascii_uppercase());
+                res.push_str(&subtag[1..].to_ascii_lowercase());
+            }
+            n => {
+                res.push_str(&subtag.to_ascii_lowercase());
+                after_singleton = after_singleton || n == 1;
+            }
+        }
+    }
+    res
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn canon(txt: &str, dt: &Iri<&'static str>) -> Option<String> {
+        Literal::<&str>::new_dt(txt, dt)
+            .canonicalized()
+            .map(|lit| lit.txt().to_string())
+    }
+
+    #[test]
+    fn integer() {
+        let dt = &xsd::iri::integer;
+        assert_eq!(canon("+5", dt), Some("5".into()));
+        assert_eq!(canon("-005", dt), Some("-5".into()));
+        assert_eq!(canon("-0", dt), Some("0".into()));
+        assert_eq!(canon("000", dt), Some("0".into()));
+        assert_eq!(canon("42", dt), None);
+        assert_eq!(canon("-42", dt), None);
+        assert_eq!(canon("4.2", dt), None);
+        assert_eq!(canon("+", dt), None);
+        assert_eq!(canon("+05", &xsd::iri::unsignedByte), Some("5".into()));
+    }
+
+    #[test]
+    fn decimal() {
+        let dt = &xsd::iri::decimal;
+        assert_eq!(canon("+5.0", dt), Some("5".into()));
+        assert_eq!(canon("05.250", dt), Some("5.25".into()));
+        assert_eq!(canon(".5", dt), Some("0.5".into()));
+        assert_eq!(canon("-0.00", dt), Some("0".into()));
+        assert_eq!(canon("5.", dt), Some("5".into()));
+        assert_eq!(canon("-1.5", dt), None);
+        assert_eq!(canon(".", dt), None);
+        assert_eq!(canon("1e3", dt), None);
+    }
+
+    #[test]
+    fn boolean() {
+        let dt = &xsd::iri::boolean;
+        assert_eq!(canon("1", dt), Some("true".into()));
+        assert_eq!(canon("0", dt), Some("false".into()));
+        assert_eq!(canon("true", dt), None);
+        assert_eq!(canon("yes", dt), None);
+    }
+
+    fn trim(txt: &str, dt: &Iri<&'static str>) -> Option<String> {
+        Literal::<&str>::new_dt(txt, dt)
+            .trim_numeric()
+            .map(|lit| lit.txt().to_string())
+    }
+
+    #[test]
+    fn trim_numeric() {
+        assert_eq!(trim(" 42 ", &xsd::iri::integer), Some("42".into()));
+        assert_eq!(trim("\t-1.5\n", &xsd::iri::decimal), Some("-1.5".into()));
+        assert_eq!(trim(" 1e3", &xsd::iri::double), Some("1e3".into()));
+        assert_eq!(trim("7 ", &xsd::iri::unsignedByte), Some("7".into()));
+        assert_eq!(trim("42", &xsd::iri::integer), None);
+        assert_eq!(trim(" 42 ", &xsd::iri::string), None);
+        assert_eq!(trim(" true ", &xsd::iri::boolean), None);
+        let lit = Literal::<&str>::new_lang(" 42 ", "en").unwrap();
+        assert!(lit.trim_numeric().is_none());
+    }
+
+    fn norm(tag: &str) -> Option<String> {
+        Literal::<&str>::new_lang("chat", tag)
+            .unwrap()
+            .normalized_lang()
+            .map(|lit| lit.lang().unwrap().to_string())
+    }
+
+    #[test]
+    fn normalized_lang() {
+        assert_eq!(norm("ZH-hant-hk"), Some("zh-Hant-HK".into()));
+        assert_eq!(norm("EN-us"), Some("en-US".into()));
+        assert_eq!(norm("FR"), Some("fr".into()));
+        assert_eq!(norm("EN-x-Ab-CDEF"), Some("en-x-ab-cdef".into()));
+        assert_eq!(
+            norm("en-a-BBB-x-Ab-CDEF"),
+            Some("en-a-bbb-x-ab-cdef".into())
+        );
+        assert_eq!(norm("zh-Hant-HK"), None);
+        assert_eq!(norm("en"), None);
+        let lit = Literal::<&str>::new_dt("chat", &xsd::iri::string);
+        assert!(lit.normalized_lang().is_none());
+    }
+
+    fn lit<'a>(txt: &'a str, dt: &'a Iri<&'static str>) -> Literal<&'a str> {
+        Literal::new_dt(txt, dt)
+    }
+
+    #[test]
+    fn value_eq() {
+        let dec = &xsd::iri::decimal;
+        assert!(lit("1.50", dec).value_eq(&lit("1.5", dec)));
+        assert!(lit("+01.5", dec).value_eq(&lit("1.500", dec)));
+        assert!(lit("-0.0", dec).value_eq(&lit("0", dec)));
+        assert!(lit("2", &xsd::iri::integer).value_eq(&lit("2.0", dec)));
+        assert!(lit(" 7 ", &xsd::iri::byte).value_eq(&lit("07", &xsd::iri::long)));
+        assert!(!lit("1.5", dec).value_eq(&lit("-1.5", dec)));
+        assert!(lit("12345678901234567890.1234567890123456789", dec)
+            .value_eq(&lit("12345678901234567890.12345678901234567890", dec)));
+        assert!(!lit("12345678901234567890.1234567890123456789", dec)
+            .value_eq(&lit("12345678901234567890.1234567890123456788", dec)));
+        assert!(lit("1", &xsd::iri::boolean).value_eq(&lit("true", &xsd::iri::boolean)));
+        assert!(!lit("1", &xsd::iri::boolean).value_eq(&lit("1", dec)));
+        // unsupported or ill-typed literals fall back to literal equality
+        assert!(!lit("1.50", &xsd::iri::double).value_eq(&lit("1.5", &xsd::iri::double)));
+        assert!(lit("foo", dec).value_eq(&lit("foo", dec)));
+        assert!(!lit("1.5", &xsd::iri::string).value_eq(&lit("1.5", dec)));
+        let int = &xsd::iri::integer;
+        assert!(!lit("2.0", int).value_eq(&lit("2", int)));
+        assert!(!lit("2.0", int).value_eq(&lit("2.0", dec)));
+        assert!(lit("2.0", int).value_eq(&lit("2.0", int)));
+    }
+
+    #[test]
+    fn unsupported() {
+        assert_eq!(canon("+5", &xsd::iri::string), None);
+        assert_eq!(canon("+5.0E0", &xsd::iri::double), None);
+        let lit = Literal::<&str>::new_lang("+5", "en").unwrap();
+        assert!(lit.canonicalized().is_none());
+    }
+}
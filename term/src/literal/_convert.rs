@@ -166,6 +166,106 @@ impl_from_literal!(f64);
 impl_from_literal!(bool);
 impl_from_literal!(String);
 
+/// Conversion of Rust values into RDF literals,
+/// using the canonical XSD datatype of their value space.
+///
+/// This complements [`AsLiteral`](trait.AsLiteral.html),
+/// which maps each Rust type to the XSD datatype
+/// having exactly the same value space (e.g. `i32` to `xsd:int`).
+/// `ToLiteral`, on the other hand, maps all integer types to `xsd:integer`
+/// and all floating point types to `xsd:double`,
+/// which is how most RDF data expresses numbers.
+///
+/// NaN and infinite values are mapped to the special lexical forms
+/// `NaN`, `INF` and `-INF` of `xsd:double`.
+pub trait ToLiteral<TD: TermData> {
+    /// Create an RDF literal, representing `self`.
+    fn to_literal(&self) -> Literal<TD>;
+
+    /// Blanked implementation to directly get a term.
+    fn to_term(&self) -> Term<TD> {
+        self.to_literal().into()
+    }
+}
+
+macro_rules! impl_to_literal {
+    ($ty:ty, $iri:expr) => {
+        impl<TD> $crate::literal::ToLiteral<TD> for $ty
+        where
+            TD: $crate::TermData + From<String> + From<&'static str>,
+        {
+            fn to_literal(&self) -> Literal<TD> {
+                $crate::literal::Literal::new_dt(self.to_string(), &$iri)
+            }
+        }
+    };
+}
+
+impl_to_literal!(u8, xsd::iri::integer);
+impl_to_literal!(u16, xsd::iri::integer);
+impl_to_literal!(u32, xsd::iri::integer);
+impl_to_literal!(u64, xsd::iri::integer);
+impl_to_literal!(i8, xsd::iri::integer);
+impl_to_literal!(i16, xsd::iri::integer);
+impl_to_literal!(i32, xsd::iri::integer);
+impl_to_literal!(i64, xsd::iri::integer);
+impl_to_literal!(bool, xsd::iri::boolean);
+impl_to_literal!(String, xsd::iri::string);
+
+impl<TD> ToLiteral<TD> for f64
+where
+    TD: TermData + From<String> + From<&'static str>,
+{
+    fn to_literal(&self) -> Literal<TD> {
+        let txt = if self.is_nan() {
+            "NaN".to_string()
+        } else if *self == f64::INFINITY {
+            "INF".to_string()
+        } else if *self == f64::NEG_INFINITY {
+            "-INF".to_string()
+        } else {
+            self.to_string()
+        };
+        Literal::new_dt(txt, &xsd::iri::double)
+    }
+}
+
+impl<TD> ToLiteral<TD> for f32
+where
+    TD: TermData + From<String> + From<&'static str>,
+{
+    fn to_literal(&self) -> Literal<TD> {
+        if self.is_finite() {
+            // not converting to f64, which would add spurious decimals
+            Literal::new_dt(self.to_string(), &xsd::iri::double)
+        } else {
+            f64::from(*self).to_literal()
+        }
+    }
+}
+
+impl<'a, TD> ToLiteral<TD> for &'a str
+where
+    TD: TermData + From<&'a str>,
+{
+    fn to_literal(&self) -> Literal<TD> {
+        Literal::new_dt(*self, xsd::iri::string.clone_with(TD::from))
+    }
+}
+
+#[cfg(feature = "chrono")]
+impl<TD, Tz> ToLiteral<TD> for chrono::DateTime<Tz>
+where
+    TD: TermData + From<String> + From<&'static str>,
+    Tz: chrono::TimeZone,
+    Tz::Offset: std::fmt::Display,
+{
+    fn to_literal(&self) -> Literal<TD> {
+        let txt = self.to_rfc3339_opts(chrono::SecondsFormat::AutoSi, true);
+        Literal::new_dt(txt, &xsd::iri::dateTime)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -187,6 +287,67 @@ mod test {
         assert_eq!(l1, l2);
     }
 
+    #[test]
+    fn to_literal_datatypes() {
+        let lit: Literal<String> = 42_i32.to_literal();
+        assert_eq!(lit.dt(), xsd::integer);
+        assert_eq!(lit.txt(), "42");
+        let lit: Literal<String> = 42_i64.to_literal();
+        assert_eq!(lit.dt(), xsd::integer);
+        let lit: Literal<String> = 42_u32.to_literal();
+        assert_eq!(lit.dt(), xsd::integer);
+        let lit: Literal<String> = 42_u64.to_literal();
+        assert_eq!(lit.dt(), xsd::integer);
+        let lit: Literal<String> = 1.5_f32.to_literal();
+        assert_eq!(lit.dt(), xsd::double);
+        assert_eq!(lit.txt(), "1.5");
+        let lit: Literal<String> = 1.5_f64.to_literal();
+        assert_eq!(lit.dt(), xsd::double);
+        assert_eq!(lit.txt(), "1.5");
+        let lit: Literal<String> = true.to_literal();
+        assert_eq!(lit.dt(), xsd::boolean);
+        assert_eq!(lit.txt(), "true");
+        let lit: Literal<&str> = "hello".to_literal();
+        assert_eq!(lit.dt(), xsd::string);
+        let lit: Literal<String> = "hello".to_string().to_literal();
+        assert_eq!(lit.dt(), xsd::string);
+
+        let t: Term<String> = 42_i32.to_term();
+        assert_eq!(t, Literal::<&str>::new_dt("42", &xsd::iri::integer));
+    }
+
+    #[test_case(f64::NAN => "NaN" ; "nan")]
+    #[test_case(f64::INFINITY => "INF" ; "inf")]
+    #[test_case(f64::NEG_INFINITY => "-INF" ; "neg inf")]
+    #[test_case(-0.25 => "-0.25" ; "finite")]
+    fn to_literal_special_doubles(f: f64) -> String {
+        let lit: Literal<String> = f.to_literal();
+        assert_eq!(lit.dt(), xsd::double);
+        lit.txt().clone()
+    }
+
+    #[test]
+    fn to_literal_special_floats() {
+        let lit: Literal<String> = f32::NEG_INFINITY.to_literal();
+        assert_eq!(lit.txt(), "-INF");
+        let lit: Literal<String> = f32::NAN.to_literal();
+        assert_eq!(lit.txt(), "NaN");
+    }
+
+    #[cfg(feature = "chrono")]
+    #[test]
+    fn to_literal_datetime() {
+        use chrono::{NaiveDate, TimeZone, Utc};
+        let naive = NaiveDate::from_ymd_opt(2020, 3, 14)
+            .unwrap()
+            .and_hms_opt(15, 9, 26)
+            .unwrap();
+        let d = Utc.from_utc_datetime(&naive);
+        let lit: Literal<String> = d.to_literal();
+        assert_eq!(lit.dt(), xsd::dateTime);
+        assert_eq!(lit.txt(), "2020-03-14T15:09:26Z");
+    }
+
     #[test_case("0" => Ok(0) ; "zero")]
     #[test_case("-10" => Ok(-10) ; "minus ten")]
     #[test_case("10" => Ok(10) ; "ten")]
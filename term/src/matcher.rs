@@ -15,6 +15,8 @@
 //!
 
 use super::*;
+use std::cell::RefCell;
+use std::collections::HashMap;
 
 pub use super::_graph_name_matcher::*;
 
@@ -32,6 +34,14 @@ pub trait TermMatcher {
     fn matches<T>(&self, t: &Term<T>) -> bool
     where
         T: TermData;
+
+    /// Whether this matcher matches only one term
+    /// (see [`constant`](#tymethod.constant)).
+    ///
+    /// This is typically used to decide which index to use for a query.
+    fn is_bound(&self) -> bool {
+        self.constant().is_some()
+    }
 }
 
 /// A universal matcher: it matches any term or graph name (even the default graph).
@@ -92,6 +102,48 @@ where
     }
 }
 
+/// A matcher matching either any term, or only the terms of a given list.
+///
+/// This is convenient to build matchers from optional filters,
+/// through its `From<Option<Vec<T>>>` implementation.
+pub enum AnyOrOneOf<T> {
+    /// Match any term.
+    Any,
+    /// Match only the terms of this list.
+    OneOf(Vec<T>),
+}
+
+impl<T> From<Option<Vec<T>>> for AnyOrOneOf<T> {
+    fn from(other: Option<Vec<T>>) -> AnyOrOneOf<T> {
+        match other {
+            None => AnyOrOneOf::Any,
+            Some(v) => AnyOrOneOf::OneOf(v),
+        }
+    }
+}
+
+impl<U> TermMatcher for AnyOrOneOf<Term<U>>
+where
+    U: TermData,
+{
+    type TermData = U;
+    fn constant(&self) -> Option<&Term<Self::TermData>> {
+        match self {
+            AnyOrOneOf::Any => None,
+            AnyOrOneOf::OneOf(v) => TermMatcher::constant(&v[..]),
+        }
+    }
+    fn matches<T>(&self, t: &Term<T>) -> bool
+    where
+        T: TermData,
+    {
+        match self {
+            AnyOrOneOf::Any => true,
+            AnyOrOneOf::OneOf(v) => TermMatcher::matches(&v[..], t),
+        }
+    }
+}
+
 impl<U> TermMatcher for Term<U>
 where
     U: TermData,
@@ -184,6 +236,69 @@ impl<F: Fn(&RefTerm) -> bool> TermMatcher for F {
     }
 }
 
+/// A matcher binding variables to the terms it matches.
+///
+/// If the wrapped term is a variable,
+/// it matches any term as long as the variable is not bound (and binds it),
+/// and then only the term the variable is bound to.
+/// Any other term matches only itself.
+///
+/// Bindings are stored in a map shared through a `RefCell`,
+/// so that the matchers used at different positions of a pattern
+/// (e.g. the subject and the object of a triple) bind variables consistently.
+/// Bindings are never undone:
+/// if a candidate fails to match on a later position,
+/// the bindings recorded on earlier positions remain,
+/// so the map should be cleared before matching another candidate.
+pub struct BindingMatcher<'a, U>
+where
+    U: TermData,
+{
+    term: Term<U>,
+    bindings: &'a RefCell<HashMap<String, BoxTerm>>,
+}
+
+impl<'a, U> BindingMatcher<'a, U>
+where
+    U: TermData,
+{
+    /// Build a matcher for `term`, recording variable bindings into `bindings`.
+    pub fn new(term: Term<U>, bindings: &'a RefCell<HashMap<String, BoxTerm>>) -> Self {
+        BindingMatcher { term, bindings }
+    }
+}
+
+impl<'a, U> TermMatcher for BindingMatcher<'a, U>
+where
+    U: TermData,
+{
+    type TermData = U;
+    fn constant(&self) -> Option<&Term<Self::TermData>> {
+        match &self.term {
+            Term::Variable(_) => None,
+            t => Some(t),
+        }
+    }
+    fn matches<T>(&self, t: &Term<T>) -> bool
+    where
+        T: TermData,
+    {
+        match &self.term {
+            Term::Variable(v) => {
+                let mut bindings = self.bindings.borrow_mut();
+                match bindings.get(&*v.value()) {
+                    Some(bound) => bound == t,
+                    None => {
+                        bindings.insert(v.value().to_string(), t.into());
+                        true
+                    }
+                }
+            }
+            term => term == t,
+        }
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
@@ -242,6 +357,39 @@ mod test {
         assert!(!TermMatcher::matches(&m, &t2));
     }
 
+    #[test]
+    fn test_aoo_any_as_matcher() {
+        let m = AnyOrOneOf::<BoxTerm>::from(None);
+        let t1 = RcTerm::new_iri_suffixed("http://champin.net/#", "pa").unwrap();
+
+        let mc = TermMatcher::constant(&m);
+        assert!(mc.is_none());
+        assert!(TermMatcher::matches(&m, &t1));
+    }
+
+    #[test]
+    fn test_aoo_one_of_as_matcher() {
+        let m = AnyOrOneOf::from(Some(vec![
+            BoxTerm::new_iri("http://champin.net/#pa").unwrap(),
+            BoxTerm::new_iri("http://example.org/").unwrap(),
+        ]));
+        // comparing to a term using a different term data, and differently cut,
+        // to make the test less obvious
+        let t1 = RcTerm::new_iri_suffixed("http://champin.net/#", "pa").unwrap();
+        let t2 = RcTerm::new_iri("http://example.org/").unwrap();
+        let t3 = RcTerm::new_iri("http://example.org/other").unwrap();
+
+        let mc = TermMatcher::constant(&m);
+        assert!(mc.is_none());
+        assert!(TermMatcher::matches(&m, &t1));
+        assert!(TermMatcher::matches(&m, &t2));
+        assert!(!TermMatcher::matches(&m, &t3));
+
+        let m = AnyOrOneOf::OneOf(vec![BoxTerm::new_iri("http://champin.net/#pa").unwrap()]);
+        let mc = TermMatcher::constant(&m);
+        assert_eq!(mc.unwrap(), &t1);
+    }
+
     #[test]
     fn test_array2_as_matcher() {
         let m = [
@@ -288,6 +436,17 @@ mod test {
         assert!(!TermMatcher::matches(&m[..], &t1));
     }
 
+    #[test]
+    fn test_is_bound() {
+        let t = BoxTerm::new_iri("http://champin.net/#pa").unwrap();
+        assert!(!TermMatcher::is_bound(&ANY));
+        assert!(TermMatcher::is_bound(&t));
+        assert!(TermMatcher::is_bound(&AnyOrExactly::Exactly(t.clone())));
+        assert!(!TermMatcher::is_bound(&AnyOrExactly::<BoxTerm>::Any));
+        assert!(TermMatcher::is_bound(&[t.clone()][..]));
+        assert!(!TermMatcher::is_bound(&[t.clone(), t][..]));
+    }
+
     #[test]
     fn test_func_as_matcher() {
         let t1 = RcTerm::new_iri_suffixed("http://champin.net/#", "pa").unwrap();
@@ -298,4 +457,37 @@ mod test {
         assert!(TermMatcher::matches(&m, &t1));
         assert!(!TermMatcher::matches(&m, &t2));
     }
+
+    #[test]
+    fn test_binding_matcher() {
+        let bindings = RefCell::new(HashMap::new());
+        let x = BoxTerm::new_variable("x").unwrap();
+        let p = BoxTerm::new_iri("http://example.org/p").unwrap();
+        let a = RcTerm::new_iri("http://example.org/a").unwrap();
+        let b = RcTerm::new_iri("http://example.org/b").unwrap();
+        let rp = RcTerm::from(&p);
+
+        // pattern ?x <p> ?x
+        let ms = BindingMatcher::new(x.clone(), &bindings);
+        let mp = BindingMatcher::new(p.clone(), &bindings);
+        let mo = BindingMatcher::new(x, &bindings);
+        assert!(TermMatcher::constant(&ms).is_none());
+        assert_eq!(TermMatcher::constant(&mp), Some(&p));
+        let matches = |t: [&RcTerm; 3]| ms.matches(t[0]) && mp.matches(t[1]) && mo.matches(t[2]);
+
+        assert!(matches([&a, &rp, &a]));
+        assert_eq!(bindings.borrow()["x"], a);
+        // ?x is already bound to a
+        assert!(!matches([&b, &rp, &b]));
+
+        bindings.borrow_mut().clear();
+        assert!(!matches([&a, &rp, &b]));
+        // the binding made on the subject remains
+        assert_eq!(bindings.borrow()["x"], a);
+
+        bindings.borrow_mut().clear();
+        assert!(matches([&b, &rp, &b]));
+        assert_eq!(bindings.borrow()["x"], b);
+        assert_eq!(bindings.borrow().len(), 1);
+    }
 }
@@ -2,6 +2,8 @@
 //!
 //! This module provides:
 //! * the [`Namespace`](struct.Namespace.html) type for defining custom namespace;
+//! * the [`PrefixMap`](struct.PrefixMap.html) type for expanding CURIEs,
+//!   and the [`WELL_KNOWN_PREFIXES`](static.WELL_KNOWN_PREFIXES.html) of common namespaces;
 //! * modules corresponding to the most common namespaces.
 //!
 //! # Example
@@ -15,7 +17,10 @@
 //! //g.insert(&s_name, &rdfs::range, &xsd::string);
 //! ```
 
-use crate::{iri::is_valid_iri_ref, Result, Term, TermData, TermError};
+use crate::{
+    iri::{is_absolute_iri_ref, is_valid_iri_ref, Iri, GEN_DELIMS},
+    Result, Term, TermData, TermError,
+};
 
 /// A custom namespace.
 #[derive(Clone, Debug)]
@@ -43,8 +48,174 @@ impl<T: TermData> Namespace<T> {
     {
         Term::new_iri_suffixed(self.0.clone(), suffix)
     }
+
+    /// Build an IRI term by appending `suffix` to this namespace,
+    /// with stricter checks than [`get`](#method.get).
+    ///
+    /// This is meant for suffixes coming from untrusted input:
+    /// an error is returned if the concatenation is not a valid *absolute* IRI,
+    /// or if `suffix` contains a [`gen-delim`](../iri/constant.GEN_DELIMS.html)
+    /// that would make the resulting IRI escape this namespace.
+    pub fn get_checked<U>(&self, suffix: U) -> Result<Term<T>>
+    where
+        U: AsRef<str>,
+        T: From<U>,
+    {
+        let sf = suffix.as_ref();
+        let full = format!("{}{}", self.0.as_ref(), sf);
+        if sf.contains(GEN_DELIMS) {
+            return Err(TermError::RejectedIri {
+                iri: full,
+                reason: "suffix escapes the namespace".to_string(),
+            });
+        }
+        if !is_absolute_iri_ref(&full) {
+            return Err(TermError::RejectedIri {
+                iri: full,
+                reason: "not an absolute IRI".to_string(),
+            });
+        }
+        Ok(Term::new_iri_suffixed_unchecked(
+            self.0.clone(),
+            suffix,
+            true,
+        ))
+    }
 }
 
+impl<T: TermData> AsRef<str> for Namespace<T> {
+    fn as_ref(&self) -> &str {
+        self.0.as_ref()
+    }
+}
+
+/// A mapping from prefixes to namespaces,
+/// used to expand CURIEs such as `rdf:type` into full IRIs.
+#[derive(Clone, Debug, Default)]
+pub struct PrefixMap(Vec<(String, Namespace<String>)>);
+
+impl PrefixMap {
+    /// Build an empty prefix map.
+    pub fn new() -> PrefixMap {
+        PrefixMap(Vec::new())
+    }
+
+    /// Map `prefix` to the namespace `iri`,
+    /// replacing any namespace previously associated to `prefix`.
+    ///
+    /// `iri` must be a valid IRI, otherwise this method returns an error.
+    pub fn insert<P, I>(&mut self, prefix: P, iri: I) -> Result<()>
+    where
+        P: Into<String>,
+        I: Into<String>,
+    {
+        let prefix = prefix.into();
+        let ns = Namespace::new(iri.into())?;
+        match self.0.iter_mut().find(|(p, _)| *p == prefix) {
+            Some(entry) => entry.1 = ns,
+            None => self.0.push((prefix, ns)),
+        }
+        Ok(())
+    }
+
+    /// The namespace associated to `prefix`, if any.
+    pub fn get(&self, prefix: &str) -> Option<&Namespace<String>> {
+        self.0.iter().find(|(p, _)| p == prefix).map(|(_, ns)| ns)
+    }
+
+    /// Iter over the (prefix, namespace) pairs of this map.
+    pub fn iter(&self) -> impl Iterator<Item = (&str, &Namespace<String>)> {
+        self.0.iter().map(|(p, ns)| (p.as_str(), ns))
+    }
+
+    /// Expand the given CURIE into a full IRI.
+    ///
+    /// Return an error if `curie` has no prefix,
+    /// if its prefix is unknown,
+    /// or if the expansion is not a valid IRI.
+    pub fn expand(&self, curie: &str) -> Result<Iri<String>> {
+        let colon = curie
+            .find(':')
+            .ok_or_else(|| TermError::InvalidCurie(curie.to_string()))?;
+        let (prefix, local) = (&curie[..colon], &curie[colon + 1..]);
+        let ns = self
+            .get(prefix)
+            .ok_or_else(|| TermError::UnknownPrefix(prefix.to_string()))?;
+        Iri::new_suffixed(ns.0.clone(), local)
+    }
+
+    /// Expand all the given CURIEs into full IRIs.
+    ///
+    /// Stop at the first CURIE that can not be expanded,
+    /// and return the corresponding error (see [`expand`](#method.expand)).
+    pub fn expand_all<'a, I>(&self, curies: I) -> Result<Vec<Iri<String>>>
+    where
+        I: IntoIterator<Item = &'a str>,
+    {
+        curies.into_iter().map(|c| self.expand(c)).collect()
+    }
+
+    /// Abbreviate `iri` into a CURIE, using the longest namespace of this map
+    /// that `iri` starts with.
+    ///
+    /// Return `None` if no namespace of this map is a prefix of `iri`.
+    /// NB: the suffix of the returned CURIE is not checked against any particular syntax.
+    pub fn compact(&self, iri: &str) -> Option<String> {
+        self.0
+            .iter()
+            .filter(|(_, ns)| iri.starts_with(ns.0.as_str()))
+            .max_by_key(|(_, ns)| ns.0.len())
+            .map(|(prefix, ns)| format!("{}:{}", prefix, &iri[ns.0.len()..]))
+    }
+
+    /// Build a prefix map for the given namespaces.
+    ///
+    /// Namespaces listed in [`WELL_KNOWN_PREFIXES`](static.WELL_KNOWN_PREFIXES.html)
+    /// get their conventional prefix;
+    /// the other ones get the prefixes `ns0`, `ns1`, ... in the order they are given
+    /// (typically, from the most used to the least used).
+    /// Duplicate or invalid namespaces are ignored.
+    pub fn auto_from_namespaces<I, S>(namespaces: I) -> PrefixMap
+    where
+        I: IntoIterator<Item = S>,
+        S: AsRef<str>,
+    {
+        let mut pm = PrefixMap::new();
+        let mut next = 0;
+        for ns in namespaces {
+            let ns = ns.as_ref();
+            if !is_valid_iri_ref(ns) || pm.iter().any(|(_, n)| n.0 == ns) {
+                continue;
+            }
+            let prefix = match WELL_KNOWN_PREFIXES.iter().find(|(_, iri)| *iri == ns) {
+                Some((prefix, _)) => prefix.to_string(),
+                None => {
+                    next += 1;
+                    format!("ns{}", next - 1)
+                }
+            };
+            pm.0.push((prefix, Namespace(ns.to_string())));
+        }
+        pm
+    }
+}
+
+/// Conventional prefixes of widely used namespaces.
+pub static WELL_KNOWN_PREFIXES: &[(&str, &str)] = &[
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+    ("xml", "http://www.w3.org/XML/1998/namespace"),
+    ("skos", "http://www.w3.org/2004/02/skos/core#"),
+    ("sh", "http://www.w3.org/ns/shacl#"),
+    ("prov", "http://www.w3.org/ns/prov#"),
+    ("dc", "http://purl.org/dc/elements/1.1/"),
+    ("dcterms", "http://purl.org/dc/terms/"),
+    ("foaf", "http://xmlns.com/foaf/0.1/"),
+    ("schema", "http://schema.org/"),
+];
+
 /// Helper for creating a "namespace module"
 /// defining a set of terms within a given IRI space.
 ///
@@ -284,6 +455,7 @@ pub mod owl {
         InverseFunctionalProperty,
         IrreflexiveProperty,
         ObjectProperty,
+        Ontology,
         SymmetricProperty,
         TransitiveProperty,
         // Properties
@@ -295,6 +467,7 @@ pub mod owl {
         distinctMembers,
         equivalentClass,
         equivalentProperty,
+        imports,
         intersectionOf,
         inverseOf,
         maxCardinality,
@@ -346,4 +519,86 @@ mod test {
         let ns1 = Namespace::new("http://schema.org/").unwrap();
         assert!(ns1.get("name ").is_err());
     }
+
+    #[test]
+    fn test_get_checked() {
+        let ns1 = Namespace::new("http://schema.org/").unwrap();
+        assert_eq!(ns1.get_checked("City").unwrap(), ns1.get("City").unwrap());
+        assert!(ns1.get_checked("bad name").is_err());
+        assert!(ns1.get_checked("a/b").is_err());
+        assert!(ns1.get_checked("x#y").is_err());
+        assert!(ns1.get("a/b").is_ok());
+
+        let rel = Namespace::new("foo/").unwrap();
+        assert!(rel.get("City").is_ok());
+        assert!(rel.get_checked("City").is_err());
+    }
+
+    #[test]
+    fn test_prefix_map_auto_from_namespaces() {
+        let pm = PrefixMap::auto_from_namespaces(vec![
+            "http://example.org/ns#",
+            rdf::PREFIX,
+            "http://example.org/other/",
+            "http://example.org/ns#",
+            "not an IRI",
+            xsd::PREFIX,
+        ]);
+        let pairs: Vec<_> = pm.iter().map(|(p, ns)| (p, ns.0.as_str())).collect();
+        assert_eq!(
+            pairs,
+            vec![
+                ("ns0", "http://example.org/ns#"),
+                ("rdf", rdf::PREFIX),
+                ("ns1", "http://example.org/other/"),
+                ("xsd", xsd::PREFIX),
+            ]
+        );
+    }
+
+    #[test]
+    fn test_prefix_map_expand_all() {
+        let mut pm = PrefixMap::new();
+        pm.insert("rdf", rdf::PREFIX).unwrap();
+        pm.insert("xsd", xsd::PREFIX).unwrap();
+        let iris = pm.expand_all(vec!["rdf:type", "xsd:string"]).unwrap();
+        assert_eq!(iris.len(), 2);
+        assert_eq!(iris[0], rdf::iri::type_);
+        assert_eq!(iris[1], xsd::iri::string);
+    }
+
+    #[test]
+    fn test_prefix_map_unknown_prefix() {
+        let mut pm = PrefixMap::new();
+        pm.insert("rdf", rdf::PREFIX).unwrap();
+        match pm.expand_all(vec!["rdf:type", "foo:bar", "baz:qux"]) {
+            Err(TermError::UnknownPrefix(p)) => assert_eq!(p, "foo"),
+            other => panic!("unexpected {:?}", other),
+        }
+        match pm.expand("type") {
+            Err(TermError::InvalidCurie(c)) => assert_eq!(c, "type"),
+            other => panic!("unexpected {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_prefix_map_compact() {
+        let mut pm = PrefixMap::new();
+        pm.insert("ex", "http://example.org/").unwrap();
+        pm.insert("exv", "http://example.org/vocab#").unwrap();
+        assert_eq!(pm.compact("http://example.org/a").unwrap(), "ex:a");
+        assert_eq!(pm.compact("http://example.org/vocab#b").unwrap(), "exv:b");
+        assert_eq!(pm.compact("http://example.org/").unwrap(), "ex:");
+        assert!(pm.compact("http://example.com/a").is_none());
+    }
+
+    #[test]
+    fn test_prefix_map_insert_replaces() {
+        let mut pm = PrefixMap::new();
+        pm.insert("ex", "http://example.org/").unwrap();
+        pm.insert("ex", "http://example.com/").unwrap();
+        assert_eq!(pm.iter().count(), 1);
+        assert_eq!(&pm.expand("ex:a").unwrap(), "http://example.com/a");
+        assert!(pm.insert("bad", "http://example.org/ x").is_err());
+    }
 }
@@ -0,0 +1,145 @@
+//! A set of terms, accepting terms with any kind of [`TermData`](../trait.TermData.html).
+
+use std::collections::hash_map::RandomState;
+use std::collections::HashMap;
+use std::hash::BuildHasher;
+
+use crate::{BoxTerm, Term, TermData};
+
+/// A set of distinct terms.
+///
+/// Terms are stored as [`BoxTerm`](../type.BoxTerm.html)s,
+/// but can be inserted and looked up with any type of [`TermData`](../trait.TermData.html).
+/// The set relies on the `Hash` implementation of [`Term`](../enum.Term.html)
+/// being independent of the underlying data,
+/// so distinctness is the same as with `Term`'s `Eq`
+/// (e.g. language tags are compared case-insensitively).
+#[derive(Clone, Debug, Default)]
+pub struct TermSet {
+    buckets: HashMap<u64, Vec<BoxTerm>>,
+    hasher: RandomState,
+    len: usize,
+}
+
+impl TermSet {
+    /// Build an empty set.
+    pub fn new() -> TermSet {
+        TermSet::default()
+    }
+
+    /// The number of terms in this set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Whether this set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Insert a copy of `t` in this set.
+    ///
+    /// Return `true` iff `t` was not already in the set.
+    pub fn insert<T>(&mut self, t: &Term<T>) -> bool
+    where
+        T: TermData,
+    {
+        let bucket = self.buckets.entry(hash_term(&self.hasher, t)).or_default();
+        if bucket.iter().any(|b| b == t) {
+            return false;
+        }
+        bucket.push(t.into());
+        self.len += 1;
+        true
+    }
+
+    /// Whether `t` is in this set.
+    pub fn contains<T>(&self, t: &Term<T>) -> bool
+    where
+        T: TermData,
+    {
+        match self.buckets.get(&hash_term(&self.hasher, t)) {
+            Some(bucket) => bucket.iter().any(|b| b == t),
+            None => false,
+        }
+    }
+
+    /// Remove `t` from this set.
+    ///
+    /// Return `true` iff `t` was in the set.
+    pub fn remove<T>(&mut self, t: &Term<T>) -> bool
+    where
+        T: TermData,
+    {
+        let key = hash_term(&self.hasher, t);
+        let bucket = match self.buckets.get_mut(&key) {
+            Some(bucket) => bucket,
+            None => return false,
+        };
+        match bucket.iter().position(|b| b == t) {
+            None => false,
+            Some(i) => {
+                bucket.swap_remove(i);
+                if bucket.is_empty() {
+                    self.buckets.remove(&key);
+                }
+                self.len -= 1;
+                true
+            }
+        }
+    }
+
+    /// Iter over the terms of this set, in no particular order.
+    pub fn iter(&self) -> impl Iterator<Item = &BoxTerm> {
+        self.buckets.values().flatten()
+    }
+}
+
+impl<T> Extend<Term<T>> for TermSet
+where
+    T: TermData,
+{
+    fn extend<I: IntoIterator<Item = Term<T>>>(&mut self, iter: I) {
+        for t in iter {
+            self.insert(&t);
+        }
+    }
+}
+
+fn hash_term<T: TermData>(hasher: &RandomState, t: &Term<T>) -> u64 {
+    hasher.hash_one(t)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::ns::xsd;
+    use crate::{RcTerm, RefTerm};
+
+    #[test]
+    fn lang_tag_case_insensitive() {
+        let mut set = TermSet::new();
+        assert!(set.insert(&BoxTerm::new_literal_lang("x", "EN").unwrap()));
+        assert!(set.contains(&RefTerm::new_literal_lang("x", "en").unwrap()));
+        assert!(!set.insert(&RcTerm::new_literal_lang("x", "en").unwrap()));
+        assert!(!set.contains(&RefTerm::new_literal_lang("x", "fr").unwrap()));
+        assert_eq!(set.len(), 1);
+    }
+
+    #[test]
+    fn heterogeneous_term_data() {
+        let mut set = TermSet::new();
+        set.insert(&RefTerm::new_iri("http://example.org/a").unwrap());
+        set.insert(&RcTerm::new_iri_suffixed("http://example.org/", "a").unwrap());
+        set.insert(&xsd::string);
+        set.insert(&RefTerm::new_literal_dt("a", xsd::string).unwrap());
+        set.insert(&RefTerm::new_bnode("a").unwrap());
+        assert_eq!(set.len(), 4);
+        assert!(set.contains(&BoxTerm::new_iri("http://www.w3.org/2001/XMLSchema#string").unwrap()));
+        assert_eq!(set.iter().count(), 4);
+
+        assert!(set.remove(&BoxTerm::new_bnode("a").unwrap()));
+        assert!(!set.remove(&BoxTerm::new_bnode("a").unwrap()));
+        assert_eq!(set.len(), 3);
+    }
+}
@@ -1,6 +1,6 @@
 use super::Term::*;
 use super::*;
-use crate::ns::xsd;
+use crate::ns::{xsd, Namespace};
 
 fn h<H: std::hash::Hash>(x: &H) -> u64 {
     use std::hash::Hasher;
@@ -467,6 +467,46 @@ fn term_similar_but_not_eq() {
     assert_ne!(h(&t3), h(&t4));
 }
 
+#[test]
+fn eq_interned_and_not_interned() {
+    use crate::factory::{ArcTermFactory, RcTermFactory, TermFactory};
+
+    let mut f = RcTermFactory::default();
+    let i1 = f.iri("http://champin.net/").unwrap();
+    let i2 = f.iri("http://champin.net/").unwrap();
+    if let (Iri(iri1), Iri(iri2)) = (&i1, &i2) {
+        assert!(Rc::ptr_eq(&iri1.ns, &iri2.ns));
+    }
+    assert_eq!(i1, i2);
+    let i3 = RcTerm::new_iri(Rc::from("http://champin.net/")).unwrap();
+    assert_eq!(i1, i3);
+    assert_eq!(i3, i1);
+    let i4 = f.iri("http://champin.net/#").unwrap();
+    assert_ne!(i1, i4);
+
+    let mut f = ArcTermFactory::default();
+    let l1 = f.literal_lang("chat", "fr").unwrap();
+    let l2 = f.literal_lang("chat", "fr").unwrap();
+    let l3 = ArcTerm::new_literal_lang(Arc::from("chat"), Arc::from("FR")).unwrap();
+    let l4 = f.literal_lang("chat", "en").unwrap();
+    assert_eq!(l1, l2);
+    assert_eq!(l1, l3);
+    assert_ne!(l1, l4);
+
+    let b1 = f.bnode("b1").unwrap();
+    let b2 = f.bnode("b1").unwrap();
+    let b3 = ArcTerm::new_bnode(Arc::from("b1")).unwrap();
+    assert_eq!(b1, b2);
+    assert_eq!(b1, b3);
+
+    let v1 = f.variable("v1").unwrap();
+    let v2 = f.variable("v1").unwrap();
+    let v3 = ArcTerm::new_variable(Arc::from("v1")).unwrap();
+    assert_eq!(v1, v2);
+    assert_eq!(v1, v3);
+    assert_ne!(v1, ArcTerm::new_variable(Arc::from("v2")).unwrap());
+}
+
 #[test]
 fn convert() {
     let t1 = StaticTerm::new_iri("http://champin.net/#pa").unwrap();
@@ -476,6 +516,151 @@ fn convert() {
     let _t = RefTerm::from(&t4);
 }
 
+#[test]
+fn replace_iri_prefix() {
+    let t = RefTerm::new_iri("http://old/X").unwrap();
+    let exp = BoxTerm::new_iri("http://new/X").unwrap();
+    assert_eq!(
+        t.replace_iri_prefix("http://old/", "http://new/"),
+        Some(exp)
+    );
+    assert_eq!(t.replace_iri_prefix("http://other/", "http://new/"), None);
+
+    let dt = RefTerm::new_iri("http://old/dt").unwrap();
+    let t = RefTerm::new_literal_dt("foo", dt).unwrap();
+    let exp = BoxTerm::new_literal_dt("foo", BoxTerm::new_iri("http://new/dt").unwrap()).unwrap();
+    assert_eq!(
+        t.replace_iri_prefix("http://old/", "http://new/"),
+        Some(exp)
+    );
+
+    let t = RefTerm::new_literal_lang("http://old/X", "en").unwrap();
+    assert_eq!(t.replace_iri_prefix("http://old/", "http://new/"), None);
+    let t = RefTerm::new_bnode("b").unwrap();
+    assert_eq!(t.replace_iri_prefix("", "http://new/"), None);
+}
+
+#[test]
+fn abbreviate() {
+    let t = RefTerm::new_iri("http://example.org/a/very/long/path/to/some#fragment").unwrap();
+    assert_eq!(t.abbreviate(100), t.to_string());
+    let abbr = t.abbreviate(40);
+    assert_eq!(abbr, "<http://example.org/...#fragment>");
+    assert!(abbr.chars().count() <= 40);
+    assert_eq!(t.abbreviate(10), "<htt...nt>");
+
+    let t = RefTerm::new_iri("http://example.org/a/very/long/path/to/resource/").unwrap();
+    assert_eq!(t.abbreviate(40), "<http://example.org/.../resource/>");
+
+    let t = RefTerm::new_literal_dt("abcdefghijklmnop", xsd::string).unwrap();
+    assert_eq!(t.abbreviate(10), "\"abc...op\"");
+}
+
+#[test]
+fn into_owned() {
+    let buffer = String::from("http://example.org/foo");
+    let t = RefTerm::new_iri(&buffer[..]).unwrap();
+    let owned = t.into_owned();
+    let buffer2 = String::from("bar");
+    let t2 = RefTerm::new_literal_lang(&buffer2[..], "en").unwrap();
+    let rc = t2.into_rc();
+    let arc = t2.into_arc();
+    drop(buffer);
+    drop(buffer2);
+
+    assert_eq!(owned, BoxTerm::new_iri("http://example.org/foo").unwrap());
+    let exp = BoxTerm::new_literal_lang("bar", "en").unwrap();
+    assert_eq!(rc, exp);
+    assert_eq!(arc, exp);
+}
+
+#[test]
+fn value_eq() {
+    let d1 = RefTerm::new_literal_dt("1.50", xsd::decimal).unwrap();
+    let d2 = BoxTerm::new_literal_dt("1.5", &xsd::decimal).unwrap();
+    assert!(d1.value_eq(&d2));
+    assert!(d1 != d2);
+    let i = RefTerm::new_iri("http://example.org/").unwrap();
+    assert!(i.value_eq(&i));
+    assert!(!i.value_eq(&d1));
+}
+
+#[test]
+fn heap_size() {
+    let i = RefTerm::new_iri("http://example.org/").unwrap();
+    assert_eq!(i.heap_size(), 19);
+    let i = RefTerm::new_iri_suffixed("http://example.org/", "foo").unwrap();
+    assert_eq!(i.heap_size(), 22);
+    assert_eq!(RefTerm::new_bnode("b1").unwrap().heap_size(), 2);
+    let l = RefTerm::new_literal_lang("chat", "fr").unwrap();
+    assert_eq!(l.heap_size(), 6);
+    let l = RefTerm::new_literal_dt("42", xsd::integer).unwrap();
+    assert_eq!(l.heap_size(), 2 + xsd::iri::integer.len());
+    assert_eq!(RefTerm::new_variable("v").unwrap().heap_size(), 1);
+}
+
+#[test]
+fn total_order() {
+    let mut terms = [
+        RefTerm::new_variable("x").unwrap(),
+        RefTerm::new_literal_lang("chat", "fr").unwrap(),
+        RefTerm::new_literal_dt("chat", xsd::string).unwrap(),
+        RefTerm::new_bnode("b").unwrap(),
+        RefTerm::new_iri("http://example.org/b").unwrap(),
+        RefTerm::new_literal_dt("42", xsd::integer).unwrap(),
+        RefTerm::new_iri_suffixed("http://example.org/", "a").unwrap(),
+        RefTerm::new_bnode("a").unwrap(),
+    ];
+    terms.sort();
+    let expected = vec![
+        "<http://example.org/a>",
+        "<http://example.org/b>",
+        "_:a",
+        "_:b",
+        "\"42\"^^<http://www.w3.org/2001/XMLSchema#integer>",
+        "\"chat\"",
+        "\"chat\"@fr",
+        "?x",
+    ];
+    let got: Vec<_> = terms.iter().map(ToString::to_string).collect();
+    assert_eq!(got, expected);
+
+    // consistent with case-insensitive equality of language tags
+    let t1 = RefTerm::new_literal_lang("chat", "en-US").unwrap();
+    let t2 = RefTerm::new_literal_lang("chat", "EN-us").unwrap();
+    assert_eq!(t1, t2);
+    assert_eq!(t1.cmp(&t2), std::cmp::Ordering::Equal);
+
+    // consistent with equality of IRIs regardless of their internal split
+    let i1 = RefTerm::new_iri("http://example.org/a").unwrap();
+    let i2 = RefTerm::new_iri_suffixed("http://example.org/", "a").unwrap();
+    assert_eq!(i1.cmp(&i2), std::cmp::Ordering::Equal);
+}
+
+#[test]
+fn term_errors() {
+    match RefTerm::new_iri("http://a.example/ b") {
+        Err(TermError::InvalidIri(iri)) => assert_eq!(iri, "http://a.example/ b"),
+        other => panic!("unexpected result {:?}", other),
+    }
+    let ns = Namespace::new("http://a.example/").unwrap();
+    match ns.get_checked("x/../y") {
+        Err(TermError::RejectedIri { iri, reason }) => {
+            assert_eq!(iri, "http://a.example/x/../y");
+            assert!(!reason.is_empty());
+        }
+        other => panic!("unexpected result {:?}", other),
+    }
+    assert!(matches!(
+        RefTerm::new_literal_lang("chat", "not a tag"),
+        Err(TermError::InvalidLanguageTag { .. })
+    ));
+    assert!(matches!(
+        RefTerm::new_bnode("not a label"),
+        Err(TermError::InvalidBlankNodeId(_))
+    ));
+}
+
 pub(crate) const POSITIVE_1CHAR_IDS: &[&str] = &[
     // PN_CHARS_BASE
     "a",
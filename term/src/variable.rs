@@ -2,10 +2,11 @@
 //! Notation3.
 //!
 
-use super::{Result, Term, TermData, TermError};
+use super::{same_txt, Result, Term, TermData, TermError};
 use crate::mown_str::MownStr;
 use lazy_static::lazy_static;
 use regex::Regex;
+use std::cmp::Ordering;
 use std::convert::TryFrom;
 use std::fmt;
 use std::io;
@@ -116,7 +117,26 @@ where
     U: TermData,
 {
     fn eq(&self, other: &Variable<U>) -> bool {
-        self.as_ref() == other.as_ref()
+        same_txt(self.as_ref(), other.as_ref())
+    }
+}
+
+impl<T, U> PartialOrd<Variable<U>> for Variable<T>
+where
+    T: TermData,
+    U: TermData,
+{
+    fn partial_cmp(&self, other: &Variable<U>) -> Option<Ordering> {
+        Some(self.as_ref().cmp(other.as_ref()))
+    }
+}
+
+impl<TD> Ord for Variable<TD>
+where
+    TD: TermData,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.as_ref().cmp(other.as_ref())
     }
 }
 